@@ -1,18 +1,110 @@
-use crate::hooks::use_batch_translation::use_batch_translation;
-use crate::services::batch_service::BatchStatus;
+use crate::components::MemoryStatsLine;
+use crate::hooks::use_batch_translation::{use_batch_translation, BatchCommand};
+use crate::services::batch_service::{
+    BatchFailure, BatchRequest, BatchStatus, DiscoveryMode, DocumentLink,
+};
+use crate::services::crawler::{parse_patterns, CrawlOptions, CrawlScope};
+use crate::services::incremental::{Changelog, DocumentChange};
+use crate::services::sitemap_service::SitemapFilter;
+use crate::types::batch_job::BatchJob;
 use leptos::*;
+use std::collections::HashSet;
 use wasm_bindgen::JsCast;
 
+/// 文档发现方式的选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiscoveryChoice {
+    IndexPage,
+    Sitemap,
+    Crawl,
+}
+
+const INPUT_CLASS: &str = "px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg dark:bg-gray-700 dark:text-white";
+
 #[component]
 pub fn BatchTranslation() -> impl IntoView {
     let batch_translation = use_batch_translation();
 
     let (index_url, set_index_url) = create_signal(String::new());
+    let (choice, set_choice) = create_signal(DiscoveryChoice::IndexPage);
+    let (path_prefix, set_path_prefix) = create_signal(String::new());
+    let (modified_since, set_modified_since) = create_signal(String::new());
+    let (respect_robots, set_respect_robots) = create_signal(true);
+    let (crawl_depth, set_crawl_depth) = create_signal(CrawlOptions::default().max_depth);
+    let (crawl_max_pages, set_crawl_max_pages) = create_signal(CrawlOptions::default().max_pages);
+    let (same_path_only, set_same_path_only) = create_signal(true);
+    let (include_patterns, set_include_patterns) = create_signal(String::new());
+    let (exclude_patterns, set_exclude_patterns) = create_signal(String::new());
+
+    // 用户确认要翻译的文档，发现新列表时默认全选
+    let selected_urls = create_rw_signal(HashSet::<String>::new());
+    create_effect(move |_| {
+        selected_urls.set(
+            batch_translation
+                .documents
+                .get()
+                .into_iter()
+                .map(|doc| doc.url)
+                .collect(),
+        );
+    });
 
-    let handle_submit = move |_| {
+    let handle_discover = move |_| {
         let url = index_url.get_untracked();
         if !url.is_empty() {
-            batch_translation.start_batch_translation.set(Some(url));
+            let discovery = match choice.get_untracked() {
+                DiscoveryChoice::IndexPage => DiscoveryMode::IndexPage,
+                DiscoveryChoice::Sitemap => DiscoveryMode::Sitemap(SitemapFilter {
+                    path_prefix: path_prefix.get_untracked(),
+                    // 日期输入框的值为 YYYY-MM-DD，留空表示不按修改时间过滤
+                    modified_since: chrono::NaiveDate::parse_from_str(
+                        &modified_since.get_untracked(),
+                        "%Y-%m-%d",
+                    )
+                    .ok(),
+                    respect_robots: respect_robots.get_untracked(),
+                }),
+                DiscoveryChoice::Crawl => DiscoveryMode::Crawl(CrawlOptions {
+                    max_depth: crawl_depth.get_untracked(),
+                    scope: if same_path_only.get_untracked() {
+                        CrawlScope::PathPrefix
+                    } else {
+                        CrawlScope::Origin
+                    },
+                    include: parse_patterns(&include_patterns.get_untracked()),
+                    exclude: parse_patterns(&exclude_patterns.get_untracked()),
+                    max_pages: crawl_max_pages.get_untracked(),
+                }),
+            };
+            batch_translation
+                .discover_documents
+                .set(Some(BatchRequest { url, discovery }));
+        }
+    };
+
+    let handle_translate = move |_| {
+        let selected = selected_urls.get_untracked();
+        let links: Vec<DocumentLink> = batch_translation
+            .documents
+            .get_untracked()
+            .into_iter()
+            .filter(|doc| selected.contains(&doc.url))
+            .collect();
+        batch_translation.start_batch_translation.set(Some(links));
+    };
+
+    let mode_option = move |value: DiscoveryChoice, label: &'static str| {
+        view! {
+            <label class="flex items-center gap-2 cursor-pointer">
+                <input
+                    type="radio"
+                    name="discovery-mode"
+                    prop:checked=move || choice.get() == value
+                    on:change=move |_| set_choice.set(value)
+                    prop:disabled=move || batch_translation.is_processing.get()
+                />
+                {label}
+            </label>
         }
     };
 
@@ -23,14 +115,38 @@ pub fn BatchTranslation() -> impl IntoView {
                     "批量翻译文档网站"
                 </h2>
                 <p class="text-gray-600 dark:text-gray-400">
-                    "输入文档网站的首页URL，系统将自动解析目录结构并批量翻译所有页面"
+                    "输入文档网站的首页URL，系统将自动解析目录结构，确认文档列表后批量翻译"
+                </p>
+                <p class="text-sm text-gray-500 dark:text-gray-400 mt-1">
+                    "也可以直接输入 SUMMARY.md（mdBook/GitBook）、sidebars.js（Docusaurus）、mkdocs.yml 或 Sphinx 的 index.rst 地址，按原始目录层级解析"
                 </p>
             </div>
 
+            // 上次未完成的任务
+            <Show when=move || {
+                !batch_translation.is_processing.get() && !batch_translation.saved_jobs.get().is_empty()
+            }>
+                <SavedJobs
+                    jobs=batch_translation.saved_jobs
+                    resume_job=batch_translation.resume_job
+                    retry_failed=batch_translation.retry_failed
+                    discard_job=batch_translation.discard_job
+                />
+            </Show>
+
             // 输入区域
             <div class="mb-6">
+                <div class="flex flex-wrap gap-4 mb-3 text-sm text-gray-700 dark:text-gray-300">
+                    {mode_option(DiscoveryChoice::IndexPage, "解析首页目录")}
+                    {mode_option(DiscoveryChoice::Sitemap, "读取站点地图 (sitemap.xml)")}
+                    {mode_option(DiscoveryChoice::Crawl, "递归爬取同站链接")}
+                </div>
                 <label class="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
-                    "文档网站首页URL"
+                    {move || match choice.get() {
+                        DiscoveryChoice::IndexPage => "文档网站首页URL",
+                        DiscoveryChoice::Sitemap => "文档网站URL或sitemap.xml地址",
+                        DiscoveryChoice::Crawl => "爬取的起始页面URL",
+                    }}
                 </label>
                 <div class="flex gap-3">
                     <input
@@ -46,27 +162,155 @@ pub fn BatchTranslation() -> impl IntoView {
                     <button
                         type="button"
                         class="px-6 py-2 bg-blue-600 hover:bg-blue-700 disabled:bg-gray-400 text-white font-medium rounded-lg transition-colors"
-                        on:click=handle_submit
+                        on:click=handle_discover
                         prop:disabled=move || batch_translation.is_processing.get() || index_url.get().is_empty()
                     >
-                        "开始批量翻译"
+                        "发现文档"
                     </button>
                 </div>
+                <Show when=move || choice.get() == DiscoveryChoice::Sitemap>
+                    <div class="grid grid-cols-1 md:grid-cols-3 gap-3 mt-3 text-sm text-gray-700 dark:text-gray-300">
+                        <label class="flex flex-col gap-1">
+                            "路径前缀"
+                            <input
+                                type="text"
+                                class=INPUT_CLASS
+                                placeholder="默认为输入地址所在目录，如 /docs/"
+                                prop:value=path_prefix
+                                on:input=move |ev| set_path_prefix.set(event_target_value(&ev))
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            />
+                        </label>
+                        <label class="flex flex-col gap-1">
+                            "只翻译此日期后修改的页面"
+                            <input
+                                type="date"
+                                class=INPUT_CLASS
+                                prop:value=modified_since
+                                on:input=move |ev| set_modified_since.set(event_target_value(&ev))
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            />
+                        </label>
+                        <label class="flex items-center gap-2 md:mt-6 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                prop:checked=respect_robots
+                                on:change=move |ev| set_respect_robots.set(event_target_checked(&ev))
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            />
+                            "遵守 robots.txt 的禁止规则"
+                        </label>
+                    </div>
+                </Show>
+                <Show when=move || choice.get() == DiscoveryChoice::Crawl>
+                    <div class="grid grid-cols-1 md:grid-cols-3 gap-3 mt-3 text-sm text-gray-700 dark:text-gray-300">
+                        <label class="flex flex-col gap-1">
+                            "爬取深度"
+                            <input
+                                type="number"
+                                min="0"
+                                max="10"
+                                class=INPUT_CLASS
+                                prop:value=move || crawl_depth.get().to_string()
+                                on:input=move |ev| {
+                                    if let Ok(depth) = event_target_value(&ev).parse::<usize>() {
+                                        set_crawl_depth.set(depth.min(10));
+                                    }
+                                }
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            />
+                        </label>
+                        <label class="flex flex-col gap-1">
+                            "最多访问页面数"
+                            <input
+                                type="number"
+                                min="1"
+                                max="2000"
+                                class=INPUT_CLASS
+                                prop:value=move || crawl_max_pages.get().to_string()
+                                on:input=move |ev| {
+                                    if let Ok(pages) = event_target_value(&ev).parse::<usize>() {
+                                        set_crawl_max_pages.set(pages.clamp(1, 2000));
+                                    }
+                                }
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            />
+                        </label>
+                        <label class="flex items-center gap-2 md:mt-6 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                prop:checked=same_path_only
+                                on:change=move |ev| set_same_path_only.set(event_target_checked(&ev))
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            />
+                            "只爬取起始地址所在目录"
+                        </label>
+                        <label class="flex flex-col gap-1 md:col-span-3">
+                            "包含模式（每行一个，如 /docs/guide/**）"
+                            <textarea
+                                rows="2"
+                                class=INPUT_CLASS
+                                placeholder="留空表示包含全部页面"
+                                prop:value=include_patterns
+                                on:input=move |ev| set_include_patterns.set(event_target_value(&ev))
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            ></textarea>
+                        </label>
+                        <label class="flex flex-col gap-1 md:col-span-3">
+                            "排除模式（每行一个，如 **/changelog*）"
+                            <textarea
+                                rows="2"
+                                class=INPUT_CLASS
+                                prop:value=exclude_patterns
+                                on:input=move |ev| set_exclude_patterns.set(event_target_value(&ev))
+                                prop:disabled=move || batch_translation.is_processing.get()
+                            ></textarea>
+                        </label>
+                    </div>
+                </Show>
             </div>
 
             // 进度显示
             <Show when=move || batch_translation.is_processing.get()>
                 <BatchProgress progress=batch_translation.progress />
+                <BatchControls
+                    progress=batch_translation.progress
+                    send_command=batch_translation.send_command
+                />
             </Show>
 
-            // 文档列表预览
+            // 文档列表确认
             <Show when=move || !batch_translation.documents.get().is_empty()>
-                <DocumentList documents=batch_translation.documents />
+                <DocumentList documents=batch_translation.documents selected=selected_urls />
+                <div class="flex justify-end mb-6">
+                    <button
+                        type="button"
+                        class="px-6 py-2 bg-blue-600 hover:bg-blue-700 disabled:bg-gray-400 text-white font-medium rounded-lg transition-colors"
+                        on:click=handle_translate
+                        prop:disabled=move || batch_translation.is_processing.get() || selected_urls.get().is_empty()
+                    >
+                        {move || format!("翻译选中的 {} 个文档", selected_urls.get().len())}
+                    </button>
+                </div>
+            </Show>
+
+            // 失败的文档
+            <Show when=move || !batch_translation.failures.get().is_empty()>
+                <FailureList
+                    failures=batch_translation.failures
+                    active_job_id=batch_translation.active_job_id
+                    retry_failed=batch_translation.retry_failed
+                    is_processing=batch_translation.is_processing
+                />
             </Show>
 
             // 翻译结果
             <Show when=move || !batch_translation.translated_docs.get().is_empty()>
-                <TranslationResults docs=batch_translation.translated_docs />
+                <TranslationResults
+                    docs=batch_translation.translated_docs
+                    failures=batch_translation.failures
+                    changelog=batch_translation.changelog
+                />
             </Show>
         </div>
     }
@@ -86,6 +330,8 @@ fn BatchProgress(
                             BatchStatus::Translating => "批量翻译中",
                             BatchStatus::Packaging => "打包文件",
                             BatchStatus::Completed => "翻译完成",
+                            BatchStatus::Paused => "已暂停",
+                            BatchStatus::Cancelled => "已取消",
                             BatchStatus::Failed(_) => "翻译失败",
                             BatchStatus::Idle => "等待中",
                         }
@@ -126,27 +372,229 @@ fn BatchProgress(
                     {move || format!("失败: {} 个文档", progress.get().failed_count)}
                 </p>
             </Show>
+
+            <MemoryStatsLine class="text-xs text-blue-600 dark:text-blue-300 mt-1" />
         </div>
     }
 }
 
 #[component]
-fn DocumentList(
-    documents: ReadSignal<Vec<crate::services::batch_service::DocumentLink>>,
+fn BatchControls(
+    progress: ReadSignal<crate::services::batch_service::BatchProgress>,
+    send_command: WriteSignal<Option<BatchCommand>>,
+) -> impl IntoView {
+    let is_paused = move || matches!(progress.get().status, BatchStatus::Paused);
+    let can_control = move || {
+        matches!(
+            progress.get().status,
+            BatchStatus::Translating | BatchStatus::Paused
+        )
+    };
+
+    view! {
+        <Show when=can_control>
+            <div class="flex justify-end gap-3 -mt-4 mb-6">
+                <button
+                    type="button"
+                    class="px-4 py-1.5 text-sm bg-yellow-500 hover:bg-yellow-600 text-white rounded-lg transition-colors"
+                    on:click=move |_| {
+                        let command = if is_paused() { BatchCommand::Resume } else { BatchCommand::Pause };
+                        send_command.set(Some(command));
+                    }
+                >
+                    {move || if is_paused() { "继续" } else { "暂停" }}
+                </button>
+                <button
+                    type="button"
+                    class="px-4 py-1.5 text-sm bg-red-600 hover:bg-red-700 text-white rounded-lg transition-colors"
+                    on:click=move |_| send_command.set(Some(BatchCommand::Cancel))
+                >
+                    "取消"
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn FailureList(
+    failures: ReadSignal<Vec<BatchFailure>>,
+    active_job_id: ReadSignal<Option<String>>,
+    retry_failed: WriteSignal<Option<String>>,
+    is_processing: ReadSignal<bool>,
 ) -> impl IntoView {
     view! {
         <div class="mb-6">
-            <h3 class="text-lg font-semibold text-gray-900 dark:text-white mb-3">
-                "发现的文档 (" {move || documents.get().len()} " 个)"
+            <div class="flex items-center justify-between mb-3">
+                <h3 class="text-lg font-semibold text-red-700 dark:text-red-400">
+                    "翻译失败的文档 (" {move || failures.get().len()} " 个)"
+                </h3>
+                <button
+                    type="button"
+                    class="px-4 py-1.5 text-sm bg-red-600 hover:bg-red-700 disabled:bg-gray-400 text-white rounded-lg transition-colors"
+                    prop:disabled=move || is_processing.get() || active_job_id.get().is_none()
+                    on:click=move |_| retry_failed.set(active_job_id.get_untracked())
+                >
+                    "只重试失败的文档"
+                </button>
+            </div>
+            <div class="max-h-60 overflow-y-auto border border-red-200 dark:border-red-800 rounded-lg">
+                <For
+                    each=move || failures.get()
+                    key=|failure| failure.link.order
+                    children=move |failure| {
+                        view! {
+                            <div class="p-3 border-b border-red-100 dark:border-red-900 last:border-b-0">
+                                <p class="text-sm font-medium text-gray-900 dark:text-white truncate">
+                                    {format!("{}. {}", failure.link.order + 1, failure.link.title)}
+                                </p>
+                                <p class="text-xs text-gray-500 dark:text-gray-400 truncate">
+                                    {failure.link.url.clone()}
+                                </p>
+                                <p class="text-xs text-red-600 dark:text-red-400 mt-1">
+                                    {failure.error.to_string()}
+                                </p>
+                            </div>
+                        }
+                    }
+                />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn SavedJobs(
+    jobs: ReadSignal<Vec<BatchJob>>,
+    resume_job: WriteSignal<Option<String>>,
+    retry_failed: WriteSignal<Option<String>>,
+    discard_job: WriteSignal<Option<String>>,
+) -> impl IntoView {
+    view! {
+        <div class="mb-6 p-4 bg-yellow-50 dark:bg-yellow-900/20 rounded-lg border border-yellow-200 dark:border-yellow-800">
+            <h3 class="text-sm font-semibold text-yellow-800 dark:text-yellow-200 mb-2">
+                "未完成的批量任务"
             </h3>
+            <For
+                each=move || jobs.get()
+                key=|job| (job.id.clone(), job.updated_at.clone())
+                children=move |job| {
+                    let resume_id = job.id.clone();
+                    let retry_id = job.id.clone();
+                    let discard_id = job.id.clone();
+                    let has_pending = job.pending_count() > 0;
+                    let has_failures = job.failed_count() > 0;
+                    view! {
+                        <div class="flex items-center justify-between gap-3 py-2 border-b border-yellow-100 dark:border-yellow-800 last:border-b-0">
+                            <div class="min-w-0">
+                                <p class="text-sm text-gray-900 dark:text-white truncate">
+                                    {job.index_url.clone()}
+                                </p>
+                                <p class="text-xs text-gray-500 dark:text-gray-400">
+                                    {format!(
+                                        "已完成 {}/{}，失败 {}，剩余 {} · 更新于 {}",
+                                        job.completed_count(),
+                                        job.total_count(),
+                                        job.failed_count(),
+                                        job.pending_count(),
+                                        job.updated_at.chars().take(19).collect::<String>().replace('T', " ")
+                                    )}
+                                </p>
+                            </div>
+                            <div class="flex gap-2 flex-shrink-0">
+                                <Show when=move || has_pending>
+                                    <button
+                                        type="button"
+                                        class="px-3 py-1 text-sm bg-blue-600 hover:bg-blue-700 text-white rounded-lg transition-colors"
+                                        on:click={
+                                            let resume_id = resume_id.clone();
+                                            move |_| resume_job.set(Some(resume_id.clone()))
+                                        }
+                                    >
+                                        "继续翻译"
+                                    </button>
+                                </Show>
+                                <Show when=move || has_failures && !has_pending>
+                                    <button
+                                        type="button"
+                                        class="px-3 py-1 text-sm bg-red-600 hover:bg-red-700 text-white rounded-lg transition-colors"
+                                        on:click={
+                                            let retry_id = retry_id.clone();
+                                            move |_| retry_failed.set(Some(retry_id.clone()))
+                                        }
+                                    >
+                                        "重试失败"
+                                    </button>
+                                </Show>
+                                <button
+                                    type="button"
+                                    class="px-3 py-1 text-sm text-red-600 dark:text-red-400 hover:underline"
+                                    on:click=move |_| discard_job.set(Some(discard_id.clone()))
+                                >
+                                    "删除"
+                                </button>
+                            </div>
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+#[component]
+fn DocumentList(
+    documents: ReadSignal<Vec<DocumentLink>>,
+    selected: RwSignal<HashSet<String>>,
+) -> impl IntoView {
+    let toggle_all = move |_| {
+        let docs = documents.get_untracked();
+        if selected.get_untracked().len() == docs.len() {
+            selected.set(HashSet::new());
+        } else {
+            selected.set(docs.into_iter().map(|doc| doc.url).collect());
+        }
+    };
+
+    view! {
+        <div class="mb-3">
+            <div class="flex items-center justify-between mb-3">
+                <h3 class="text-lg font-semibold text-gray-900 dark:text-white">
+                    "发现的文档 (" {move || documents.get().len()} " 个)"
+                </h3>
+                <button
+                    type="button"
+                    class="text-sm text-blue-600 dark:text-blue-400 hover:underline"
+                    on:click=toggle_all
+                >
+                    {move || if selected.get().len() == documents.get().len() { "取消全选" } else { "全选" }}
+                </button>
+            </div>
             <div class="max-h-60 overflow-y-auto border border-gray-200 dark:border-gray-700 rounded-lg">
                 <For
                     each=move || documents.get()
                     key=|doc| doc.order
                     children=move |doc| {
+                        let url = doc.url.clone();
+                        let checked_url = doc.url.clone();
                         view! {
-                            <div class="p-3 border-b border-gray-100 dark:border-gray-700 last:border-b-0 hover:bg-gray-50 dark:hover:bg-gray-700">
+                            <label class="block p-3 border-b border-gray-100 dark:border-gray-700 last:border-b-0 hover:bg-gray-50 dark:hover:bg-gray-700 cursor-pointer">
                                 <div class="flex items-start gap-3">
+                                    <input
+                                        type="checkbox"
+                                        class="mt-1"
+                                        prop:checked=move || selected.get().contains(&checked_url)
+                                        on:change=move |ev| {
+                                            let checked = event_target_checked(&ev);
+                                            selected.update(|urls| {
+                                                if checked {
+                                                    urls.insert(url.clone());
+                                                } else {
+                                                    urls.remove(&url);
+                                                }
+                                            });
+                                        }
+                                    />
                                     <span class="flex-shrink-0 w-8 h-6 bg-blue-100 dark:bg-blue-900 text-blue-800 dark:text-blue-200 text-xs font-medium rounded flex items-center justify-center">
                                         {doc.order + 1}
                                     </span>
@@ -159,7 +607,7 @@ fn DocumentList(
                                         </p>
                                     </div>
                                 </div>
-                            </div>
+                            </label>
                         }
                     }
                 />
@@ -171,6 +619,8 @@ fn DocumentList(
 #[component]
 fn TranslationResults(
     docs: ReadSignal<Vec<crate::services::batch_service::TranslatedDocument>>,
+    failures: ReadSignal<Vec<BatchFailure>>,
+    changelog: ReadSignal<Option<Changelog>>,
 ) -> impl IntoView {
     let (select_all, set_select_all) = create_signal(true);
 
@@ -233,7 +683,11 @@ fn TranslationResults(
         let config = AppConfig::default();
         let service = BatchTranslationService::new(&config);
 
-        match service.create_compressed_archive(&selected_docs) {
+        match service.create_compressed_archive(
+            &selected_docs,
+            &failures.get_untracked(),
+            changelog.get_untracked().as_ref(),
+        ) {
             Ok(zip_data) => {
                 // 触发下载
                 let array = js_sys::Array::new();
@@ -312,6 +766,15 @@ fn TranslationResults(
                 <p class="text-green-700 dark:text-green-300 text-sm mb-4">
                     "选择要下载的文档，系统将按索引顺序打包为tar.gz文件。所有文件统一放在documents文件夹中，文件名包含路径和序号信息。"
                 </p>
+                {move || changelog.get().map(|changelog| view! {
+                    <p class="text-green-700 dark:text-green-300 text-sm mb-4">
+                        {format!(
+                            "增量翻译（与 {} 相比）: {}，变更记录已写入 CHANGELOG.md",
+                            changelog.previous_translated_at,
+                            changelog.summary()
+                        )}
+                    </p>
+                })}
 
                 // 显示文档列表（按序号排序）
                 <DocumentFolderView docs_selection=docs_selection toggle_doc_selection=toggle_doc_selection />
@@ -368,6 +831,11 @@ fn DocumentFolderView(
                                                 <span class="text-xs text-gray-500 dark:text-gray-400 font-mono">
                                                     {doc.file_name.clone()}
                                                 </span>
+                                                <Show when=move || doc.change != DocumentChange::Added>
+                                                    <span class="text-xs px-1.5 py-0.5 rounded bg-gray-100 dark:bg-gray-700 text-gray-600 dark:text-gray-300">
+                                                        {doc.change.label()}
+                                                    </span>
+                                                </Show>
                                                 <button
                                                     type="button"
                                                     class="text-xs text-blue-600 dark:text-blue-400 hover:text-blue-800 dark:hover:text-blue-200"
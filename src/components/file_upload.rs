@@ -0,0 +1,120 @@
+use crate::error::{use_error_handler, AppError};
+use crate::services::local_file_service::{LocalFile, LocalFileFormat, ACCEPTED_FILE_TYPES};
+use crate::theme::use_theme_context;
+use leptos::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+#[component]
+pub fn FileUpload(
+    on_file: impl Fn(LocalFile) + 'static + Copy,
+    is_loading: ReadSignal<bool>,
+) -> impl IntoView {
+    let theme_context = use_theme_context();
+    let error_handler = use_error_handler();
+    let (is_dragging, set_is_dragging) = create_signal(false);
+    let (file_name, set_file_name) = create_signal(String::new());
+
+    let handle_file = move |file: web_sys::File| {
+        let name = file.name();
+        if LocalFileFormat::from_file_name(&name).is_none() {
+            error_handler.handle_error(AppError::validation(
+                "文件",
+                format!(
+                    "不支持的文件类型: {}，请选择 Markdown、HTML、TXT、DOCX 或 PDF 文件",
+                    name
+                ),
+            ));
+            return;
+        }
+
+        set_file_name.set(name.clone());
+        spawn_local(async move {
+            match JsFuture::from(file.array_buffer()).await {
+                Ok(buffer) => {
+                    let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+                    web_sys::console::log_1(
+                        &format!("已读取文件: {}，{} 字节", name, bytes.len()).into(),
+                    );
+                    on_file(LocalFile { name, bytes });
+                }
+                Err(e) => {
+                    error_handler
+                        .handle_error(AppError::file(format!("读取文件 {} 失败: {:?}", name, e)));
+                }
+            }
+        });
+    };
+
+    let handle_drop = move |ev: web_sys::DragEvent| {
+        ev.prevent_default();
+        set_is_dragging.set(false);
+        if is_loading.get() {
+            return;
+        }
+        if let Some(file) = ev
+            .data_transfer()
+            .and_then(|transfer| transfer.files())
+            .and_then(|files| files.get(0))
+        {
+            handle_file(file);
+        }
+    };
+
+    let handle_change = move |ev: web_sys::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        if let Some(file) = input.files().and_then(|files| files.get(0)) {
+            handle_file(file);
+        }
+        // 清空选择，以便再次选择同一个文件
+        input.set_value("");
+    };
+
+    view! {
+        <div>
+            <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                "或翻译本地文件"
+            </label>
+            <label
+                class="flex flex-col items-center justify-center px-4 py-6 rounded-md border-2 border-dashed cursor-pointer transition-colors"
+                style=move || {
+                    let theme = theme_context.get().theme;
+                    if is_dragging.get() {
+                        format!("{} border-color: {};", theme.input_style(), theme.info_color())
+                    } else {
+                        theme.input_style()
+                    }
+                }
+                on:dragover=move |ev: web_sys::DragEvent| {
+                    ev.prevent_default();
+                    set_is_dragging.set(true);
+                }
+                on:dragleave=move |_| set_is_dragging.set(false)
+                on:drop=handle_drop
+            >
+                <svg class="w-8 h-8 mb-2" fill="none" stroke="currentColor" viewBox="0 0 24 24" style=move || format!("color: {};", theme_context.get().theme.info_color())>
+                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M7 16a4 4 0 01-.88-7.903A5 5 0 1115.9 6L16 6a5 5 0 011 9.9M15 13l-3-3m0 0l-3 3m3-3v12"></path>
+                </svg>
+                <span class="text-sm" style=move || theme_context.get().theme.text_style()>
+                    {move || {
+                        let name = file_name.get();
+                        if name.is_empty() {
+                            "拖放文件到此处，或点击选择文件".to_string()
+                        } else {
+                            format!("当前文件: {}", name)
+                        }
+                    }}
+                </span>
+                <span class="text-xs mt-1" style=move || theme_context.get().theme.subtext_style()>
+                    "支持 Markdown、HTML、TXT、DOCX 和 PDF"
+                </span>
+                <input
+                    type="file"
+                    class="hidden"
+                    accept=ACCEPTED_FILE_TYPES
+                    on:change=handle_change
+                    disabled=is_loading
+                />
+            </label>
+        </div>
+    }
+}
@@ -1,7 +1,9 @@
 pub mod batch_translation;
 pub mod common;
 pub mod file_name_preview;
+pub mod file_upload;
 pub mod header;
+pub mod paste_input;
 pub mod preview_panel;
 pub mod progress_indicator;
 pub mod settings;
@@ -11,8 +13,10 @@ pub mod url_input;
 
 pub use batch_translation::BatchTranslation;
 pub use file_name_preview::{AdvancedFileNamePreview, BatchFileNamePreview, FileNamePreview};
+pub use file_upload::FileUpload;
+pub use paste_input::PasteInput;
 pub use preview_panel::PreviewPanel;
-pub use progress_indicator::ProgressIndicator;
+pub use progress_indicator::{MemoryStatsLine, ProgressIndicator};
 pub use theme_selector::ThemeSelector;
 pub use translation_result::TranslationResult;
 pub use url_input::UrlInput;
@@ -0,0 +1,54 @@
+use crate::theme::use_theme_context;
+use leptos::*;
+
+#[component]
+pub fn PasteInput(
+    text: ReadSignal<String>,
+    set_text: WriteSignal<String>,
+    on_submit: impl Fn(web_sys::MouseEvent) + 'static + Copy,
+    is_loading: ReadSignal<bool>,
+) -> impl IntoView {
+    let theme_context = use_theme_context();
+
+    // Ctrl+Enter 提交，普通回车用于换行
+    let handle_keydown = move |ev: web_sys::KeyboardEvent| {
+        if ev.key() == "Enter" && (ev.ctrl_key() || ev.meta_key()) && !is_loading.get() {
+            ev.prevent_default();
+            if let Ok(mouse_event) = web_sys::MouseEvent::new("click") {
+                on_submit(mouse_event);
+            }
+        }
+    };
+
+    view! {
+        <div>
+            <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                "粘贴文本或Markdown"
+            </label>
+            <textarea
+                class="w-full h-48 px-4 py-2 rounded-md font-mono text-sm focus:ring-2 focus:border-transparent"
+                style=move || theme_context.get().theme.input_style()
+                placeholder="在此粘贴聊天记录、工单内容等，代码块会保持原样"
+                prop:value=text
+                on:input=move |ev| {
+                    set_text.set(event_target_value(&ev));
+                }
+                on:keydown=handle_keydown
+                disabled=is_loading
+            ></textarea>
+            <div class="flex items-center justify-between mt-2">
+                <span class="text-xs" style=move || theme_context.get().theme.subtext_style()>
+                    {move || format!("{} 字符 · Ctrl+Enter 翻译", text.get().chars().count())}
+                </span>
+                <button
+                    class="px-6 py-2 rounded-md disabled:opacity-50 disabled:cursor-not-allowed transition-colors hover:opacity-90"
+                    style=move || theme_context.get().theme.button_primary_style()
+                    disabled=move || is_loading.get() || text.get().trim().is_empty()
+                    on:click=on_submit
+                >
+                    {move || if is_loading.get() { "处理中..." } else { "翻译" }}
+                </button>
+            </div>
+        </div>
+    }
+}
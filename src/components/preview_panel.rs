@@ -1,7 +1,13 @@
-use crate::hooks::use_preview::use_preview;
+use crate::hooks::use_config::use_config;
+use crate::hooks::use_preview::{use_comparison, use_preview};
 use crate::services::preview_service::{PreviewContent, PreviewOptions};
+use crate::services::translator::engine_chain;
+use crate::types::api_types::TranslationEngine;
 use leptos::*;
 
+/// 同时对比的引擎数上限
+const MAX_COMPARISON_ENGINES: usize = 3;
+
 #[component]
 pub fn PreviewPanel(
     #[prop(into)] url: Signal<String>,
@@ -71,11 +77,200 @@ pub fn PreviewPanel(
                 <Show when=move || preview.preview_content.get().is_some()>
                     <PreviewContentDisplay content=preview.preview_content />
                 </Show>
+
+                // 多引擎对比
+                <EngineComparison url=url />
             </div>
         </Show>
     }
 }
 
+/// 用多个引擎翻译完整文档，逐段挑选译文
+#[component]
+fn EngineComparison(#[prop(into)] url: Signal<String>) -> impl IntoView {
+    let comparison = use_comparison();
+    let config_hook = use_config();
+
+    let selected_engines = create_rw_signal(
+        engine_chain(&config_hook.config.get_untracked())
+            .into_iter()
+            .take(MAX_COMPARISON_ENGINES)
+            .collect::<Vec<_>>(),
+    );
+
+    let toggle_engine = move |engine: TranslationEngine, checked: bool| {
+        selected_engines.update(|engines| {
+            if checked {
+                if !engines.contains(&engine) && engines.len() < MAX_COMPARISON_ENGINES {
+                    engines.push(engine);
+                }
+            } else {
+                engines.retain(|e| *e != engine);
+            }
+        });
+    };
+
+    let handle_compare = move |_| {
+        let current_url = url.get();
+        if !current_url.is_empty() {
+            comparison
+                .generate_comparison
+                .set(Some((current_url, selected_engines.get())));
+        }
+    };
+
+    let handle_save = move |_| {
+        comparison.save_to_history.set(Some(url.get()));
+    };
+
+    view! {
+        <div class="mt-4 p-3 bg-white dark:bg-gray-800 rounded border">
+            <div class="flex items-center justify-between mb-2">
+                <h4 class="text-sm font-medium text-gray-700 dark:text-gray-300">
+                    "多引擎对比（完整文档）"
+                </h4>
+                <div class="flex gap-2">
+                    <button
+                        type="button"
+                        class="px-3 py-1 text-sm bg-blue-600 hover:bg-blue-700 disabled:bg-gray-400 text-white rounded transition-colors"
+                        on:click=handle_compare
+                        prop:disabled=move || {
+                            comparison.is_loading.get()
+                                || url.get().is_empty()
+                                || selected_engines.get().len() < 2
+                        }
+                    >
+                        {move || if comparison.is_loading.get() { "翻译中..." } else { "开始对比" }}
+                    </button>
+                    <button
+                        type="button"
+                        class="px-3 py-1 text-sm bg-green-600 hover:bg-green-700 disabled:bg-gray-400 text-white rounded transition-colors"
+                        on:click=handle_save
+                        prop:disabled=move || comparison.comparison.get().is_none() || comparison.is_saved.get()
+                    >
+                        {move || if comparison.is_saved.get() { "已保存" } else { "保存到历史" }}
+                    </button>
+                </div>
+            </div>
+
+            // 引擎选择
+            <div class="flex flex-wrap gap-4 text-sm text-gray-600 dark:text-gray-400 mb-2">
+                {TranslationEngine::ALL
+                    .into_iter()
+                    .map(|engine| {
+                        view! {
+                            <label class="flex items-center gap-2">
+                                <input
+                                    type="checkbox"
+                                    class="rounded"
+                                    prop:checked=move || selected_engines.get().contains(&engine)
+                                    prop:disabled=move || {
+                                        let engines = selected_engines.get();
+                                        !engines.contains(&engine)
+                                            && engines.len() >= MAX_COMPARISON_ENGINES
+                                    }
+                                    on:change=move |ev| toggle_engine(engine, event_target_checked(&ev))
+                                />
+                                {engine.display_name()}
+                            </label>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <p class="text-xs text-gray-500 dark:text-gray-400 mb-2">
+                {format!("选择 2-{} 个引擎同时翻译整篇文档，点击译文段落即可选用", MAX_COMPARISON_ENGINES)}
+            </p>
+
+            <Show when=move || comparison.is_loading.get()>
+                <div class="my-4 p-3 bg-blue-100 dark:bg-blue-800 rounded text-blue-800 dark:text-blue-200 text-sm">
+                    <div class="flex items-center gap-2">
+                        <div class="animate-spin rounded-full h-4 w-4 border-b-2 border-blue-600"></div>
+                        "正在用多个引擎翻译完整文档，请稍候..."
+                    </div>
+                </div>
+            </Show>
+
+            {move || {
+                comparison.comparison.get().map(|content| {
+                    let column_count = content.columns.len() + 1;
+                    let grid_style = format!(
+                        "grid-template-columns: repeat({}, minmax(0, 1fr));",
+                        column_count
+                    );
+                    let headers = content
+                        .columns
+                        .iter()
+                        .map(|column| {
+                            let title = match &column.error {
+                                Some(error) => format!("{}（失败: {}）", column.engine.display_name(), error),
+                                None => column.engine.display_name().to_string(),
+                            };
+                            view! { <div class="font-semibold text-gray-800 dark:text-gray-200">{title}</div> }
+                        })
+                        .collect_view();
+
+                    let rows = content
+                        .original_paragraphs
+                        .iter()
+                        .enumerate()
+                        .map(|(index, original)| {
+                            let cells = content
+                                .columns
+                                .iter()
+                                .enumerate()
+                                .map(|(column_index, column)| {
+                                    let text = column.paragraphs.get(index).cloned().unwrap_or_default();
+                                    let available = column.error.is_none();
+                                    let is_selected = move || {
+                                        comparison.choices.get().get(index) == Some(&column_index)
+                                    };
+                                    view! {
+                                        <div
+                                            class="p-2 rounded border text-sm whitespace-pre-wrap text-gray-700 dark:text-gray-300"
+                                            class:cursor-pointer=available
+                                            class:border-blue-500=is_selected
+                                            class:bg-blue-50=is_selected
+                                            class:dark:bg-blue-900=is_selected
+                                            class:border-gray-200=move || !is_selected()
+                                            class:dark:border-gray-700=move || !is_selected()
+                                            on:click=move |_| {
+                                                if available {
+                                                    comparison.choices.update(|choices| {
+                                                        if let Some(choice) = choices.get_mut(index) {
+                                                            *choice = column_index;
+                                                        }
+                                                    });
+                                                }
+                                            }
+                                        >
+                                            {text}
+                                        </div>
+                                    }
+                                })
+                                .collect_view();
+
+                            view! {
+                                <div class="p-2 rounded border border-gray-200 dark:border-gray-700 text-sm whitespace-pre-wrap text-gray-500 dark:text-gray-400">
+                                    {original.clone()}
+                                </div>
+                                {cells}
+                            }
+                        })
+                        .collect_view();
+
+                    view! {
+                        <div class="grid gap-2 max-h-[36rem] overflow-y-auto" style=grid_style>
+                            <div class="font-semibold text-gray-800 dark:text-gray-200">"原文"</div>
+                            {headers}
+                            {rows}
+                        </div>
+                    }
+                })
+            }}
+        </div>
+    }
+}
+
 #[component]
 fn PreviewOptionsPanel(
     options: ReadSignal<PreviewOptions>,
@@ -1,5 +1,7 @@
 use crate::hooks::use_translation::TranslationStatus;
+use crate::services::translation_memory::{session_stats, MemoryStats};
 use leptos::*;
+use std::time::Duration;
 
 #[component]
 pub fn ProgressIndicator(
@@ -29,8 +31,38 @@ pub fn ProgressIndicator(
                             }
                         }}
                     </div>
+                    <MemoryStatsLine class="text-xs mt-1" />
                 </div>
             </div>
         </Show>
     }
 }
+
+/// 翻译记忆的命中统计，翻译过程中定时刷新，没有查询时不显示
+#[component]
+pub fn MemoryStatsLine(#[prop(into)] class: String) -> impl IntoView {
+    let (stats, set_stats) = create_signal(session_stats());
+    if let Ok(handle) = set_interval_with_handle(
+        move || set_stats.set(session_stats()),
+        Duration::from_millis(500),
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    view! {
+        <Show when=move || { stats.get().lookups() > 0 }>
+            <div class=class.clone()>
+                {move || memory_stats_text(&stats.get())}
+            </div>
+        </Show>
+    }
+}
+
+fn memory_stats_text(stats: &MemoryStats) -> String {
+    format!(
+        "翻译记忆: 命中 {} / 未命中 {} ({:.0}%)",
+        stats.hits,
+        stats.misses,
+        stats.hit_rate()
+    )
+}
@@ -22,7 +22,7 @@ pub fn Settings() -> impl IntoView {
             max_requests_per_second: 10,
             max_text_length: 5000,
             max_paragraphs_per_request: 10,
-            file_naming: initial_config.file_naming.clone(),
+            ..initial_config.clone()
         };
 
         match config_service.save_config(&config) {
@@ -1,21 +1,66 @@
-use crate::error::{use_error_handler, AppError};
+use crate::error::{use_error_handler, AppError, ErrorHandler};
 use crate::services::{
+    batch_job_service::BatchJobService,
     batch_service::{
-        BatchProgress, BatchStatus, BatchTranslationService, DocumentLink, TranslatedDocument,
+        BatchControl, BatchFailure, BatchProgress, BatchRequest, BatchStatus,
+        BatchTranslationService, DocumentLink, TranslatedDocument,
     },
     config_service::ConfigService,
     history_service::HistoryService,
+    incremental::{Changelog, PreviousTranslations},
+    translation_memory::reset_session_stats,
+    translator::merge_engine_usage,
 };
+use crate::types::api_types::AppConfig;
+use crate::types::batch_job::BatchJob;
 use crate::types::history::{BatchDocumentInfo, BatchTranslationData, HistoryEntry};
 use leptos::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use wasm_bindgen_futures::spawn_local;
 
+/// 批量任务运行时更新的界面状态
+#[derive(Clone, Copy)]
+struct JobSignals {
+    progress: WriteSignal<BatchProgress>,
+    translated_docs: WriteSignal<Vec<TranslatedDocument>>,
+    failures: WriteSignal<Vec<BatchFailure>>,
+    changelog: WriteSignal<Option<Changelog>>,
+}
+
+/// 对正在运行的批量任务的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchCommand {
+    Pause,
+    Resume,
+    Cancel,
+}
+
 pub struct UseBatchTranslationReturn {
     pub is_processing: ReadSignal<bool>,
     pub progress: ReadSignal<BatchProgress>,
     pub documents: ReadSignal<Vec<DocumentLink>>,
     pub translated_docs: ReadSignal<Vec<TranslatedDocument>>,
-    pub start_batch_translation: WriteSignal<Option<String>>,
+    /// 重试后仍失败的文档及错误
+    pub failures: ReadSignal<Vec<BatchFailure>>,
+    /// 与上一次翻译同一站点相比的变更记录，首次翻译时为 None
+    pub changelog: ReadSignal<Option<Changelog>>,
+    /// 最近运行的批量任务ID
+    pub active_job_id: ReadSignal<Option<String>>,
+    /// 保存在本地、还有文档未处理或失败的批量任务
+    pub saved_jobs: ReadSignal<Vec<BatchJob>>,
+    /// 按请求发现文档，结果写入 documents 供用户确认
+    pub discover_documents: WriteSignal<Option<BatchRequest>>,
+    /// 翻译用户确认的文档
+    pub start_batch_translation: WriteSignal<Option<Vec<DocumentLink>>>,
+    /// 按任务ID继续未完成的批量任务
+    pub resume_job: WriteSignal<Option<String>>,
+    /// 按任务ID只重新翻译失败的文档
+    pub retry_failed: WriteSignal<Option<String>>,
+    /// 按任务ID删除保存的批量任务
+    pub discard_job: WriteSignal<Option<String>>,
+    /// 暂停、继续或取消正在运行的任务
+    pub send_command: WriteSignal<Option<BatchCommand>>,
 }
 
 pub fn use_batch_translation() -> UseBatchTranslationReturn {
@@ -31,283 +76,268 @@ pub fn use_batch_translation() -> UseBatchTranslationReturn {
     });
     let (documents, set_documents) = create_signal(Vec::<DocumentLink>::new());
     let (translated_docs, set_translated_docs) = create_signal(Vec::<TranslatedDocument>::new());
-    let (start_trigger, set_start_trigger) = create_signal(None::<String>);
-
-    // 批量翻译处理Effect
-    create_effect({
-        let set_is_processing = set_is_processing.clone();
-        let set_progress = set_progress.clone();
-        let set_documents = set_documents.clone();
-        let set_translated_docs = set_translated_docs.clone();
-
-        move |_| {
-            if let Some(index_url) = start_trigger.get() {
-                if index_url.is_empty() {
+    let (source_url, set_source_url) = create_signal(String::new());
+    let (discover_trigger, set_discover_trigger) = create_signal(None::<BatchRequest>);
+    let (start_trigger, set_start_trigger) = create_signal(None::<Vec<DocumentLink>>);
+    let (failures, set_failures) = create_signal(Vec::<BatchFailure>::new());
+    let (changelog, set_changelog) = create_signal(None::<Changelog>);
+    let (active_job_id, set_active_job_id) = create_signal(None::<String>);
+    let (resume_trigger, set_resume_trigger) = create_signal(None::<String>);
+    let (retry_trigger, set_retry_trigger) = create_signal(None::<String>);
+    let (discard_trigger, set_discard_trigger) = create_signal(None::<String>);
+    let (saved_jobs, set_saved_jobs) = create_signal(Vec::<BatchJob>::new());
+    spawn_local(refresh_saved_jobs(set_saved_jobs));
+    let (command_trigger, set_command_trigger) = create_signal(None::<BatchCommand>);
+    // 当前运行任务的控制令牌，每次开始或恢复任务时替换
+    let control = store_value(BatchControl::new());
+    let job_signals = JobSignals {
+        progress: set_progress,
+        translated_docs: set_translated_docs,
+        failures: set_failures,
+        changelog: set_changelog,
+    };
+
+    // 文档发现Effect：只生成文档列表，由用户确认后再开始翻译
+    create_effect(move |_| {
+        if let Some(request) = discover_trigger.get() {
+            if request.url.is_empty() {
+                error_handler.handle_error(AppError::validation("URL", "请输入有效的文档索引URL"));
+                return;
+            }
+
+            set_is_processing.set(true);
+            set_documents.set(Vec::new());
+            set_translated_docs.set(Vec::new());
+            set_failures.set(Vec::new());
+            set_changelog.set(None);
+            set_source_url.set(request.url.clone());
+            set_progress.set(BatchProgress {
+                total: 0,
+                completed: 0,
+                current_task: "正在发现文档...".to_string(),
+                failed_count: 0,
+                status: BatchStatus::Parsing,
+            });
+
+            spawn_local(async move {
+                web_sys::console::log_1(&"=== 步骤1: 发现文档 ===".into());
+                web_sys::console::log_1(&format!("索引URL: {}", request.url).into());
+
+                let result = match ConfigService::new().get_config() {
+                    Ok(config) => {
+                        BatchTranslationService::new(&config)
+                            .discover_documents(&request, move |progress| {
+                                set_progress.set(progress)
+                            })
+                            .await
+                    }
+                    Err(e) => Err(format!("配置加载失败: {}", e)),
+                };
+
+                match result {
+                    Ok(links) => {
+                        web_sys::console::log_1(
+                            &format!("发现 {} 个文档，等待确认", links.len()).into(),
+                        );
+                        set_progress.set(BatchProgress {
+                            total: links.len(),
+                            completed: 0,
+                            current_task: format!("发现 {} 个文档，请确认后开始翻译", links.len()),
+                            failed_count: 0,
+                            status: BatchStatus::Idle,
+                        });
+                        if links.is_empty() {
+                            error_handler
+                                .handle_error(AppError::extraction("没有发现可翻译的文档"));
+                        }
+                        set_documents.set(links);
+                    }
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("文档发现失败: {}", e).into());
+                        let error_msg = format!("文档发现失败: {}", e);
+                        set_progress.set(BatchProgress {
+                            total: 0,
+                            completed: 0,
+                            current_task: error_msg.clone(),
+                            failed_count: 0,
+                            status: BatchStatus::Failed(error_msg.clone()),
+                        });
+                        error_handler.handle_error(AppError::extraction(error_msg));
+                    }
+                }
+
+                set_is_processing.set(false);
+            });
+        }
+    });
+
+    // 批量翻译处理Effect：为用户确认的文档创建新任务
+    create_effect(move |_| {
+        if let Some(links) = start_trigger.get() {
+            if links.is_empty() {
+                error_handler.handle_error(AppError::validation("文档", "请至少选择一个文档"));
+                return;
+            }
+            let index_url = source_url.get_untracked();
+
+            set_is_processing.set(true);
+            set_translated_docs.set(Vec::new());
+            set_failures.set(Vec::new());
+            set_changelog.set(None);
+            set_progress.set(BatchProgress {
+                total: links.len(),
+                completed: 0,
+                current_task: "开始处理...".to_string(),
+                failed_count: 0,
+                status: BatchStatus::Translating,
+            });
+
+            let run_control = BatchControl::new();
+            control.set_value(run_control.clone());
+
+            spawn_local(async move {
+                web_sys::console::log_1(&"=== 开始批量翻译流程 ===".into());
+                web_sys::console::log_1(&format!("索引URL: {}", index_url).into());
+
+                match ConfigService::new().get_config() {
+                    Ok(config) => {
+                        web_sys::console::log_1(&"配置加载成功".into());
+                        let job = BatchJob::new(
+                            index_url,
+                            config.default_source_lang.clone(),
+                            config.default_target_lang.clone(),
+                            links,
+                        );
+                        set_active_job_id.set(Some(job.id.clone()));
+                        run_batch_job(job, config, run_control, job_signals, error_handler).await;
+                    }
+                    Err(e) => report_config_error(e, set_progress, error_handler),
+                }
+
+                refresh_saved_jobs(set_saved_jobs).await;
+                set_is_processing.set(false);
+            });
+        }
+    });
+
+    // 继续保存的任务：只翻译待处理的文档，重试时先把失败的文档重新标记为待处理
+    let run_saved_job = move |job_id: String, retry_failed: bool| {
+        spawn_local(async move {
+            let saved = match BatchJobService::open().await {
+                Ok(job_service) => job_service.get_job(&job_id).await,
+                Err(e) => Err(e),
+            };
+            let mut job = match saved {
+                Ok(Some(job)) => job,
+                Ok(None) => {
                     error_handler
-                        .handle_error(AppError::validation("URL", "请输入有效的文档索引URL"));
+                        .handle_error(AppError::validation("任务", "批量任务不存在或已删除"));
+                    refresh_saved_jobs(set_saved_jobs).await;
+                    return;
+                }
+                Err(e) => {
+                    error_handler.handle_error(e);
                     return;
                 }
+            };
+            if retry_failed && job.reset_failed() == 0 {
+                error_handler.handle_error(AppError::validation("任务", "没有需要重试的文档"));
+                return;
+            }
 
-                set_is_processing.set(true);
-                set_translated_docs.set(Vec::new());
-                set_progress.set(BatchProgress {
-                    total: 0,
-                    completed: 0,
-                    current_task: "开始处理...".to_string(),
-                    failed_count: 0,
-                    status: BatchStatus::Parsing,
-                });
+            set_is_processing.set(true);
+            set_active_job_id.set(Some(job.id.clone()));
+            set_source_url.set(job.index_url.clone());
+            set_documents.set(job.documents.iter().map(|doc| doc.link.clone()).collect());
+            set_translated_docs.set(job.completed_documents());
+            set_failures.set(job.failures());
+            set_changelog.set(None);
+            set_progress.set(BatchProgress {
+                total: job.pending_count(),
+                completed: 0,
+                current_task: if retry_failed {
+                    format!("重新翻译 {} 个失败的文档", job.pending_count())
+                } else {
+                    format!(
+                        "继续翻译剩余 {} 个文档（已完成 {} 个）",
+                        job.pending_count(),
+                        job.completed_count()
+                    )
+                },
+                failed_count: 0,
+                status: BatchStatus::Translating,
+            });
+
+            let run_control = BatchControl::new();
+            control.set_value(run_control.clone());
+
+            web_sys::console::log_1(&format!("=== 恢复批量任务: {} ===", job.index_url).into());
+
+            match ConfigService::new().get_config() {
+                Ok(config) => {
+                    run_batch_job(job, config, run_control, job_signals, error_handler).await;
+                }
+                Err(e) => report_config_error(e, set_progress, error_handler),
+            }
 
-                let set_progress_clone = set_progress.clone();
-                let set_is_processing_clone = set_is_processing.clone();
-                let set_documents_clone = set_documents.clone();
-                let set_translated_docs_clone = set_translated_docs.clone();
+            refresh_saved_jobs(set_saved_jobs).await;
+            set_is_processing.set(false);
+        });
+    };
 
-                spawn_local(async move {
-                    web_sys::console::log_1(&"=== 开始批量翻译流程 ===".into());
-                    web_sys::console::log_1(&format!("索引URL: {}", index_url).into());
-
-                    let config_service = ConfigService::new();
-
-                    match config_service.get_config() {
-                        Ok(config) => {
-                            web_sys::console::log_1(&"配置加载成功".into());
-                            let batch_service = BatchTranslationService::new(&config);
-
-                            // 步骤1: 解析文档索引
-                            web_sys::console::log_1(&"=== 步骤1: 解析文档索引 ===".into());
-                            set_progress_clone.set(BatchProgress {
-                                total: 0,
-                                completed: 0,
-                                current_task: "正在解析文档索引...".to_string(),
-                                failed_count: 0,
-                                status: BatchStatus::Parsing,
-                            });
-
-                            match batch_service.parse_document_index(&index_url).await {
-                                Ok(links) => {
-                                    web_sys::console::log_1(
-                                        &format!("索引解析成功，找到 {} 个文档", links.len())
-                                            .into(),
-                                    );
-                                    set_documents_clone.set(links.clone());
-
-                                    // 步骤2: 批量翻译
-                                    web_sys::console::log_1(&"=== 步骤2: 开始批量翻译 ===".into());
-
-                                    let total_links = links.len(); // 保存总数
-
-                                    let progress_callback = {
-                                        let set_progress_clone = set_progress_clone.clone();
-                                        move |progress: BatchProgress| {
-                                            set_progress_clone.set(progress);
-                                        }
-                                    };
-
-                                    match batch_service
-                                        .batch_translate(links, progress_callback)
-                                        .await
-                                    {
-                                        Ok(translated_documents) => {
-                                            web_sys::console::log_1(
-                                                &format!(
-                                                    "批量翻译完成，成功翻译 {} 个文档",
-                                                    translated_documents.len()
-                                                )
-                                                .into(),
-                                            );
-                                            set_translated_docs_clone
-                                                .set(translated_documents.clone());
-
-                                            // 步骤3: 创建ZIP文件
-                                            web_sys::console::log_1(
-                                                &"=== 步骤3: 创建ZIP文件 ===".into(),
-                                            );
-                                            set_progress_clone.set(BatchProgress {
-                                                total: translated_documents.len(),
-                                                completed: translated_documents.len(),
-                                                current_task: "正在打包文件...".to_string(),
-                                                failed_count: 0,
-                                                status: BatchStatus::Packaging,
-                                            });
-
-                                            match batch_service
-                                                .create_compressed_archive(&translated_documents)
-                                            {
-                                                Ok(compressed_data) => {
-                                                    web_sys::console::log_1(
-                                                        &"tar.gz文件创建成功".into(),
-                                                    );
-
-                                                    // 保存到历史记录
-                                                    web_sys::console::log_1(
-                                                        &"=== 步骤4: 保存历史记录 ===".into(),
-                                                    );
-                                                    let history_service = HistoryService::new();
-
-                                                    // 创建批量翻译数据
-                                                    let batch_document_list: Vec<
-                                                        BatchDocumentInfo,
-                                                    > = translated_documents
-                                                        .iter()
-                                                        .map(|doc| BatchDocumentInfo {
-                                                            title: doc.link.title.clone(),
-                                                            url: doc.link.url.clone(),
-                                                            file_name: doc.file_name.clone(),
-                                                            folder_path: doc.folder_path.clone(),
-                                                            order: doc.link.order,
-                                                            translated: true,
-                                                            original_content: doc
-                                                                .original_content
-                                                                .clone(),
-                                                            translated_content: doc
-                                                                .translated_content
-                                                                .clone(),
-                                                        })
-                                                        .collect();
-
-                                                    let failed_count =
-                                                        total_links - translated_documents.len();
-                                                    let batch_data = BatchTranslationData {
-                                                        total_documents: total_links,
-                                                        successful_documents: translated_documents
-                                                            .len(),
-                                                        failed_documents: failed_count,
-                                                        index_url: index_url.clone(),
-                                                        document_list: batch_document_list,
-                                                    };
-
-                                                    let title = format!(
-                                                        "批量翻译: {}",
-                                                        if let Some(first_doc) =
-                                                            translated_documents.first()
-                                                        {
-                                                            extract_domain_from_url(
-                                                                &first_doc.link.url,
-                                                            )
-                                                        } else {
-                                                            "未知网站".to_string()
-                                                        }
-                                                    );
-
-                                                    let history_entry =
-                                                        HistoryEntry::new_batch_translation(
-                                                            index_url.clone(),
-                                                            title,
-                                                            config.default_source_lang.clone(),
-                                                            config.default_target_lang.clone(),
-                                                            batch_data,
-                                                        );
-
-                                                    if let Err(e) =
-                                                        history_service.add_entry(history_entry)
-                                                    {
-                                                        web_sys::console::log_1(
-                                                            &format!("保存历史记录失败: {}", e)
-                                                                .into(),
-                                                        );
-                                                    } else {
-                                                        web_sys::console::log_1(
-                                                            &"批量翻译历史记录保存成功".into(),
-                                                        );
-                                                    }
-
-                                                    // 生成智能压缩文件名
-                                                    let archive_name =
-                                                        generate_archive_name(&index_url);
-
-                                                    // 触发下载
-                                                    if let Err(e) = trigger_download(
-                                                        &compressed_data,
-                                                        &archive_name,
-                                                    ) {
-                                                        web_sys::console::log_1(
-                                                            &format!("下载失败: {}", e).into(),
-                                                        );
-                                                        error_handler.handle_error(AppError::file(
-                                                            format!("下载失败: {}", e),
-                                                        ));
-                                                    }
-
-                                                    set_progress_clone.set(BatchProgress {
-                                                        total: translated_documents.len(),
-                                                        completed: translated_documents.len(),
-                                                        current_task: "批量翻译完成".to_string(),
-                                                        failed_count: 0,
-                                                        status: BatchStatus::Completed,
-                                                    });
-
-                                                    web_sys::console::log_1(
-                                                        &"=== 批量翻译流程完成 ===".into(),
-                                                    );
-                                                }
-                                                Err(e) => {
-                                                    web_sys::console::log_1(
-                                                        &format!("ZIP创建失败: {}", e).into(),
-                                                    );
-                                                    let error_msg = format!("打包失败: {}", e);
-                                                    set_progress_clone.set(BatchProgress {
-                                                        total: 0,
-                                                        completed: 0,
-                                                        current_task: error_msg.clone(),
-                                                        failed_count: 0,
-                                                        status: BatchStatus::Failed(
-                                                            error_msg.clone(),
-                                                        ),
-                                                    });
-                                                    error_handler
-                                                        .handle_error(AppError::file(error_msg));
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            web_sys::console::log_1(
-                                                &format!("批量翻译失败: {}", e).into(),
-                                            );
-                                            let error_msg = format!("批量翻译失败: {}", e);
-                                            set_progress_clone.set(BatchProgress {
-                                                total: 0,
-                                                completed: 0,
-                                                current_task: error_msg.clone(),
-                                                failed_count: 0,
-                                                status: BatchStatus::Failed(error_msg.clone()),
-                                            });
-                                            error_handler
-                                                .handle_error(AppError::translation(error_msg));
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    web_sys::console::log_1(&format!("索引解析失败: {}", e).into());
-                                    let error_msg = format!("索引解析失败: {}", e);
-                                    set_progress_clone.set(BatchProgress {
-                                        total: 0,
-                                        completed: 0,
-                                        current_task: error_msg.clone(),
-                                        failed_count: 0,
-                                        status: BatchStatus::Failed(error_msg.clone()),
-                                    });
-                                    error_handler.handle_error(AppError::extraction(error_msg));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            web_sys::console::log_1(&format!("配置加载失败: {}", e).into());
-                            let error_msg = format!("配置加载失败: {}", e);
-                            set_progress_clone.set(BatchProgress {
-                                total: 0,
-                                completed: 0,
-                                current_task: error_msg.clone(),
-                                failed_count: 0,
-                                status: BatchStatus::Failed(error_msg.clone()),
-                            });
-                            error_handler.handle_error(AppError::config(error_msg));
-                        }
-                    }
+    // 恢复任务Effect
+    create_effect(move |_| {
+        if let Some(job_id) = resume_trigger.get() {
+            run_saved_job(job_id, false);
+        }
+    });
 
-                    set_is_processing_clone.set(false);
-                });
-            }
+    // 只重试失败文档的Effect，结果合并到已完成的译文中
+    create_effect(move |_| {
+        if let Some(job_id) = retry_trigger.get() {
+            run_saved_job(job_id, true);
+        }
+    });
+
+    // 暂停、继续或取消Effect
+    create_effect(move |_| {
+        if let Some(command) = command_trigger.get() {
+            let run_control = control.get_value();
+            let (status, message) = match command {
+                BatchCommand::Pause => {
+                    run_control.pause();
+                    (BatchStatus::Paused, "已暂停，正在处理的文档完成后停止")
+                }
+                BatchCommand::Resume => {
+                    run_control.resume();
+                    (BatchStatus::Translating, "继续翻译...")
+                }
+                BatchCommand::Cancel => {
+                    run_control.cancel();
+                    (BatchStatus::Cancelled, "正在取消，等待进行中的请求结束...")
+                }
+            };
+            web_sys::console::log_1(&format!("批量任务操作: {:?}", command).into());
+            set_progress.update(|progress| {
+                progress.status = status;
+                progress.current_task = message.to_string();
+            });
+        }
+    });
+
+    // 删除保存的任务
+    create_effect(move |_| {
+        if let Some(job_id) = discard_trigger.get() {
+            spawn_local(async move {
+                let deleted = match BatchJobService::open().await {
+                    Ok(job_service) => job_service.delete_job(&job_id).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = deleted {
+                    web_sys::console::log_1(&format!("删除批量任务失败: {}", e).into());
+                }
+                refresh_saved_jobs(set_saved_jobs).await;
+            });
         }
     });
 
@@ -316,7 +346,365 @@ pub fn use_batch_translation() -> UseBatchTranslationReturn {
         progress,
         documents,
         translated_docs,
+        failures,
+        changelog,
+        active_job_id,
+        saved_jobs,
+        discover_documents: set_discover_trigger,
         start_batch_translation: set_start_trigger,
+        resume_job: set_resume_trigger,
+        retry_failed: set_retry_trigger,
+        discard_job: set_discard_trigger,
+        send_command: set_command_trigger,
+    }
+}
+
+/// 翻译任务中未处理的文档，每完成一个就保存断点，全部处理后打包下载并写入历史记录。
+/// 任务取消时保留已完成的译文供手动打包，未处理的文档可稍后继续
+async fn run_batch_job(
+    job: BatchJob,
+    config: AppConfig,
+    control: BatchControl,
+    signals: JobSignals,
+    error_handler: ErrorHandler,
+) {
+    reset_session_stats();
+    // 断点无法保存时仍然翻译，但提示用户任务中断后不能继续
+    let job_service = match BatchJobService::open().await {
+        Ok(job_service) => Some(job_service),
+        Err(e) => {
+            report_checkpoint_error(&e, error_handler);
+            None
+        }
+    };
+    if let Some(job_service) = &job_service {
+        let saved = match job_service.save_job(&job).await {
+            Ok(()) => job_service.prune_jobs().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = saved {
+            report_checkpoint_error(&e, error_handler);
+        }
+    }
+
+    // 同一站点上次的翻译结果，原文未变的文档沿用上次的译文
+    let previous = HistoryService::new()
+        .find_batch_entry(
+            &job.index_url,
+            &job.source_lang,
+            &job.target_lang,
+            &job.created_at,
+        )
+        .and_then(|entry| PreviousTranslations::for_job(&entry, &job));
+    if let Some(previous) = &previous {
+        web_sys::console::log_1(
+            &format!(
+                "找到 {} 的翻译记录，只翻译新增或变化的文档",
+                previous.translated_at()
+            )
+            .into(),
+        );
+    }
+
+    let pending = job.pending_links();
+    let links: Vec<DocumentLink> = job.documents.iter().map(|doc| doc.link.clone()).collect();
+    let job = Rc::new(RefCell::new(job));
+    let batch_service =
+        BatchTranslationService::new(&config).with_previous_translations(previous.clone());
+
+    // 步骤2: 批量翻译
+    web_sys::console::log_1(
+        &format!("=== 步骤2: 开始批量翻译，剩余 {} 个文档 ===", pending.len()).into(),
+    );
+
+    // 连续保存失败时只提示一次，保存恢复正常后再次失败时重新提示
+    let checkpoint_failed = Rc::new(Cell::new(false));
+    let checkpoint = {
+        let job = job.clone();
+        let job_service = job_service.clone();
+        move |link: &DocumentLink, result: Result<&TranslatedDocument, &AppError>| {
+            let mut job = job.borrow_mut();
+            match result {
+                Ok(doc) => job.record_success(doc),
+                Err(e) => job.record_failure(link, e),
+            }
+            if let Some(job_service) = &job_service {
+                // 写入事务在这里按完成顺序创建，后台等待提交
+                let saving = job_service.save_job(&job);
+                let checkpoint_failed = checkpoint_failed.clone();
+                spawn_local(async move {
+                    let saved = saving.await;
+                    if let Err(e) = &saved {
+                        if !checkpoint_failed.get() {
+                            report_checkpoint_error(e, error_handler);
+                        }
+                    }
+                    checkpoint_failed.set(saved.is_err());
+                });
+            }
+            // 暂停或取消时也能查看和打包已完成的部分
+            signals.translated_docs.set(job.completed_documents());
+            signals.failures.set(job.failures());
+        }
+    };
+
+    let outcome = batch_service
+        .batch_translate(
+            pending,
+            &control,
+            move |progress: BatchProgress| signals.progress.set(progress),
+            checkpoint,
+        )
+        .await;
+
+    // 等待中的断点写入会先提交，再保存一次最终状态；仍然失败时删除过期的断点，
+    // 以免继续任务时恢复到旧的进度
+    if let Some(job_service) = &job_service {
+        let snapshot = job.borrow().clone();
+        if let Err(e) = job_service.save_job(&snapshot).await {
+            report_checkpoint_error(&e, error_handler);
+            if let Err(e) = job_service.delete_job(&snapshot.id).await {
+                web_sys::console::log_1(&format!("删除过期的批量任务断点失败: {}", e).into());
+            }
+        }
+    }
+
+    match outcome {
+        Ok(result) => {
+            web_sys::console::log_1(
+                &format!(
+                    "本次翻译成功 {} 个，失败 {} 个",
+                    result.documents.len(),
+                    result.failures.len()
+                )
+                .into(),
+            );
+            for failure in &result.failures {
+                web_sys::console::log_1(
+                    &format!(
+                        "✗ {} ({}): {}",
+                        failure.link.title, failure.link.url, failure.error
+                    )
+                    .into(),
+                );
+            }
+        }
+        Err(e) => {
+            web_sys::console::log_1(&format!("批量翻译失败: {}", e).into());
+            let error_msg = format!("批量翻译失败: {}", e);
+            signals.progress.set(BatchProgress {
+                total: 0,
+                completed: 0,
+                current_task: error_msg.clone(),
+                failed_count: 0,
+                status: BatchStatus::Failed(error_msg.clone()),
+            });
+            error_handler.handle_error(AppError::translation(error_msg));
+            return;
+        }
+    }
+
+    let job = job.borrow().clone();
+    let translated_documents = job.completed_documents();
+    let failures = job.failures();
+    let total_links = job.total_count();
+    web_sys::console::log_1(
+        &format!(
+            "批量翻译完成，成功翻译 {} 个文档",
+            translated_documents.len()
+        )
+        .into(),
+    );
+    signals.translated_docs.set(translated_documents.clone());
+    signals.failures.set(failures.clone());
+
+    let changelog = previous
+        .as_ref()
+        .map(|previous| Changelog::build(previous, &links, &translated_documents));
+    if let Some(changelog) = &changelog {
+        web_sys::console::log_1(&format!("变更记录: {}", changelog.summary()).into());
+    }
+    signals.changelog.set(changelog.clone());
+
+    if control.is_cancelled() {
+        web_sys::console::log_1(
+            &format!(
+                "批量任务已取消，保留 {} 个已完成的文档，剩余 {} 个可稍后继续",
+                translated_documents.len(),
+                job.pending_count()
+            )
+            .into(),
+        );
+        return;
+    }
+
+    // 全部成功的任务不再需要断点，有失败文档时保留以便只重试失败的文档
+    if let Some(job_service) = job_service.filter(|_| job.failed_count() == 0) {
+        if let Err(e) = job_service.delete_job(&job.id).await {
+            web_sys::console::log_1(&format!("删除批量任务失败: {}", e).into());
+        }
+    }
+
+    if translated_documents.is_empty() {
+        signals.progress.set(BatchProgress {
+            total: total_links,
+            completed: 0,
+            current_task: "没有成功翻译的文档".to_string(),
+            failed_count: failures.len(),
+            status: BatchStatus::Completed,
+        });
+        return;
+    }
+
+    // 步骤3: 创建压缩文件
+    web_sys::console::log_1(&"=== 步骤3: 创建ZIP文件 ===".into());
+    signals.progress.set(BatchProgress {
+        total: translated_documents.len(),
+        completed: translated_documents.len(),
+        current_task: "正在打包文件...".to_string(),
+        failed_count: 0,
+        status: BatchStatus::Packaging,
+    });
+
+    match batch_service.create_compressed_archive(
+        &translated_documents,
+        &failures,
+        changelog.as_ref(),
+    ) {
+        Ok(compressed_data) => {
+            web_sys::console::log_1(&"tar.gz文件创建成功".into());
+
+            // 保存到历史记录
+            web_sys::console::log_1(&"=== 步骤4: 保存历史记录 ===".into());
+            let history_service = HistoryService::new();
+
+            // 创建批量翻译数据
+            let batch_document_list: Vec<BatchDocumentInfo> = translated_documents
+                .iter()
+                .map(|doc| BatchDocumentInfo {
+                    title: doc.link.title.clone(),
+                    url: doc.link.url.clone(),
+                    file_name: doc.file_name.clone(),
+                    folder_path: doc.folder_path.clone(),
+                    order: doc.link.order,
+                    translated: true,
+                    original_content: doc.original_content.clone(),
+                    translated_content: doc.translated_content.clone(),
+                    engine_usage: doc.engine_usage.clone(),
+                    content_hash: doc.content_hash.clone(),
+                })
+                .collect();
+
+            let batch_data = BatchTranslationData {
+                total_documents: total_links,
+                successful_documents: translated_documents.len(),
+                failed_documents: total_links - translated_documents.len(),
+                index_url: job.index_url.clone(),
+                document_list: batch_document_list,
+                job_id: job.id.clone(),
+            };
+
+            let title = format!(
+                "批量翻译: {}",
+                if let Some(first_doc) = translated_documents.first() {
+                    extract_domain_from_url(&first_doc.link.url)
+                } else {
+                    "未知网站".to_string()
+                }
+            );
+
+            let history_entry = HistoryEntry::new_batch_translation(
+                job.index_url.clone(),
+                title,
+                job.source_lang.clone(),
+                job.target_lang.clone(),
+                batch_data,
+            )
+            .with_engine_usage(merge_engine_usage(
+                translated_documents
+                    .iter()
+                    .flat_map(|doc| doc.engine_usage.iter().copied()),
+            ));
+
+            if let Err(e) = history_service.add_entry(history_entry) {
+                web_sys::console::log_1(&format!("保存历史记录失败: {}", e).into());
+            } else {
+                web_sys::console::log_1(&"批量翻译历史记录保存成功".into());
+            }
+
+            // 生成智能压缩文件名
+            let archive_name = generate_archive_name(&job.index_url);
+
+            // 触发下载
+            if let Err(e) = trigger_download(&compressed_data, &archive_name) {
+                web_sys::console::log_1(&format!("下载失败: {}", e).into());
+                error_handler.handle_error(AppError::file(format!("下载失败: {}", e)));
+            }
+
+            signals.progress.set(BatchProgress {
+                total: translated_documents.len(),
+                completed: translated_documents.len(),
+                current_task: if failures.is_empty() {
+                    "批量翻译完成".to_string()
+                } else {
+                    format!("批量翻译完成，{} 个文档失败，可稍后重试", failures.len())
+                },
+                failed_count: failures.len(),
+                status: BatchStatus::Completed,
+            });
+
+            web_sys::console::log_1(&"=== 批量翻译流程完成 ===".into());
+        }
+        Err(e) => {
+            web_sys::console::log_1(&format!("ZIP创建失败: {}", e).into());
+            let error_msg = format!("打包失败: {}", e);
+            signals.progress.set(BatchProgress {
+                total: 0,
+                completed: 0,
+                current_task: error_msg.clone(),
+                failed_count: 0,
+                status: BatchStatus::Failed(error_msg.clone()),
+            });
+            error_handler.handle_error(AppError::file(error_msg));
+        }
+    }
+}
+
+fn report_config_error(
+    e: Box<dyn std::error::Error>,
+    set_progress: WriteSignal<BatchProgress>,
+    error_handler: ErrorHandler,
+) {
+    web_sys::console::log_1(&format!("配置加载失败: {}", e).into());
+    let error_msg = format!("配置加载失败: {}", e);
+    set_progress.set(BatchProgress {
+        total: 0,
+        completed: 0,
+        current_task: error_msg.clone(),
+        failed_count: 0,
+        status: BatchStatus::Failed(error_msg.clone()),
+    });
+    error_handler.handle_error(AppError::config(error_msg));
+}
+
+/// 断点无法保存时告知用户：任务仍会继续，但中断后不能从断点恢复
+fn report_checkpoint_error(e: &AppError, error_handler: ErrorHandler) {
+    web_sys::console::log_1(&format!("保存批量任务断点失败: {}", e).into());
+    error_handler.handle_error(AppError::file(format!(
+        "保存批量任务断点失败，任务中断后无法继续: {}",
+        e
+    )));
+}
+
+/// 重新读取可以继续的批量任务
+async fn refresh_saved_jobs(set_saved_jobs: WriteSignal<Vec<BatchJob>>) {
+    let jobs = match BatchJobService::open().await {
+        Ok(job_service) => job_service.resumable_jobs().await,
+        Err(e) => Err(e),
+    };
+    match jobs {
+        Ok(jobs) => set_saved_jobs.set(jobs),
+        Err(e) => web_sys::console::log_1(&format!("读取批量任务失败: {}", e).into()),
     }
 }
 
@@ -1,8 +1,13 @@
 use crate::error::{use_error_handler, AppError};
+use crate::hooks::use_translation::extract_title_from_content;
 use crate::services::{
     config_service::ConfigService,
-    preview_service::{PreviewContent, PreviewOptions, PreviewService},
+    history_service::HistoryService,
+    preview_service::{ComparisonContent, PreviewContent, PreviewOptions, PreviewService},
+    translator::format_engine_usage,
 };
+use crate::types::api_types::TranslationEngine;
+use crate::types::history::HistoryEntry;
 use leptos::*;
 use wasm_bindgen_futures::spawn_local;
 
@@ -106,6 +111,139 @@ pub fn use_preview() -> UsePreviewReturn {
     }
 }
 
+pub struct UseComparisonReturn {
+    pub is_loading: ReadSignal<bool>,
+    pub comparison: ReadSignal<Option<ComparisonContent>>,
+    /// 每段选用的列序号
+    pub choices: RwSignal<Vec<usize>>,
+    pub generate_comparison: WriteSignal<Option<(String, Vec<TranslationEngine>)>>,
+    pub save_to_history: WriteSignal<Option<String>>,
+    pub is_saved: ReadSignal<bool>,
+}
+
+/// 多引擎对比Hook
+pub fn use_comparison() -> UseComparisonReturn {
+    let error_handler = use_error_handler();
+
+    let (is_loading, set_is_loading) = create_signal(false);
+    let (comparison, set_comparison) = create_signal(None::<ComparisonContent>);
+    let choices = create_rw_signal(Vec::<usize>::new());
+    let (generate_trigger, set_generate_trigger) =
+        create_signal(None::<(String, Vec<TranslationEngine>)>);
+    let (save_trigger, set_save_trigger) = create_signal(None::<String>);
+    let (is_saved, set_is_saved) = create_signal(false);
+
+    // 对比翻译效果
+    create_effect(move |_| {
+        if let Some((url, engines)) = generate_trigger.get() {
+            if url.is_empty() {
+                error_handler.handle_error(AppError::validation("URL", "请输入有效的URL"));
+                return;
+            }
+            if engines.len() < 2 {
+                error_handler.handle_error(AppError::validation("引擎", "请至少选择两个翻译引擎"));
+                return;
+            }
+
+            set_is_loading.set(true);
+            set_comparison.set(None);
+            set_is_saved.set(false);
+
+            spawn_local(async move {
+                web_sys::console::log_1(&format!("对比URL: {}", url).into());
+
+                match ConfigService::new().get_config() {
+                    Ok(config) => {
+                        let preview_service = PreviewService::new(&config);
+
+                        match preview_service
+                            .generate_comparison(&url, &config, &engines)
+                            .await
+                        {
+                            Ok(content) => {
+                                choices.set(content.default_choices());
+                                set_comparison.set(Some(content));
+                            }
+                            Err(e) => {
+                                web_sys::console::log_1(&format!("对比翻译失败: {}", e).into());
+                                error_handler.handle_error(AppError::translation(format!(
+                                    "对比翻译失败: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("配置加载失败: {}", e).into());
+                        error_handler
+                            .handle_error(AppError::config(format!("配置加载失败: {}", e)));
+                    }
+                }
+
+                set_is_loading.set(false);
+            });
+        }
+    });
+
+    // 保存所选译文到历史记录
+    create_effect(move |_| {
+        let Some(url) = save_trigger.get() else {
+            return;
+        };
+        let Some(content) = comparison.get_untracked() else {
+            return;
+        };
+
+        let config = match ConfigService::new().get_config() {
+            Ok(config) => config,
+            Err(e) => {
+                error_handler.handle_error(AppError::config(format!("配置加载失败: {}", e)));
+                return;
+            }
+        };
+
+        let selected = choices.get_untracked();
+        let engine_usage = content.engine_usage(&selected);
+        web_sys::console::log_1(
+            &format!("对比结果选用: {}", format_engine_usage(&engine_usage)).into(),
+        );
+
+        let history_entry = HistoryEntry::new(
+            url,
+            if content.title.is_empty() {
+                extract_title_from_content(&content.original_text)
+            } else {
+                content.title.clone()
+            },
+            config.default_source_lang.clone(),
+            config.default_target_lang.clone(),
+            content.original_text.clone(),
+            content.compose(&selected),
+        )
+        .with_engine_usage(engine_usage)
+        .with_paragraph_engines(content.chosen_engines(&selected));
+
+        match HistoryService::new().add_entry(history_entry) {
+            Ok(()) => {
+                web_sys::console::log_1(&"对比结果已保存到历史记录".into());
+                set_is_saved.set(true);
+            }
+            Err(e) => {
+                error_handler.handle_error(AppError::unknown(format!("保存历史记录失败: {}", e)));
+            }
+        }
+    });
+
+    UseComparisonReturn {
+        is_loading,
+        comparison,
+        choices,
+        generate_comparison: set_generate_trigger,
+        save_to_history: set_save_trigger,
+        is_saved,
+    }
+}
+
 /// 快速验证Hook
 pub fn use_quick_validation() -> (
     ReadSignal<bool>,
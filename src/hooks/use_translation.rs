@@ -1,7 +1,13 @@
 use crate::error::{use_error_handler, AppError};
 use crate::services::{
-    config_service::ConfigService, content_processor::ContentProcessor,
-    deeplx_service::DeepLXService, history_service::HistoryService, jina_service::JinaService,
+    config_service::ConfigService,
+    content_processor::ContentProcessor,
+    extractor::create_extractor,
+    history_service::HistoryService,
+    incremental::{translate_changed_paragraphs, ParagraphDiff},
+    local_file_service::{convert_local_file, local_file_url, pasted_text_content, LocalFile},
+    translation_memory::reset_session_stats,
+    translator::{create_translator, format_engine_usage, TranslationOutcome},
 };
 use crate::types::history::HistoryEntry;
 use leptos::*;
@@ -21,7 +27,32 @@ pub struct UseTranslationReturn {
     pub translation_result: ReadSignal<String>,
     pub progress_message: ReadSignal<String>,
     pub status: ReadSignal<TranslationStatus>,
+    /// 提取器给出的文档标题，未提供时为空
+    pub document_title: ReadSignal<String>,
     pub translate: WriteSignal<Option<String>>,
+    /// 翻译上传的本地文件
+    pub translate_file: WriteSignal<Option<LocalFile>>,
+    /// 直接翻译粘贴的文本或Markdown，不经过内容提取
+    pub translate_text: WriteSignal<Option<String>>,
+}
+
+/// 待翻译内容的来源
+#[derive(Clone, Debug)]
+enum TranslationSource {
+    Url(String),
+    File(LocalFile),
+    Text(String),
+}
+
+impl TranslationSource {
+    /// 写入历史记录的来源地址
+    fn origin_url(&self) -> String {
+        match self {
+            Self::Url(url) => url.clone(),
+            Self::File(file) => local_file_url(&file.name),
+            Self::Text(_) => String::new(),
+        }
+    }
 }
 
 pub fn use_translation() -> UseTranslationReturn {
@@ -31,7 +62,37 @@ pub fn use_translation() -> UseTranslationReturn {
     let (translation_result, set_translation_result) = create_signal(String::new());
     let (progress_message, set_progress_message) = create_signal(String::new());
     let (status, set_status) = create_signal(TranslationStatus::Idle);
+    let (document_title, set_document_title) = create_signal(String::new());
     let (translate_trigger, set_translate_trigger) = create_signal(None::<String>);
+    let (file_trigger, set_file_trigger) = create_signal(None::<LocalFile>);
+    let (text_trigger, set_text_trigger) = create_signal(None::<String>);
+    let (source_trigger, set_source_trigger) = create_signal(None::<TranslationSource>);
+
+    create_effect(move |_| {
+        if let Some(url) = translate_trigger.get() {
+            if url.is_empty() {
+                error_handler.handle_error(AppError::validation("URL", "请输入有效的URL"));
+                return;
+            }
+            set_source_trigger.set(Some(TranslationSource::Url(url)));
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(file) = file_trigger.get() {
+            set_source_trigger.set(Some(TranslationSource::File(file)));
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(text) = text_trigger.get() {
+            if text.trim().is_empty() {
+                error_handler.handle_error(AppError::validation("文本", "请输入要翻译的文本"));
+                return;
+            }
+            set_source_trigger.set(Some(TranslationSource::Text(text)));
+        }
+    });
 
     // Effect to handle translation when trigger changes
     create_effect({
@@ -41,16 +102,17 @@ pub fn use_translation() -> UseTranslationReturn {
         let set_status = set_status.clone();
 
         move |_| {
-            if let Some(url) = translate_trigger.get() {
-                if url.is_empty() {
-                    error_handler.handle_error(AppError::validation("URL", "请输入有效的URL"));
-                    return;
-                }
-
+            if let Some(source) = source_trigger.get() {
                 set_is_loading.set(true);
                 set_translation_result.set(String::new());
+                set_document_title.set(String::new());
                 set_status.set(TranslationStatus::ExtractingContent);
-                set_progress_message.set("正在提取网页内容...".to_string());
+                reset_session_stats();
+                set_progress_message.set(match &source {
+                    TranslationSource::Url(_) => "正在提取网页内容...".to_string(),
+                    TranslationSource::File(_) => "正在读取文件内容...".to_string(),
+                    TranslationSource::Text(_) => "正在准备文本...".to_string(),
+                });
 
                 let set_progress_clone = set_progress_message.clone();
                 let set_loading_clone = set_is_loading.clone();
@@ -59,6 +121,7 @@ pub fn use_translation() -> UseTranslationReturn {
 
                 spawn_local(async move {
                     web_sys::console::log_1(&"=== 开始翻译流程 ===".into());
+                    let url = source.origin_url();
                     web_sys::console::log_1(&format!("URL: {}", url).into());
 
                     let config_service = ConfigService::new();
@@ -67,15 +130,28 @@ pub fn use_translation() -> UseTranslationReturn {
                     match config_service.get_config() {
                         Ok(config) => {
                             web_sys::console::log_1(&"配置加载成功，创建服务...".into());
-                            let jina_service = JinaService::new(&config);
-                            let deeplx_service = DeepLXService::new(&config);
+                            let extractor = create_extractor(&config);
+                            let translator = create_translator(&config);
 
                             // 步骤1: 提取内容
                             web_sys::console::log_1(&"=== 步骤1: 开始提取网页内容 ===".into());
-                            set_progress_clone.set("正在提取网页内容...".to_string());
 
-                            match jina_service.extract_content(&url, &config).await {
-                                Ok(content) => {
+                            let extraction = match &source {
+                                TranslationSource::Url(url) => {
+                                    set_progress_clone.set("正在提取网页内容...".to_string());
+                                    extractor.extract(url).await
+                                }
+                                TranslationSource::File(file) => {
+                                    set_progress_clone.set("正在读取文件内容...".to_string());
+                                    convert_local_file(file)
+                                }
+                                TranslationSource::Text(text) => pasted_text_content(text),
+                            };
+
+                            match extraction {
+                                Ok(extracted) => {
+                                    let content = extracted.document();
+                                    set_document_title.set(extracted.title.clone());
                                     web_sys::console::log_1(
                                         &format!("内容提取成功，长度: {} 字符", content.len())
                                             .into(),
@@ -103,30 +179,86 @@ pub fn use_translation() -> UseTranslationReturn {
                                     set_status_clone.set(TranslationStatus::Translating);
                                     set_progress_clone.set("正在翻译内容...".to_string());
 
-                                    match deeplx_service
-                                        .translate(
-                                            &protected_content,
-                                            &config.default_source_lang,
-                                            &config.default_target_lang,
-                                            &config,
-                                        )
-                                        .await
-                                    {
-                                        Ok(translated_protected_content) => {
+                                    // 流式引擎每收到新内容就刷新结果，其余引擎在完成时刷新一次
+                                    let on_progress = |partial: &str| {
+                                        set_result_clone
+                                            .set(content_processor.restore_code_blocks(partial));
+                                    };
+
+                                    // 同一URL翻译过时只翻译变化的段落，其余沿用上次的译文
+                                    let diff = match &source {
+                                        TranslationSource::Text(_) => None,
+                                        _ => HistoryService::new()
+                                            .find_page_entry(
+                                                &url,
+                                                &config.default_source_lang,
+                                                &config.default_target_lang,
+                                            )
+                                            .and_then(|entry| {
+                                                ParagraphDiff::new(
+                                                    &content,
+                                                    &entry.original_content,
+                                                    &entry.translated_content,
+                                                )
+                                            })
+                                            .filter(|diff| diff.reused_count() > 0),
+                                    };
+
+                                    let translation = match &diff {
+                                        Some(diff) => {
                                             web_sys::console::log_1(
                                                 &format!(
-                                                    "翻译成功，长度: {} 字符",
-                                                    translated_protected_content.len()
+                                                    "段落增量翻译: {} 段中 {} 段有变化",
+                                                    diff.paragraph_count(),
+                                                    diff.changed_count()
                                                 )
                                                 .into(),
                                             );
+                                            set_progress_clone.set(format!(
+                                                "正在翻译变化的 {} 段...",
+                                                diff.changed_count()
+                                            ));
+                                            translate_changed_paragraphs(
+                                                diff,
+                                                translator.as_ref(),
+                                                &config.default_source_lang,
+                                                &config.default_target_lang,
+                                            )
+                                            .await
+                                        }
+                                        None => translator
+                                            .translate_with_report(
+                                                &protected_content,
+                                                &config.default_source_lang,
+                                                &config.default_target_lang,
+                                                &on_progress,
+                                            )
+                                            .await
+                                            .map(|outcome| TranslationOutcome {
+                                                text: content_processor
+                                                    .restore_code_blocks(&outcome.text),
+                                                ..outcome
+                                            }),
+                                    };
 
-                                            // 步骤4: 恢复代码块
+                                    match translation {
+                                        Ok(outcome) => {
+                                            let engine_usage = outcome.engine_usage();
+                                            let final_translated_content = outcome.text;
+                                            web_sys::console::log_1(
+                                                &format!(
+                                                    "翻译引擎统计: {}",
+                                                    format_engine_usage(&engine_usage)
+                                                )
+                                                .into(),
+                                            );
                                             web_sys::console::log_1(
-                                                &"=== 步骤4: 恢复代码块 ===".into(),
+                                                &format!(
+                                                    "翻译成功，长度: {} 字符",
+                                                    final_translated_content.len()
+                                                )
+                                                .into(),
                                             );
-                                            let final_translated_content = content_processor
-                                                .restore_code_blocks(&translated_protected_content);
 
                                             set_result_clone.set(final_translated_content.clone());
                                             set_status_clone.set(TranslationStatus::Completed);
@@ -134,15 +266,33 @@ pub fn use_translation() -> UseTranslationReturn {
 
                                             // 保存到历史记录
                                             let history_service = HistoryService::new();
-                                            let title = extract_title_from_content(&content);
-                                            let history_entry = HistoryEntry::new(
-                                                url.clone(),
-                                                title,
-                                                config.default_source_lang.clone(),
-                                                config.default_target_lang.clone(),
-                                                content,
-                                                final_translated_content,
-                                            );
+                                            let title = if extracted.title.is_empty() {
+                                                extract_title_from_content(&content)
+                                            } else {
+                                                extracted.title.clone()
+                                            };
+                                            let source_lang = config.default_source_lang.clone();
+                                            let target_lang = config.default_target_lang.clone();
+                                            let history_entry = match &source {
+                                                TranslationSource::Text(_) => {
+                                                    HistoryEntry::new_pasted_text(
+                                                        title,
+                                                        source_lang,
+                                                        target_lang,
+                                                        content,
+                                                        final_translated_content,
+                                                    )
+                                                }
+                                                _ => HistoryEntry::new(
+                                                    url.clone(),
+                                                    title,
+                                                    source_lang,
+                                                    target_lang,
+                                                    content,
+                                                    final_translated_content,
+                                                ),
+                                            }
+                                            .with_engine_usage(engine_usage);
 
                                             if let Err(e) = history_service.add_entry(history_entry)
                                             {
@@ -159,8 +309,11 @@ pub fn use_translation() -> UseTranslationReturn {
                                             web_sys::console::log_1(
                                                 &format!("翻译失败: {}", e).into(),
                                             );
-                                            let error_msg =
-                                                format!("翻译失败: {}。请检查DeepLX API配置。", e);
+                                            let error_msg = format!(
+                                                "翻译失败: {}。请检查{} API配置。",
+                                                e,
+                                                translator.name()
+                                            );
                                             set_status_clone
                                                 .set(TranslationStatus::Failed(error_msg.clone()));
                                             error_handler
@@ -170,10 +323,19 @@ pub fn use_translation() -> UseTranslationReturn {
                                 }
                                 Err(e) => {
                                     web_sys::console::log_1(&format!("内容提取失败: {}", e).into());
-                                    let error_msg = format!(
-                                        "内容提取失败: {}。请检查URL是否有效，或Jina API是否可用。",
-                                        e
-                                    );
+                                    let error_msg = match &source {
+                                        TranslationSource::Url(_) => format!(
+                                            "内容提取失败: {}。请检查URL是否有效，或{}是否可用。",
+                                            e,
+                                            extractor.name()
+                                        ),
+                                        TranslationSource::File(file) => {
+                                            format!("文件 {} 读取失败: {}", file.name, e)
+                                        }
+                                        TranslationSource::Text(_) => {
+                                            format!("文本处理失败: {}", e)
+                                        }
+                                    };
                                     set_status_clone
                                         .set(TranslationStatus::Failed(error_msg.clone()));
                                     error_handler.handle_error(AppError::extraction(error_msg));
@@ -200,11 +362,14 @@ pub fn use_translation() -> UseTranslationReturn {
         translation_result,
         progress_message,
         status,
+        document_title,
         translate: set_translate_trigger,
+        translate_file: set_file_trigger,
+        translate_text: set_text_trigger,
     }
 }
 
-fn extract_title_from_content(content: &str) -> String {
+pub(crate) fn extract_title_from_content(content: &str) -> String {
     // 尝试从内容中提取标题
     let lines: Vec<&str> = content.lines().collect();
 
@@ -1,5 +1,6 @@
 use crate::hooks::use_history::use_history;
 use crate::services::history_service::{ExportFormat, HistoryService};
+use crate::services::translator::format_engine_usage;
 use crate::theme::use_theme_context;
 use crate::types::history::{HistoryEntryType, HistoryFilter, HistorySortBy};
 use leptos::*;
@@ -245,7 +246,7 @@ pub fn HistoryPage() -> impl IntoView {
 
                                     // 克隆所有在view!中需要使用的字段
                                     let entry_title = entry.title.clone();
-                                    let entry_url = entry.url.clone();
+                                    let entry_url = entry.source_label();
                                     let entry_source_lang = entry.source_lang.clone();
                                     let entry_target_lang = entry.target_lang.clone();
                                     let entry_word_count = entry.word_count;
@@ -254,6 +255,8 @@ pub fn HistoryPage() -> impl IntoView {
                                     let entry_translated_content = entry.translated_content.clone();
                                     let entry_type = entry.entry_type.clone();
                                     let entry_batch_data = entry.batch_data.clone();
+                                    let entry_engine_usage = format_engine_usage(&entry.engine_usage);
+                                    let entry_is_comparison = !entry.paragraph_engines.is_empty();
 
                                     let is_expanded = create_memo(move |_| {
                                         selected_entry_id.get() == Some(entry_id.clone())
@@ -299,6 +302,12 @@ pub fn HistoryPage() -> impl IntoView {
                                                         <span>{entry_formatted_date.clone()}</span>
                                                         <span>{format!("{} -> {}", entry_source_lang, entry_target_lang)}</span>
                                                         <span>{format!("{} 字", entry_word_count)}</span>
+                                                        {(!entry_engine_usage.is_empty()).then(|| view! {
+                                                            <span>{format!("引擎: {}", entry_engine_usage)}</span>
+                                                        })}
+                                                        {entry_is_comparison.then(|| view! {
+                                                            <span>"多引擎对比"</span>
+                                                        })}
                                                     </div>
                                                     <div class="text-sm themed-subtext0 mt-1 truncate">
                                                         {entry_url.clone()}
@@ -309,7 +318,7 @@ pub fn HistoryPage() -> impl IntoView {
                                                     // 下载按钮
                                                     {
                                                         match entry_type {
-                                                            HistoryEntryType::SinglePage => {
+                                                            HistoryEntryType::SinglePage | HistoryEntryType::PastedText => {
                                                                 let entry_id = entry_id_for_download_single.clone();
                                                                 view! {
                                                                     <button
@@ -374,7 +383,7 @@ pub fn HistoryPage() -> impl IntoView {
                                                 <div class="mt-4 space-y-4 border-t pt-4 themed-border-t">
                                                     {
                                                         match entry_type.clone() {
-                                                            HistoryEntryType::SinglePage => {
+                                                            HistoryEntryType::SinglePage | HistoryEntryType::PastedText => {
                                                                 view! {
                                                                     <div>
                                                                         <h4 class="font-medium themed-text mb-2">"原文内容"</h4>
@@ -1,9 +1,11 @@
 use crate::components::{
-    FileNamePreview, PreviewPanel, ProgressIndicator, TranslationResult, UrlInput,
+    FileNamePreview, FileUpload, PasteInput, PreviewPanel, ProgressIndicator, TranslationResult,
+    UrlInput,
 };
 use crate::hooks::use_config::use_config;
 use crate::hooks::use_translation::use_translation;
 use crate::services::file_naming_service::{FileNamingContext, FileNamingService};
+use crate::services::local_file_service::{local_file_url, LocalFile};
 use crate::theme::use_theme_context;
 use chrono::Utc;
 use leptos::*;
@@ -11,6 +13,13 @@ use leptos_router::*;
 use wasm_bindgen::prelude::*;
 use web_sys::{window, Blob, Url};
 
+/// 单页翻译的输入方式
+#[derive(Clone, Copy, PartialEq)]
+enum InputMode {
+    Url,
+    Text,
+}
+
 #[component]
 pub fn HomePage() -> impl IntoView {
     let translation = use_translation();
@@ -19,11 +28,43 @@ pub fn HomePage() -> impl IntoView {
     let (url, set_url) = create_signal(String::new());
     let (show_preview, set_show_preview) = create_signal(false);
 
+    let (input_mode, set_input_mode) = create_signal(InputMode::Url);
+    let (pasted_text, set_pasted_text) = create_signal(String::new());
+    // 当前翻译内容的来源地址，用于生成下载文件名，粘贴的文本为空
+    let (source_url, set_source_url) = create_signal(String::new());
+
     let handle_translate = move |_| {
         let url_value = url.get();
+        set_source_url.set(url_value.clone());
         translation.translate.set(Some(url_value));
     };
 
+    let handle_file = move |file: LocalFile| {
+        set_source_url.set(local_file_url(&file.name));
+        translation.translate_file.set(Some(file));
+    };
+
+    let handle_translate_text = move |_| {
+        set_source_url.set(String::new());
+        translation.translate_text.set(Some(pasted_text.get()));
+    };
+
+    let select_mode = move |mode: InputMode| {
+        set_input_mode.set(mode);
+        if mode == InputMode::Text {
+            set_show_preview.set(false);
+        }
+    };
+
+    let mode_tab_style = move |mode: InputMode| {
+        let theme = theme_context.get().theme;
+        if input_mode.get() == mode {
+            theme.button_primary_style()
+        } else {
+            theme.button_secondary_style()
+        }
+    };
+
     let download_markdown = move |_| {
         let content = translation.translation_result.get();
         if content.is_empty() {
@@ -31,15 +72,15 @@ pub fn HomePage() -> impl IntoView {
         }
 
         // 使用智能文件命名服务生成文件名
-        let current_url = url.get();
+        let current_url = source_url.get();
         let config = config_hook.config.get();
         let mut naming_service = FileNamingService::new(config.file_naming);
 
-        // 从翻译结果中提取标题（如果有的话）
-        let title = extract_title_from_content(&content).unwrap_or_else(|| {
-            // 如果无法从内容中提取标题，尝试从URL中提取
-            extract_title_from_url(&current_url)
-        });
+        // 优先使用提取器给出的标题，其次从翻译结果和URL中推断
+        let title = Some(translation.document_title.get())
+            .filter(|title| !title.is_empty())
+            .or_else(|| extract_title_from_content(&content))
+            .unwrap_or_else(|| extract_title_from_url(&current_url));
 
         let context = FileNamingContext {
             url: current_url,
@@ -117,44 +158,81 @@ pub fn HomePage() -> impl IntoView {
 
             <div class="rounded-lg shadow-lg p-6" style=move || theme_context.get().theme.card_style()>
                 <div class="space-y-4">
-                    <UrlInput
-                        url=url
-                        set_url=set_url
-                        on_submit=handle_translate
-                        is_loading=translation.is_loading
-                    />
-
-                    // 预览切换按钮
-                    <div class="flex items-center gap-4">
+                    // 输入方式切换
+                    <div class="flex gap-2">
                         <button
                             type="button"
-                            class="inline-flex items-center px-3 py-2 text-sm font-medium rounded-md border transition-colors"
-                            class:bg-blue-100=move || show_preview.get()
-                            class:border-blue-300=move || show_preview.get()
-                            class:text-blue-800=move || show_preview.get()
-                            class:dark:bg-blue-900=move || show_preview.get()
-                            class:dark:border-blue-700=move || show_preview.get()
-                            class:dark:text-blue-200=move || show_preview.get()
-                            class:bg-gray-100=move || !show_preview.get()
-                            class:border-gray-300=move || !show_preview.get()
-                            class:text-gray-700=move || !show_preview.get()
-                            class:dark:bg-gray-700=move || !show_preview.get()
-                            class:dark:border-gray-600=move || !show_preview.get()
-                            class:dark:text-gray-300=move || !show_preview.get()
-                            on:click=move |_| set_show_preview.update(|show| *show = !*show)
+                            class="px-4 py-2 text-sm rounded-md transition-colors hover:opacity-90"
+                            style=move || mode_tab_style(InputMode::Url)
+                            on:click=move |_| select_mode(InputMode::Url)
                         >
-                            <svg class="w-4 h-4 mr-2" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 12a3 3 0 11-6 0 3 3 0 016 0z"></path>
-                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M2.458 12C3.732 7.943 7.523 5 12 5c4.478 0 8.268 2.943 9.542 7-1.274 4.057-5.064 7-9.542 7-4.477 0-8.268-2.943-9.542-7z"></path>
-                            </svg>
-                            {move || if show_preview.get() { "隐藏预览" } else { "显示预览" }}
+                            "网页 / 文件"
+                        </button>
+                        <button
+                            type="button"
+                            class="px-4 py-2 text-sm rounded-md transition-colors hover:opacity-90"
+                            style=move || mode_tab_style(InputMode::Text)
+                            on:click=move |_| select_mode(InputMode::Text)
+                        >
+                            "粘贴文本"
                         </button>
-
-                        <span class="text-xs text-gray-500 dark:text-gray-400">
-                            "预览功能可以在正式翻译前查看前几段的翻译效果"
-                        </span>
                     </div>
 
+                    <Show
+                        when=move || input_mode.get() == InputMode::Url
+                        fallback=move || view! {
+                            <PasteInput
+                                text=pasted_text
+                                set_text=set_pasted_text
+                                on_submit=handle_translate_text
+                                is_loading=translation.is_loading
+                            />
+                        }
+                    >
+                        <UrlInput
+                            url=url
+                            set_url=set_url
+                            on_submit=handle_translate
+                            is_loading=translation.is_loading
+                        />
+
+                        <FileUpload
+                            on_file=handle_file
+                            is_loading=translation.is_loading
+                        />
+
+                        // 预览切换按钮
+                        <div class="flex items-center gap-4">
+                            <button
+                                type="button"
+                                class="inline-flex items-center px-3 py-2 text-sm font-medium rounded-md border transition-colors"
+                                class:bg-blue-100=move || show_preview.get()
+                                class:border-blue-300=move || show_preview.get()
+                                class:text-blue-800=move || show_preview.get()
+                                class:dark:bg-blue-900=move || show_preview.get()
+                                class:dark:border-blue-700=move || show_preview.get()
+                                class:dark:text-blue-200=move || show_preview.get()
+                                class:bg-gray-100=move || !show_preview.get()
+                                class:border-gray-300=move || !show_preview.get()
+                                class:text-gray-700=move || !show_preview.get()
+                                class:dark:bg-gray-700=move || !show_preview.get()
+                                class:dark:border-gray-600=move || !show_preview.get()
+                                class:dark:text-gray-300=move || !show_preview.get()
+                                on:click=move |_| set_show_preview.update(|show| *show = !*show)
+                            >
+                                <svg class="w-4 h-4 mr-2" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 12a3 3 0 11-6 0 3 3 0 016 0z"></path>
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M2.458 12C3.732 7.943 7.523 5 12 5c4.478 0 8.268 2.943 9.542 7-1.274 4.057-5.064 7-9.542 7-4.477 0-8.268-2.943-9.542-7z"></path>
+                                </svg>
+                                {move || if show_preview.get() { "隐藏预览" } else { "显示预览" }}
+                            </button>
+
+                            <span class="text-xs text-gray-500 dark:text-gray-400">
+                                "预览功能可以在正式翻译前查看前几段的翻译效果"
+                            </span>
+                        </div>
+                    </Show>
+
                     <ProgressIndicator
                         is_loading=translation.is_loading
                         progress_message=translation.progress_message
@@ -162,7 +240,7 @@ pub fn HomePage() -> impl IntoView {
                     />
 
                     // 文件名预览
-                    <Show when=move || !url.get().is_empty()>
+                    <Show when=move || input_mode.get() == InputMode::Url && !url.get().is_empty()>
                         <FileNamePreview
                             url=url
                         />
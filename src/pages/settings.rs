@@ -1,8 +1,22 @@
 use crate::components::ThemeSelector;
+use crate::error::AppResult;
 use crate::hooks::use_config::use_config;
+use crate::services::batch_service::{BatchConfig, MAX_BATCH_CONCURRENCY};
+use crate::services::deepl_service::DeepLConfig;
+use crate::services::extractor::{ExtractorConfig, ExtractorKind};
 use crate::services::file_naming_service::{FileNamingConfig, FileNamingMode};
+use crate::services::jina_service::JinaConfig;
+use crate::services::libretranslate_service::{
+    from_libre_lang, LibreTranslateConfig, LibreTranslateService,
+};
+use crate::services::mock_translator::{MockConfig, MockErrorKind, MockMode};
+use crate::services::ollama_service::{OllamaConfig, OllamaEndpoint};
+use crate::services::openai_service::OpenAIConfig;
+use crate::services::translation_memory::{
+    MemoryEntry, MemorySummary, TranslationMemory, TranslationMemoryConfig,
+};
 use crate::theme::use_theme_context;
-use crate::types::api_types::AppConfig;
+use crate::types::api_types::{AppConfig, TranslationEngine};
 use leptos::*;
 use wasm_bindgen::JsCast;
 
@@ -12,15 +26,65 @@ pub fn SettingsPage() -> impl IntoView {
     let theme_context = use_theme_context();
 
     // 本地状态用于表单编辑
+    let (translation_engine, set_translation_engine) = create_signal(String::new());
+    // 主引擎失败时依次尝试的备用引擎
+    let fallback_engines = create_rw_signal(Vec::<TranslationEngine>::new());
     let (deeplx_url, set_deeplx_url) = create_signal(String::new());
     let (jina_url, set_jina_url) = create_signal(String::new());
+    let (extractor_kind, set_extractor_kind) = create_signal(String::new());
+    let (jina_key, set_jina_key) = create_signal(String::new());
+    let (jina_return_format, set_jina_return_format) = create_signal(String::new());
+    let (jina_target_selector, set_jina_target_selector) = create_signal(String::new());
+    let (jina_remove_selector, set_jina_remove_selector) = create_signal(String::new());
+    let (jina_generated_alt, set_jina_generated_alt) = create_signal(false);
+    let (jina_no_cache, set_jina_no_cache) = create_signal(false);
+    let (jina_json_mode, set_jina_json_mode) = create_signal(true);
+    let (cors_proxy, set_cors_proxy) = create_signal(String::new());
     let (source_lang, set_source_lang) = create_signal(String::new());
     let (target_lang, set_target_lang) = create_signal(String::new());
     let (max_requests_per_second, set_max_requests_per_second) = create_signal(String::new());
     let (max_text_length, set_max_text_length) = create_signal(String::new());
     let (max_paragraphs, set_max_paragraphs) = create_signal(String::new());
+    let (chunk_concurrency, set_chunk_concurrency) = create_signal(String::new());
+    let (batch_concurrency, set_batch_concurrency) = create_signal(String::new());
+    let (memory_enabled, set_memory_enabled) = create_signal(true);
     let (save_message, set_save_message) = create_signal(String::new());
 
+    // OpenAI兼容接口配置状态
+    let (openai_url, set_openai_url) = create_signal(String::new());
+    let (openai_key, set_openai_key) = create_signal(String::new());
+    let (openai_model, set_openai_model) = create_signal(String::new());
+    let (openai_temperature, set_openai_temperature) = create_signal(String::new());
+    let (openai_prompt, set_openai_prompt) = create_signal(String::new());
+
+    // DeepL官方API配置状态
+    let (deepl_url, set_deepl_url) = create_signal(String::new());
+    let (deepl_key, set_deepl_key) = create_signal(String::new());
+    let (deepl_formality, set_deepl_formality) = create_signal(String::new());
+    let (deepl_tag_handling, set_deepl_tag_handling) = create_signal(String::new());
+    let (deepl_preserve_formatting, set_deepl_preserve_formatting) = create_signal(true);
+    let (deepl_glossary_id, set_deepl_glossary_id) = create_signal(String::new());
+
+    // LibreTranslate配置状态
+    let (libre_url, set_libre_url) = create_signal(String::new());
+    let (libre_key, set_libre_key) = create_signal(String::new());
+    let (libre_status, set_libre_status) = create_signal(String::new());
+    // 服务端 /languages 返回的语言列表，为空时使用内置列表
+    let libre_languages = create_rw_signal(Vec::<(String, String)>::new());
+
+    // Ollama本地模型配置状态
+    let (ollama_url, set_ollama_url) = create_signal(String::new());
+    let (ollama_model, set_ollama_model) = create_signal(String::new());
+    let (ollama_endpoint, set_ollama_endpoint) = create_signal(String::new());
+    let (ollama_temperature, set_ollama_temperature) = create_signal(String::new());
+    let (ollama_prompt, set_ollama_prompt) = create_signal(String::new());
+
+    // 模拟引擎配置状态
+    let (mock_mode, set_mock_mode) = create_signal(String::new());
+    let (mock_latency, set_mock_latency) = create_signal(String::new());
+    let (mock_fail_every, set_mock_fail_every) = create_signal(String::new());
+    let (mock_error_kind, set_mock_error_kind) = create_signal(String::new());
+
     // 文件命名配置状态
     let (naming_mode, set_naming_mode) = create_signal(String::new());
     let (max_filename_length, set_max_filename_length) = create_signal(String::new());
@@ -30,13 +94,81 @@ pub fn SettingsPage() -> impl IntoView {
     // 初始化表单值
     create_effect(move |_| {
         let config = config_hook.config.get();
+        set_translation_engine.set(engine_key(config.translation_engine).to_string());
+        fallback_engines.set(config.fallback_engines);
         set_deeplx_url.set(config.deeplx_api_url);
         set_jina_url.set(config.jina_api_url);
+        set_extractor_kind.set(
+            match config.extractor.kind {
+                ExtractorKind::Jina => "jina",
+                ExtractorKind::Readability => "readability",
+            }
+            .to_string(),
+        );
+        set_cors_proxy.set(config.extractor.cors_proxy);
+        set_jina_key.set(config.jina.api_key);
+        set_jina_return_format.set(config.jina.return_format);
+        set_jina_target_selector.set(config.jina.target_selector);
+        set_jina_remove_selector.set(config.jina.remove_selector);
+        set_jina_generated_alt.set(config.jina.with_generated_alt);
+        set_jina_no_cache.set(config.jina.no_cache);
+        set_jina_json_mode.set(config.jina.json_mode);
         set_source_lang.set(config.default_source_lang);
         set_target_lang.set(config.default_target_lang);
         set_max_requests_per_second.set(config.max_requests_per_second.to_string());
         set_max_text_length.set(config.max_text_length.to_string());
         set_max_paragraphs.set(config.max_paragraphs_per_request.to_string());
+        set_chunk_concurrency.set(config.chunk_concurrency.to_string());
+        set_batch_concurrency.set(config.batch.concurrency.to_string());
+        set_memory_enabled.set(config.translation_memory.enabled);
+
+        set_openai_url.set(config.openai.api_url);
+        set_openai_key.set(config.openai.api_key);
+        set_openai_model.set(config.openai.model);
+        set_openai_temperature.set(config.openai.temperature.to_string());
+        set_openai_prompt.set(config.openai.system_prompt);
+
+        set_deepl_url.set(config.deepl.api_url);
+        set_deepl_key.set(config.deepl.auth_key);
+        set_deepl_formality.set(config.deepl.formality);
+        set_deepl_tag_handling.set(config.deepl.tag_handling);
+        set_deepl_preserve_formatting.set(config.deepl.preserve_formatting);
+        set_deepl_glossary_id.set(config.deepl.glossary_id);
+
+        set_libre_url.set(config.libretranslate.api_url);
+        set_libre_key.set(config.libretranslate.api_key);
+
+        set_ollama_url.set(config.ollama.api_url);
+        set_ollama_model.set(config.ollama.model);
+        set_ollama_endpoint.set(
+            match config.ollama.endpoint {
+                OllamaEndpoint::Chat => "chat",
+                OllamaEndpoint::Generate => "generate",
+            }
+            .to_string(),
+        );
+        set_ollama_temperature.set(config.ollama.temperature.to_string());
+        set_ollama_prompt.set(config.ollama.system_prompt);
+
+        set_mock_mode.set(
+            match config.mock.mode {
+                MockMode::PseudoLocalize => "pseudo",
+                MockMode::Echo => "echo",
+                MockMode::Reverse => "reverse",
+            }
+            .to_string(),
+        );
+        set_mock_latency.set(config.mock.latency_ms.to_string());
+        set_mock_fail_every.set(config.mock.fail_every.to_string());
+        set_mock_error_kind.set(
+            match config.mock.error_kind {
+                MockErrorKind::Network => "network",
+                MockErrorKind::RateLimit => "rate_limit",
+                MockErrorKind::Api => "api",
+                MockErrorKind::Config => "config",
+            }
+            .to_string(),
+        );
 
         // 初始化文件命名配置
         let mode_str = match config.file_naming.mode {
@@ -53,10 +185,64 @@ pub fn SettingsPage() -> impl IntoView {
         set_word_separator.set(config.file_naming.word_separator);
     });
 
+    // 从LibreTranslate服务端加载语言列表
+    let load_libre_languages = move || {
+        let config = AppConfig {
+            libretranslate: LibreTranslateConfig {
+                api_url: libre_url.get_untracked(),
+                api_key: libre_key.get_untracked(),
+            },
+            ..config_hook.config.get_untracked()
+        };
+        set_libre_status.set("正在加载语言列表...".to_string());
+
+        spawn_local(async move {
+            let service = LibreTranslateService::new(&config);
+            match service.languages().await {
+                Ok(languages) => {
+                    set_libre_status.set(format!("已从服务端加载 {} 种语言", languages.len()));
+                    libre_languages.set(
+                        languages
+                            .into_iter()
+                            .map(|lang| (from_libre_lang(&lang.code), lang.name))
+                            .collect(),
+                    );
+                }
+                Err(e) => {
+                    web_sys::console::log_1(
+                        &format!("加载LibreTranslate语言列表失败: {}", e).into(),
+                    );
+                    set_libre_status.set(format!("加载语言列表失败，使用内置列表: {}", e));
+                    libre_languages.set(Vec::new());
+                }
+            }
+        });
+    };
+
+    // 切换到LibreTranslate时自动加载语言列表，切换到其他引擎时恢复内置列表
+    create_effect(move |_| {
+        if translation_engine.get() == "libretranslate" {
+            load_libre_languages();
+        } else {
+            libre_languages.set(Vec::new());
+            set_libre_status.set(String::new());
+        }
+    });
+
     let save_settings = move |_| {
         let max_requests_val = max_requests_per_second.get().parse::<u32>().unwrap_or(10);
         let max_text_val = max_text_length.get().parse::<usize>().unwrap_or(5000);
         let max_paragraphs_val = max_paragraphs.get().parse::<usize>().unwrap_or(10);
+        let chunk_concurrency_val = chunk_concurrency
+            .get()
+            .parse::<usize>()
+            .unwrap_or(3)
+            .clamp(1, 8);
+        let batch_concurrency_val = batch_concurrency
+            .get()
+            .parse::<usize>()
+            .unwrap_or(3)
+            .clamp(1, MAX_BATCH_CONCURRENCY);
 
         // 构建文件命名配置
         let naming_mode_val = match naming_mode.get().as_str() {
@@ -79,15 +265,109 @@ pub fn SettingsPage() -> impl IntoView {
             include_extension: true,
         };
 
+        let translation_engine_val = engine_from_key(&translation_engine.get());
+        let fallback_engines_val: Vec<TranslationEngine> = fallback_engines
+            .get()
+            .into_iter()
+            .filter(|engine| *engine != translation_engine_val)
+            .collect();
+
+        let openai_config = OpenAIConfig {
+            api_url: openai_url.get(),
+            api_key: openai_key.get(),
+            model: openai_model.get(),
+            system_prompt: openai_prompt.get(),
+            temperature: openai_temperature
+                .get()
+                .parse::<f32>()
+                .unwrap_or(0.2)
+                .clamp(0.0, 2.0),
+        };
+
+        let deepl_config = DeepLConfig {
+            api_url: deepl_url.get(),
+            auth_key: deepl_key.get(),
+            formality: deepl_formality.get(),
+            tag_handling: deepl_tag_handling.get(),
+            preserve_formatting: deepl_preserve_formatting.get(),
+            glossary_id: deepl_glossary_id.get(),
+        };
+
+        let libretranslate_config = LibreTranslateConfig {
+            api_url: libre_url.get(),
+            api_key: libre_key.get(),
+        };
+
+        let ollama_config = OllamaConfig {
+            api_url: ollama_url.get(),
+            model: ollama_model.get(),
+            endpoint: match ollama_endpoint.get().as_str() {
+                "generate" => OllamaEndpoint::Generate,
+                _ => OllamaEndpoint::Chat,
+            },
+            system_prompt: ollama_prompt.get(),
+            temperature: ollama_temperature
+                .get()
+                .parse::<f32>()
+                .unwrap_or(0.2)
+                .clamp(0.0, 2.0),
+        };
+
+        let mock_config = MockConfig {
+            mode: match mock_mode.get().as_str() {
+                "echo" => MockMode::Echo,
+                "reverse" => MockMode::Reverse,
+                _ => MockMode::PseudoLocalize,
+            },
+            latency_ms: mock_latency.get().parse::<u32>().unwrap_or(0).min(10_000),
+            fail_every: mock_fail_every.get().parse::<u32>().unwrap_or(0),
+            error_kind: match mock_error_kind.get().as_str() {
+                "rate_limit" => MockErrorKind::RateLimit,
+                "api" => MockErrorKind::Api,
+                "config" => MockErrorKind::Config,
+                _ => MockErrorKind::Network,
+            },
+        };
+
         let new_config = AppConfig {
+            translation_engine: translation_engine_val,
+            fallback_engines: fallback_engines_val,
             deeplx_api_url: deeplx_url.get(),
             jina_api_url: jina_url.get(),
+            jina: JinaConfig {
+                api_key: jina_key.get(),
+                return_format: jina_return_format.get(),
+                target_selector: jina_target_selector.get(),
+                remove_selector: jina_remove_selector.get(),
+                with_generated_alt: jina_generated_alt.get(),
+                no_cache: jina_no_cache.get(),
+                json_mode: jina_json_mode.get(),
+            },
+            extractor: ExtractorConfig {
+                kind: match extractor_kind.get().as_str() {
+                    "readability" => ExtractorKind::Readability,
+                    _ => ExtractorKind::Jina,
+                },
+                cors_proxy: cors_proxy.get(),
+            },
             default_source_lang: source_lang.get(),
             default_target_lang: target_lang.get(),
             max_requests_per_second: max_requests_val,
             max_text_length: max_text_val,
             max_paragraphs_per_request: max_paragraphs_val,
+            chunk_concurrency: chunk_concurrency_val,
             file_naming: file_naming_config,
+            openai: openai_config,
+            deepl: deepl_config,
+            libretranslate: libretranslate_config,
+            ollama: ollama_config,
+            mock: mock_config,
+            batch: BatchConfig {
+                concurrency: batch_concurrency_val,
+            },
+            translation_memory: TranslationMemoryConfig {
+                enabled: memory_enabled.get(),
+            },
         };
 
         (config_hook.save_config)(new_config);
@@ -120,6 +400,32 @@ pub fn SettingsPage() -> impl IntoView {
             <div class="rounded-lg shadow-lg p-6" style=move || theme_context.get().theme.card_style()>
                 <div class="space-y-6">
                     <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                        <div>
+                            <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                "翻译引擎"
+                            </label>
+                            <select
+                                class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                style=move || theme_context.get().theme.input_style()
+                                prop:value=translation_engine
+                                on:change=move |ev| {
+                                    set_translation_engine.set(event_target_value(&ev));
+                                }
+                            >
+                                <option value="deeplx">"DeepLX"</option>
+                                <option value="openai">"OpenAI 兼容接口"</option>
+                                <option value="deepl">"DeepL 官方API"</option>
+                                <option value="libretranslate">"LibreTranslate (自建)"</option>
+                                <option value="ollama">"Ollama 本地模型"</option>
+                                <option value="mock">"Mock 模拟引擎（离线）"</option>
+                            </select>
+                        </div>
+
+                        <FallbackEngineList
+                            primary=Signal::derive(move || engine_from_key(&translation_engine.get()))
+                            engines=fallback_engines
+                        />
+
                         <ConfigInput
                             label="DeepLX API URL"
                             placeholder="https://deepl3.fileaiwork.online/dptrans?token=..."
@@ -136,11 +442,39 @@ pub fn SettingsPage() -> impl IntoView {
                             input_type="url"
                         />
 
+                        <div>
+                            <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                "内容提取方式"
+                            </label>
+                            <select
+                                class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                style=move || theme_context.get().theme.input_style()
+                                prop:value=extractor_kind
+                                on:change=move |ev| {
+                                    set_extractor_kind.set(event_target_value(&ev));
+                                }
+                            >
+                                <option value="jina">"Jina Reader"</option>
+                                <option value="readability">"内置提取（直接获取HTML）"</option>
+                            </select>
+                        </div>
+
+                        <Show when=move || extractor_kind.get() == "readability">
+                            <ConfigInput
+                                label="CORS代理地址 ({url} 为目标地址)"
+                                placeholder="https://api.allorigins.win/raw?url={url}"
+                                value=cors_proxy
+                                set_value=set_cors_proxy
+                                input_type="url"
+                            />
+                        </Show>
+
                         <LanguageSelect
                             label="默认源语言"
                             value=source_lang
                             set_value=set_source_lang
                             include_auto=true
+                            languages=libre_languages
                         />
 
                         <LanguageSelect
@@ -148,9 +482,447 @@ pub fn SettingsPage() -> impl IntoView {
                             value=target_lang
                             set_value=set_target_lang
                             include_auto=false
+                            languages=libre_languages
                         />
                     </div>
 
+                    <Show when=move || extractor_kind.get() != "readability">
+                        <div class="border-t pt-6 themed-border-t">
+                            <h3 class="text-lg font-medium themed-text mb-4">
+                                "Jina Reader 高级选项"
+                            </h3>
+                            <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                                <ConfigInput
+                                    label="API Key"
+                                    placeholder="jina_..."
+                                    value=jina_key
+                                    set_value=set_jina_key
+                                    input_type="password"
+                                />
+
+                                <div>
+                                    <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                        "返回格式 (X-Return-Format)"
+                                    </label>
+                                    <select
+                                        class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                        style=move || theme_context.get().theme.input_style()
+                                        prop:value=jina_return_format
+                                        on:change=move |ev| {
+                                            set_jina_return_format.set(event_target_value(&ev));
+                                        }
+                                    >
+                                        <option value="">"默认"</option>
+                                        <option value="markdown">"markdown"</option>
+                                        <option value="html">"html"</option>
+                                        <option value="text">"text"</option>
+                                    </select>
+                                </div>
+
+                                <ConfigInput
+                                    label="目标选择器 (X-Target-Selector)"
+                                    placeholder="article, .markdown-body"
+                                    value=jina_target_selector
+                                    set_value=set_jina_target_selector
+                                    input_type="text"
+                                />
+
+                                <ConfigInput
+                                    label="移除选择器 (X-Remove-Selector)"
+                                    placeholder="nav, footer, .ads"
+                                    value=jina_remove_selector
+                                    set_value=set_jina_remove_selector
+                                    input_type="text"
+                                />
+
+                                <div class="flex items-center">
+                                    <input
+                                        type="checkbox"
+                                        id="jina_json_mode"
+                                        class="rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                        prop:checked=jina_json_mode
+                                        on:change=move |ev| {
+                                            set_jina_json_mode.set(event_target_checked(&ev));
+                                        }
+                                    />
+                                    <label
+                                        for="jina_json_mode"
+                                        class="ml-2 text-sm font-medium"
+                                        style=move || theme_context.get().theme.text_style()
+                                    >
+                                        "JSON模式（获取标题和链接列表）"
+                                    </label>
+                                </div>
+
+                                <div class="flex items-center">
+                                    <input
+                                        type="checkbox"
+                                        id="jina_generated_alt"
+                                        class="rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                        prop:checked=jina_generated_alt
+                                        on:change=move |ev| {
+                                            set_jina_generated_alt.set(event_target_checked(&ev));
+                                        }
+                                    />
+                                    <label
+                                        for="jina_generated_alt"
+                                        class="ml-2 text-sm font-medium"
+                                        style=move || theme_context.get().theme.text_style()
+                                    >
+                                        "为图片生成说明 (X-With-Generated-Alt)"
+                                    </label>
+                                </div>
+
+                                <div class="flex items-center">
+                                    <input
+                                        type="checkbox"
+                                        id="jina_no_cache"
+                                        class="rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                        prop:checked=jina_no_cache
+                                        on:change=move |ev| {
+                                            set_jina_no_cache.set(event_target_checked(&ev));
+                                        }
+                                    />
+                                    <label
+                                        for="jina_no_cache"
+                                        class="ml-2 text-sm font-medium"
+                                        style=move || theme_context.get().theme.text_style()
+                                    >
+                                        "跳过缓存 (X-No-Cache)"
+                                    </label>
+                                </div>
+                            </div>
+                        </div>
+                    </Show>
+
+                    <Show when=move || translation_engine.get() == "openai">
+                        <div class="border-t pt-6 themed-border-t">
+                            <h3 class="text-lg font-medium themed-text mb-4">
+                                "OpenAI 兼容接口设置"
+                            </h3>
+                            <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                                <ConfigInput
+                                    label="Chat Completions URL"
+                                    placeholder="https://api.openai.com/v1/chat/completions"
+                                    value=openai_url
+                                    set_value=set_openai_url
+                                    input_type="url"
+                                />
+
+                                <ConfigInput
+                                    label="API Key"
+                                    placeholder="sk-..."
+                                    value=openai_key
+                                    set_value=set_openai_key
+                                    input_type="password"
+                                />
+
+                                <ConfigInput
+                                    label="模型名称"
+                                    placeholder="gpt-4o-mini"
+                                    value=openai_model
+                                    set_value=set_openai_model
+                                    input_type="text"
+                                />
+
+                                <ConfigInput
+                                    label="Temperature"
+                                    placeholder="0.2"
+                                    value=openai_temperature
+                                    set_value=set_openai_temperature
+                                    input_type="number"
+                                    min="0"
+                                    max="2"
+                                />
+                            </div>
+
+                            <div class="mt-4">
+                                <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                    "系统提示词"
+                                </label>
+                                <textarea
+                                    class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent font-mono text-sm"
+                                    style=move || theme_context.get().theme.input_style()
+                                    rows="5"
+                                    prop:value=openai_prompt
+                                    on:input=move |ev| {
+                                        set_openai_prompt.set(event_target_value(&ev));
+                                    }
+                                ></textarea>
+                                <p class="text-xs mt-1" style=move || theme_context.get().theme.subtext_style()>
+                                    "支持 {source_lang} 和 {target_lang} 占位符，请保留对 __CODE_BLOCK_xxx__ 占位符原样输出的要求"
+                                </p>
+                            </div>
+                        </div>
+                    </Show>
+
+                    <Show when=move || translation_engine.get() == "deepl">
+                        <div class="border-t pt-6 themed-border-t">
+                            <h3 class="text-lg font-medium themed-text mb-4">
+                                "DeepL 官方API设置"
+                            </h3>
+                            <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                                <ConfigInput
+                                    label="DeepL API URL"
+                                    placeholder="https://api-free.deepl.com/v2/translate"
+                                    value=deepl_url
+                                    set_value=set_deepl_url
+                                    input_type="url"
+                                />
+
+                                <ConfigInput
+                                    label="Auth Key"
+                                    placeholder="xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx:fx"
+                                    value=deepl_key
+                                    set_value=set_deepl_key
+                                    input_type="password"
+                                />
+
+                                <div>
+                                    <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                        "语气 (formality)"
+                                    </label>
+                                    <select
+                                        class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                        style=move || theme_context.get().theme.input_style()
+                                        prop:value=deepl_formality
+                                        on:change=move |ev| {
+                                            set_deepl_formality.set(event_target_value(&ev));
+                                        }
+                                    >
+                                        <option value="default">"默认"</option>
+                                        <option value="more">"更正式"</option>
+                                        <option value="less">"更随意"</option>
+                                        <option value="prefer_more">"尽量正式"</option>
+                                        <option value="prefer_less">"尽量随意"</option>
+                                    </select>
+                                </div>
+
+                                <div>
+                                    <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                        "标签处理 (tag_handling)"
+                                    </label>
+                                    <select
+                                        class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                        style=move || theme_context.get().theme.input_style()
+                                        prop:value=deepl_tag_handling
+                                        on:change=move |ev| {
+                                            set_deepl_tag_handling.set(event_target_value(&ev));
+                                        }
+                                    >
+                                        <option value="">"不处理"</option>
+                                        <option value="markdown">"Markdown"</option>
+                                        <option value="html">"HTML"</option>
+                                    </select>
+                                </div>
+
+                                <ConfigInput
+                                    label="术语表ID (glossary_id)"
+                                    placeholder="需要同时指定源语言"
+                                    value=deepl_glossary_id
+                                    set_value=set_deepl_glossary_id
+                                    input_type="text"
+                                />
+
+                                <div class="flex items-center">
+                                    <input
+                                        type="checkbox"
+                                        id="deepl_preserve_formatting"
+                                        class="rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                        prop:checked=deepl_preserve_formatting
+                                        on:change=move |ev| {
+                                            set_deepl_preserve_formatting.set(event_target_checked(&ev));
+                                        }
+                                    />
+                                    <label
+                                        for="deepl_preserve_formatting"
+                                        class="ml-2 text-sm font-medium"
+                                        style=move || theme_context.get().theme.text_style()
+                                    >
+                                        "保留原始格式 (preserve_formatting)"
+                                    </label>
+                                </div>
+                            </div>
+                        </div>
+                    </Show>
+
+                    <Show when=move || translation_engine.get() == "libretranslate">
+                        <div class="border-t pt-6 themed-border-t">
+                            <h3 class="text-lg font-medium themed-text mb-4">
+                                "LibreTranslate 设置"
+                            </h3>
+                            <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                                <ConfigInput
+                                    label="服务地址"
+                                    placeholder="http://localhost:5000"
+                                    value=libre_url
+                                    set_value=set_libre_url
+                                    input_type="url"
+                                />
+
+                                <ConfigInput
+                                    label="API Key（可选）"
+                                    placeholder="未启用API Key时留空"
+                                    value=libre_key
+                                    set_value=set_libre_key
+                                    input_type="password"
+                                />
+                            </div>
+
+                            <div class="flex items-center space-x-3 mt-4">
+                                <button
+                                    class="px-4 py-2 rounded-md text-sm transition-colors"
+                                    style=move || theme_context.get().theme.button_secondary_style()
+                                    on:click=move |_| load_libre_languages()
+                                >
+                                    "刷新语言列表"
+                                </button>
+                                <span class="text-xs" style=move || theme_context.get().theme.subtext_style()>
+                                    {libre_status}
+                                </span>
+                            </div>
+                        </div>
+                    </Show>
+
+                    <Show when=move || translation_engine.get() == "ollama">
+                        <div class="border-t pt-6 themed-border-t">
+                            <h3 class="text-lg font-medium themed-text mb-4">
+                                "Ollama 本地模型设置"
+                            </h3>
+                            <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                                <ConfigInput
+                                    label="服务地址"
+                                    placeholder="http://localhost:11434"
+                                    value=ollama_url
+                                    set_value=set_ollama_url
+                                    input_type="url"
+                                />
+
+                                <ConfigInput
+                                    label="模型名称"
+                                    placeholder="qwen2.5:7b"
+                                    value=ollama_model
+                                    set_value=set_ollama_model
+                                    input_type="text"
+                                />
+
+                                <div>
+                                    <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                        "接口"
+                                    </label>
+                                    <select
+                                        class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                        style=move || theme_context.get().theme.input_style()
+                                        prop:value=ollama_endpoint
+                                        on:change=move |ev| {
+                                            set_ollama_endpoint.set(event_target_value(&ev));
+                                        }
+                                    >
+                                        <option value="chat">"/api/chat"</option>
+                                        <option value="generate">"/api/generate"</option>
+                                    </select>
+                                </div>
+
+                                <ConfigInput
+                                    label="Temperature"
+                                    placeholder="0.2"
+                                    value=ollama_temperature
+                                    set_value=set_ollama_temperature
+                                    input_type="number"
+                                    min="0"
+                                    max="2"
+                                />
+                            </div>
+
+                            <div class="mt-4">
+                                <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                    "系统提示词"
+                                </label>
+                                <textarea
+                                    class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent font-mono text-sm"
+                                    style=move || theme_context.get().theme.input_style()
+                                    rows="5"
+                                    prop:value=ollama_prompt
+                                    on:input=move |ev| {
+                                        set_ollama_prompt.set(event_target_value(&ev));
+                                    }
+                                ></textarea>
+                                <p class="text-xs mt-1" style=move || theme_context.get().theme.subtext_style()>
+                                    "译文会边生成边显示。浏览器访问本地Ollama时需设置 OLLAMA_ORIGINS 允许当前站点"
+                                </p>
+                            </div>
+                        </div>
+                    </Show>
+
+                    <Show when=move || translation_engine.get() == "mock">
+                        <div class="border-t pt-6 themed-border-t">
+                            <h3 class="text-lg font-medium themed-text mb-4">
+                                "Mock 模拟引擎设置"
+                            </h3>
+                            <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                                <div>
+                                    <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                        "输出方式"
+                                    </label>
+                                    <select
+                                        class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                        style=move || theme_context.get().theme.input_style()
+                                        prop:value=mock_mode
+                                        on:change=move |ev| {
+                                            set_mock_mode.set(event_target_value(&ev));
+                                        }
+                                    >
+                                        <option value="pseudo">"伪本地化"</option>
+                                        <option value="echo">"原样返回"</option>
+                                        <option value="reverse">"反转文本"</option>
+                                    </select>
+                                </div>
+
+                                <ConfigInput
+                                    label="模拟延迟 (毫秒/块)"
+                                    placeholder="0"
+                                    value=mock_latency
+                                    set_value=set_mock_latency
+                                    input_type="number"
+                                    min="0"
+                                    max="10000"
+                                />
+
+                                <ConfigInput
+                                    label="每N次请求失败一次 (0为不失败)"
+                                    placeholder="0"
+                                    value=mock_fail_every
+                                    set_value=set_mock_fail_every
+                                    input_type="number"
+                                    min="0"
+                                    max="100"
+                                />
+
+                                <div>
+                                    <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                                        "注入的错误类型"
+                                    </label>
+                                    <select
+                                        class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                                        style=move || theme_context.get().theme.input_style()
+                                        prop:value=mock_error_kind
+                                        on:change=move |ev| {
+                                            set_mock_error_kind.set(event_target_value(&ev));
+                                        }
+                                    >
+                                        <option value="network">"网络错误（可重试）"</option>
+                                        <option value="rate_limit">"限流错误（可重试）"</option>
+                                        <option value="api">"API错误（可重试）"</option>
+                                        <option value="config">"配置错误（不可重试）"</option>
+                                    </select>
+                                </div>
+                            </div>
+                            <p class="text-xs mt-2" style=move || theme_context.get().theme.subtext_style()>
+                                "模拟引擎不发起翻译请求，网页内容也由模拟提取器生成，结果确定可复现，适合离线开发和测试翻译流程"
+                            </p>
+                        </div>
+                    </Show>
+
                     <div class="border-t pt-6 themed-border-t">
                         <h3 class="text-lg font-medium themed-text mb-4">
                             "速率限制设置"
@@ -185,9 +957,56 @@ pub fn SettingsPage() -> impl IntoView {
                                 min="5"
                                 max="50"
                             />
+
+                            <ConfigInput
+                                label="长文档分块并发数"
+                                placeholder="3"
+                                value=chunk_concurrency
+                                set_value=set_chunk_concurrency
+                                input_type="number"
+                                min="1"
+                                max="8"
+                            />
+
+                            <ConfigInput
+                                label="批量翻译并发文档数"
+                                placeholder="3"
+                                value=batch_concurrency
+                                set_value=set_batch_concurrency
+                                input_type="number"
+                                min="1"
+                                max="8"
+                            />
                         </div>
                     </div>
 
+                    <div class="border-t pt-6 themed-border-t">
+                        <h3 class="text-lg font-medium themed-text mb-4">
+                            "翻译记忆"
+                        </h3>
+                        <div class="flex items-center mb-4">
+                            <input
+                                type="checkbox"
+                                id="memory_enabled"
+                                class="rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                prop:checked=memory_enabled
+                                on:change=move |ev| {
+                                    let target = ev.target().unwrap();
+                                    let input = target.dyn_into::<web_sys::HtmlInputElement>().unwrap();
+                                    set_memory_enabled.set(input.checked());
+                                }
+                            />
+                            <label
+                                for="memory_enabled"
+                                class="ml-2 text-sm font-medium"
+                                style=move || theme_context.get().theme.text_style()
+                            >
+                                "启用翻译记忆（DeepLX）"
+                            </label>
+                        </div>
+                        <TranslationMemoryPanel />
+                    </div>
+
                     <div class="border-t pt-6" style=move || format!("border-color: {};", theme_context.get().theme.surface2)>
                         <h3 class="text-lg font-medium themed-text mb-4">
                             "文件命名设置"
@@ -340,7 +1159,7 @@ pub fn SettingsPage() -> impl IntoView {
                         </h3>
                         <div class="text-sm space-y-2 themed-subtext">
                             <p>"• 输入要翻译的网页URL，系统会自动提取内容并翻译"</p>
-                            <p>"• 使用Jina AI Reader服务提取网页内容，保持原始格式"</p>
+                            <p>"• 默认使用Jina AI Reader服务提取网页内容，也可切换为内置提取器直接解析HTML正文"</p>
                             <p>"• 使用DeepLX API进行翻译，支持多种语言"</p>
                             <p>"• 翻译完成后可以下载Markdown格式的文件"</p>
                         </div>
@@ -384,15 +1203,325 @@ fn ConfigInput(
     }
 }
 
+/// 备用引擎列表，按顺序回退，可调整顺序
+#[component]
+fn FallbackEngineList(
+    primary: Signal<TranslationEngine>,
+    engines: RwSignal<Vec<TranslationEngine>>,
+) -> impl IntoView {
+    let theme_context = use_theme_context();
+
+    let move_engine = move |index: usize, offset: isize| {
+        engines.update(|list| {
+            let target = index as isize + offset;
+            if target >= 0 && (target as usize) < list.len() {
+                list.swap(index, target as usize);
+            }
+        });
+    };
+
+    view! {
+        <div>
+            <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                "备用引擎（按顺序回退）"
+            </label>
+            <div class="space-y-2">
+                {move || {
+                    let list = engines.get();
+                    let len = list.len();
+                    list.into_iter()
+                        .enumerate()
+                        .filter(|(_, engine)| *engine != primary.get())
+                        .map(|(index, engine)| {
+                            view! {
+                                <div class="flex items-center justify-between text-sm px-3 py-1 rounded themed-bg-surface1">
+                                    <span style=move || theme_context.get().theme.text_style()>
+                                        {format!("{}. {}", index + 1, engine.display_name())}
+                                    </span>
+                                    <div class="flex space-x-1">
+                                        <button
+                                            class="px-2 rounded themed-button-secondary"
+                                            disabled=index == 0
+                                            on:click=move |_| move_engine(index, -1)
+                                            title="上移"
+                                        >
+                                            "↑"
+                                        </button>
+                                        <button
+                                            class="px-2 rounded themed-button-secondary"
+                                            disabled=index + 1 == len
+                                            on:click=move |_| move_engine(index, 1)
+                                            title="下移"
+                                        >
+                                            "↓"
+                                        </button>
+                                        <button
+                                            class="px-2 rounded themed-button-danger"
+                                            on:click=move |_| engines.update(|list| {
+                                                list.remove(index);
+                                            })
+                                            title="移除"
+                                        >
+                                            "×"
+                                        </button>
+                                    </div>
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }}
+
+                <select
+                    class="w-full px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                    style=move || theme_context.get().theme.input_style()
+                    prop:value=""
+                    on:change=move |ev| {
+                        let key = event_target_value(&ev);
+                        if !key.is_empty() {
+                            let engine = engine_from_key(&key);
+                            engines.update(|list| {
+                                if !list.contains(&engine) {
+                                    list.push(engine);
+                                }
+                            });
+                        }
+                    }
+                >
+                    <option value="">"添加备用引擎..."</option>
+                    {move || {
+                        let list = engines.get();
+                        TranslationEngine::ALL
+                            .into_iter()
+                            .filter(|engine| *engine != primary.get() && !list.contains(engine))
+                            .map(|engine| {
+                                view! {
+                                    <option value=engine_key(engine)>{engine.display_name()}</option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+            </div>
+            <p class="text-xs mt-1" style=move || theme_context.get().theme.subtext_style()>
+                "某个文本块在当前引擎出现可重试的错误时，改用下一个引擎翻译该块"
+            </p>
+        </div>
+    }
+}
+
+/// 翻译记忆库的统计、最近使用的条目以及清理操作，操作立即生效，无需保存设置
+#[component]
+fn TranslationMemoryPanel() -> impl IntoView {
+    let theme_context = use_theme_context();
+    let entries = create_rw_signal(Vec::<MemoryEntry>::new());
+    let (status, set_status) = create_signal(String::new());
+    let (prune_days, set_prune_days) = create_signal("30".to_string());
+
+    let reload = move || {
+        spawn_local(async move {
+            match load_memory_entries().await {
+                Ok(list) => entries.set(list),
+                Err(e) => set_status.set(format!("读取翻译记忆失败: {}", e)),
+            }
+        });
+    };
+    reload();
+
+    let prune = move |_| {
+        let days = prune_days
+            .get_untracked()
+            .parse::<i64>()
+            .unwrap_or(30)
+            .max(0);
+        spawn_local(async move {
+            let result = match TranslationMemory::open().await {
+                Ok(memory) => memory.prune_unused(days).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(removed) => {
+                    set_status.set(format!("已删除 {} 条超过 {} 天未使用的记录", removed, days))
+                }
+                Err(e) => set_status.set(format!("清理翻译记忆失败: {}", e)),
+            }
+            reload();
+        });
+    };
+
+    let clear = move |_| {
+        if !web_sys::window()
+            .and_then(|w| w.confirm_with_message("确定要清空翻译记忆吗？").ok())
+            .unwrap_or(false)
+        {
+            return;
+        }
+        spawn_local(async move {
+            let result = match TranslationMemory::open().await {
+                Ok(memory) => memory.clear().await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => set_status.set("翻译记忆已清空".to_string()),
+                Err(e) => set_status.set(format!("清空翻译记忆失败: {}", e)),
+            }
+            reload();
+        });
+    };
+
+    view! {
+        <div class="space-y-4">
+            <div class="text-sm" style=move || theme_context.get().theme.text_style()>
+                {move || {
+                    let summary = MemorySummary::from_entries(&entries.get());
+                    format!("共 {} 条记录，累计命中 {} 次", summary.entries, summary.total_hits)
+                }}
+            </div>
+            <div class="text-xs space-y-1" style=move || theme_context.get().theme.subtext_style()>
+                {move || {
+                    MemorySummary::from_entries(&entries.get())
+                        .by_pair
+                        .into_iter()
+                        .map(|(pair, count)| view! { <p>{format!("{}: {} 条", pair, count)}</p> })
+                        .collect_view()
+                }}
+            </div>
+
+            <Show when=move || !entries.get().is_empty()>
+                <div class="max-h-60 overflow-y-auto rounded-md p-3 space-y-2" style=move || theme_context.get().theme.content_bg_style()>
+                    <h4 class="text-sm font-medium" style=move || theme_context.get().theme.text_style()>
+                        "最近使用的记录"
+                    </h4>
+                    {move || {
+                        entries
+                            .get()
+                            .into_iter()
+                            .take(20)
+                            .map(|entry| {
+                                view! {
+                                    <div class="text-xs border-b pb-2 last:border-b-0 themed-border-t">
+                                        <p class="truncate" style=move || theme_context.get().theme.text_style()>
+                                            {entry.source.clone()}
+                                        </p>
+                                        <p class="truncate" style=move || theme_context.get().theme.subtext_style()>
+                                            {format!("→ {}", entry.translation)}
+                                        </p>
+                                        <p style=move || theme_context.get().theme.subtext_style()>
+                                            {format!(
+                                                "{} {} -> {} · 命中 {} 次",
+                                                entry.engine.display_name(),
+                                                entry.source_lang,
+                                                entry.target_lang,
+                                                entry.hits
+                                            )}
+                                        </p>
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </Show>
+
+            <div class="flex flex-wrap items-end gap-3">
+                <div>
+                    <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
+                        "清理多少天未使用的记录"
+                    </label>
+                    <input
+                        type="number"
+                        class="w-32 px-4 py-2 rounded-md focus:ring-2 focus:border-transparent"
+                        style=move || theme_context.get().theme.input_style()
+                        prop:value=prune_days
+                        prop:min="0"
+                        on:input=move |ev| set_prune_days.set(event_target_value(&ev))
+                    />
+                </div>
+                <button
+                    type="button"
+                    class="px-4 py-2 rounded-md transition-colors"
+                    style=move || theme_context.get().theme.button_secondary_style()
+                    on:click=prune
+                >
+                    "清理"
+                </button>
+                <button
+                    type="button"
+                    class="px-4 py-2 rounded-md transition-colors"
+                    style=move || theme_context.get().theme.button_danger_style()
+                    on:click=clear
+                >
+                    "清空翻译记忆"
+                </button>
+            </div>
+
+            <Show when=move || !status.get().is_empty()>
+                <p class="text-sm" style=move || theme_context.get().theme.subtext_style()>
+                    {status}
+                </p>
+            </Show>
+        </div>
+    }
+}
+
+async fn load_memory_entries() -> AppResult<Vec<MemoryEntry>> {
+    TranslationMemory::open().await?.entries().await
+}
+
+/// 翻译引擎在表单中使用的标识
+fn engine_key(engine: TranslationEngine) -> &'static str {
+    match engine {
+        TranslationEngine::DeepLX => "deeplx",
+        TranslationEngine::OpenAI => "openai",
+        TranslationEngine::DeepL => "deepl",
+        TranslationEngine::LibreTranslate => "libretranslate",
+        TranslationEngine::Ollama => "ollama",
+        TranslationEngine::Mock => "mock",
+    }
+}
+
+fn engine_from_key(key: &str) -> TranslationEngine {
+    TranslationEngine::ALL
+        .into_iter()
+        .find(|engine| engine_key(*engine) == key)
+        .unwrap_or_default()
+}
+
 #[component]
 fn LanguageSelect(
     label: &'static str,
     value: ReadSignal<String>,
     set_value: WriteSignal<String>,
     include_auto: bool,
+    /// 可选语言（代码, 名称），为空时使用内置列表
+    #[prop(into)]
+    languages: Signal<Vec<(String, String)>>,
 ) -> impl IntoView {
     let theme_context = use_theme_context();
 
+    let options = move || {
+        let languages = languages.get();
+        let languages = if languages.is_empty() {
+            default_language_options()
+        } else {
+            languages
+        };
+
+        languages
+            .into_iter()
+            .map(|(code, name)| {
+                let selected_code = code.clone();
+                view! {
+                    <option
+                        value=code
+                        selected=move || value.get() == selected_code
+                    >
+                        {name}
+                    </option>
+                }
+            })
+            .collect_view()
+    };
+
     view! {
         <div>
             <label class="block text-sm font-medium mb-2" style=move || theme_context.get().theme.text_style()>
@@ -411,13 +1540,23 @@ fn LanguageSelect(
                 } else {
                     view! {}.into_view()
                 }}
-                <option value="ZH">"中文"</option>
-                <option value="EN">"英语"</option>
-                <option value="JA">"日语"</option>
-                <option value="FR">"法语"</option>
-                <option value="DE">"德语"</option>
-                <option value="ES">"西班牙语"</option>
+                {options}
             </select>
         </div>
     }
 }
+
+/// 内置的语言列表
+fn default_language_options() -> Vec<(String, String)> {
+    [
+        ("ZH", "中文"),
+        ("EN", "英语"),
+        ("JA", "日语"),
+        ("FR", "法语"),
+        ("DE", "德语"),
+        ("ES", "西班牙语"),
+    ]
+    .into_iter()
+    .map(|(code, name)| (code.to_string(), name.to_string()))
+    .collect()
+}
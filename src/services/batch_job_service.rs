@@ -0,0 +1,125 @@
+use super::indexed_db::{
+    await_request, await_transaction, storage_error, LocalDatabase, BATCH_JOBS_STORE,
+};
+use super::platform::log;
+use crate::error::{AppError, AppResult};
+use crate::types::batch_job::BatchJob;
+use gloo_storage::{LocalStorage, Storage};
+use std::future::Future;
+use wasm_bindgen::JsValue;
+use web_sys::{IdbTransaction, IdbTransactionMode};
+
+/// 旧版本保存在 localStorage 中的任务，打开时迁移到 IndexedDB
+const LEGACY_STORAGE_KEY: &str = "batch_jobs";
+/// 任务中保存了译文全文，只保留最近的几个任务
+const MAX_SAVED_JOBS: usize = 5;
+
+/// 批量翻译任务的断点存储。任务包含所有文档的译文，
+/// 保存在 IndexedDB 中，避免大型任务超出 localStorage 的容量
+#[derive(Clone)]
+pub struct BatchJobService {
+    db: LocalDatabase,
+}
+
+impl BatchJobService {
+    pub async fn open() -> AppResult<Self> {
+        let service = Self {
+            db: LocalDatabase::open().await?,
+        };
+        service.migrate_legacy_jobs().await;
+        Ok(service)
+    }
+
+    /// 所有保存的任务，最近更新的在前
+    pub async fn get_all_jobs(&self) -> AppResult<Vec<BatchJob>> {
+        let (store, _) = self
+            .db
+            .object_store(BATCH_JOBS_STORE, IdbTransactionMode::Readonly)?;
+        let request = store.get_all().map_err(storage_error)?;
+        let values = js_sys::Array::from(&await_request(&request).await?);
+        let mut jobs: Vec<BatchJob> = values.iter().filter_map(|v| parse_job(&v)).collect();
+        jobs.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(jobs)
+    }
+
+    /// 还有文档未处理或失败、可以继续的任务
+    pub async fn resumable_jobs(&self) -> AppResult<Vec<BatchJob>> {
+        Ok(self
+            .get_all_jobs()
+            .await?
+            .into_iter()
+            .filter(|job| !job.is_finished() || job.failed_count() > 0)
+            .collect())
+    }
+
+    pub async fn get_job(&self, id: &str) -> AppResult<Option<BatchJob>> {
+        let (store, _) = self
+            .db
+            .object_store(BATCH_JOBS_STORE, IdbTransactionMode::Readonly)?;
+        let request = store.get(&JsValue::from_str(id)).map_err(storage_error)?;
+        Ok(parse_job(&await_request(&request).await?))
+    }
+
+    /// 保存任务断点，已存在的任务会被替换。
+    /// 调用时立即创建写入事务，事务按创建顺序提交，连续保存时先保存的断点不会覆盖后保存的
+    pub fn save_job(&self, job: &BatchJob) -> impl Future<Output = AppResult<()>> {
+        let transaction = self.put_job(job);
+        async move { await_transaction(&transaction?).await }
+    }
+
+    fn put_job(&self, job: &BatchJob) -> AppResult<IdbTransaction> {
+        let value = serde_json::to_string(job)
+            .map_err(|e| AppError::parse(format!("序列化批量任务失败: {}", e)))?;
+        let (store, transaction) = self
+            .db
+            .object_store(BATCH_JOBS_STORE, IdbTransactionMode::Readwrite)?;
+        store
+            .put_with_key(&JsValue::from_str(&value), &JsValue::from_str(&job.id))
+            .map_err(storage_error)?;
+        Ok(transaction)
+    }
+
+    /// 只保留最近更新的几个任务
+    pub async fn prune_jobs(&self) -> AppResult<()> {
+        let jobs = self.get_all_jobs().await?;
+        if jobs.len() <= MAX_SAVED_JOBS {
+            return Ok(());
+        }
+        let (store, transaction) = self
+            .db
+            .object_store(BATCH_JOBS_STORE, IdbTransactionMode::Readwrite)?;
+        for job in &jobs[MAX_SAVED_JOBS..] {
+            store
+                .delete(&JsValue::from_str(&job.id))
+                .map_err(storage_error)?;
+        }
+        await_transaction(&transaction).await
+    }
+
+    pub async fn delete_job(&self, id: &str) -> AppResult<()> {
+        let (store, transaction) = self
+            .db
+            .object_store(BATCH_JOBS_STORE, IdbTransactionMode::Readwrite)?;
+        store
+            .delete(&JsValue::from_str(id))
+            .map_err(storage_error)?;
+        await_transaction(&transaction).await
+    }
+
+    async fn migrate_legacy_jobs(&self) {
+        let Ok(jobs) = LocalStorage::get::<Vec<BatchJob>>(LEGACY_STORAGE_KEY) else {
+            return;
+        };
+        for job in &jobs {
+            if let Err(e) = self.save_job(job).await {
+                log(&format!("迁移批量任务失败: {}", e));
+                return;
+            }
+        }
+        LocalStorage::delete(LEGACY_STORAGE_KEY);
+    }
+}
+
+fn parse_job(value: &JsValue) -> Option<BatchJob> {
+    serde_json::from_str(&value.as_string()?).ok()
+}
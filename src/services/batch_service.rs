@@ -1,18 +1,102 @@
+use super::platform::{log, sleep_ms};
+use crate::error::{AppError, AppResult};
 use crate::services::{
     content_processor::ContentProcessor,
-    deeplx_service::DeepLXService,
+    crawler::{CrawlOptions, Crawler},
+    extractor::{create_extractor, Extractor},
     file_naming_service::{FileNamingContext, FileNamingService},
-    jina_service::JinaService,
+    incremental::{
+        content_hash, translate_changed_paragraphs, Changelog, DocumentChange, ParagraphDiff,
+        PreviousTranslations,
+    },
+    index_parser::{parse_sphinx_toctree, raw_source_url, IndexFormat},
+    readability_extractor::ReadabilityExtractor,
+    sitemap_service::{SitemapFilter, SitemapService},
+    translator::{
+        create_translator, format_engine_usage, merge_engine_usage, EngineUsage,
+        TranslationOutcome, Translator,
+    },
 };
 use crate::types::api_types::AppConfig;
 use chrono::Utc;
 use flate2::write::GzEncoder;
 use flate2::Compression;
-use gloo_timers::future::TimeoutFuture;
-use std::collections::HashMap;
+use futures::future::{poll_fn, FutureExt};
+use futures::stream::{self, StreamExt};
+use futures::{pin_mut, select};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::rc::Rc;
+use std::task::{Poll, Waker};
 use tar::Builder;
 
-#[derive(Debug, Clone)]
+/// 展开 Sphinx 子文档 toctree 时最多获取的文件数
+const MAX_TOCTREE_FETCHES: usize = 200;
+
+/// 批量翻译同时处理的文档数上限
+pub const MAX_BATCH_CONCURRENCY: usize = 8;
+
+/// 批量翻译配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BatchConfig {
+    /// 同时提取和翻译的文档数
+    pub concurrency: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self { concurrency: 3 }
+    }
+}
+
+impl BatchConfig {
+    /// 实际使用的工作者数量，限制在 1 到 [`MAX_BATCH_CONCURRENCY`] 之间
+    pub fn workers(&self) -> usize {
+        self.concurrency.clamp(1, MAX_BATCH_CONCURRENCY)
+    }
+}
+
+/// 生成翻译失败文档的Markdown列表，没有失败时返回空字符串
+pub fn failure_report(failures: &[BatchFailure]) -> String {
+    if failures.is_empty() {
+        return String::new();
+    }
+
+    let mut content = format!("## 翻译失败的文档 ({} 个)\n\n", failures.len());
+    for failure in failures {
+        content.push_str(&format!(
+            "- {}. {}\n  - 原始URL: {}\n  - 错误: {}\n",
+            failure.link.order + 1,
+            failure.link.title,
+            failure.link.url,
+            failure.error
+        ));
+    }
+    content.push('\n');
+    content
+}
+
+/// 以最多 `workers` 个任务同时运行的方式处理所有条目，结果按完成顺序返回
+pub async fn run_bounded<I, T, F, Fut>(items: I, workers: usize, task: F) -> Vec<T>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = T>,
+{
+    stream::iter(items)
+        .map(task)
+        .buffer_unordered(workers.max(1))
+        .collect()
+        .await
+}
+
+/// 目录缩进级别的上限，各种文档发现方式得到的级别都不超过它
+pub const MAX_LEVEL: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocumentLink {
     pub title: String,
     pub url: String,
@@ -20,14 +104,37 @@ pub struct DocumentLink {
     pub order: usize, // 在目录中的顺序
 }
 
-#[derive(Debug, Clone)]
+/// 批量翻译的文档发现方式
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiscoveryMode {
+    /// 解析首页中的Markdown链接目录
+    IndexPage,
+    /// 读取 sitemap.xml，遵守 robots.txt
+    Sitemap(SitemapFilter),
+    /// 从起始页面递归跟随同站链接
+    Crawl(CrawlOptions),
+}
+
+/// 一次批量翻译请求
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRequest {
+    pub url: String,
+    pub discovery: DiscoveryMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TranslatedDocument {
     pub link: DocumentLink,
     pub original_content: String,
     pub translated_content: String,
-    pub file_name: String,   // 文件保存名称
-    pub folder_path: String, // 文件夹路径
-    pub selected: bool,      // 是否选中下载
+    pub file_name: String,              // 文件保存名称
+    pub folder_path: String,            // 文件夹路径
+    pub selected: bool,                 // 是否选中下载
+    pub engine_usage: Vec<EngineUsage>, // 各翻译引擎完成的文本块数
+    #[serde(default)]
+    pub content_hash: String, // 原文内容哈希，用于增量翻译
+    #[serde(default)]
+    pub change: DocumentChange, // 与上一次翻译相比的变化
 }
 
 #[derive(Debug, Clone)]
@@ -46,9 +153,121 @@ pub enum BatchStatus {
     Translating,
     Packaging,
     Completed,
+    /// 已暂停，已完成的译文保留
+    Paused,
+    /// 已取消，已完成的译文保留，未处理的文档可稍后继续
+    Cancelled,
     Failed(String),
 }
 
+/// 批量任务的运行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ControlState {
+    #[default]
+    Running,
+    Paused,
+    Cancelled,
+}
+
+#[derive(Debug, Default)]
+struct ControlInner {
+    state: Cell<ControlState>,
+    waiters: RefCell<Vec<Waker>>,
+}
+
+/// 协作式的暂停与取消令牌，克隆后共享同一状态。
+/// 批量翻译在开始每个文档、提取完成后和每次重试前检查该令牌
+#[derive(Debug, Clone, Default)]
+pub struct BatchControl {
+    inner: Rc<ControlInner>,
+}
+
+impl BatchControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> ControlState {
+        self.inner.state.get()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state() == ControlState::Cancelled
+    }
+
+    /// 暂停后续文档，正在进行的请求会继续完成，重试前的等待会提前结束
+    pub fn pause(&self) {
+        if self.state() == ControlState::Running {
+            self.set_and_wake(ControlState::Paused);
+        }
+    }
+
+    pub fn resume(&self) {
+        if self.state() == ControlState::Paused {
+            self.set_and_wake(ControlState::Running);
+        }
+    }
+
+    /// 取消任务，暂停中的工作者会立即退出
+    pub fn cancel(&self) {
+        self.set_and_wake(ControlState::Cancelled);
+    }
+
+    /// 暂停时等待恢复或取消；返回 false 表示任务已取消
+    pub async fn proceed(&self) -> bool {
+        poll_fn(|cx| match self.state() {
+            ControlState::Paused => {
+                self.inner.waiters.borrow_mut().push(cx.waker().clone());
+                Poll::Pending
+            }
+            state => Poll::Ready(state == ControlState::Running),
+        })
+        .await
+    }
+
+    /// 在任务暂停或取消时完成，用于打断重试前的等待
+    pub async fn interrupted(&self) {
+        poll_fn(|cx| match self.state() {
+            ControlState::Running => {
+                self.inner.waiters.borrow_mut().push(cx.waker().clone());
+                Poll::Pending
+            }
+            _ => Poll::Ready(()),
+        })
+        .await
+    }
+
+    fn set_and_wake(&self, state: ControlState) {
+        self.inner.state.set(state);
+        let waiters: Vec<Waker> = self.inner.waiters.borrow_mut().drain(..).collect();
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
+
+/// 重试后仍失败的文档及其最后一次错误
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchFailure {
+    pub link: DocumentLink,
+    pub error: AppError,
+}
+
+/// 一次批量翻译的结果，两个列表都按目录顺序排列
+#[derive(Debug, Clone, Default)]
+pub struct BatchResult {
+    pub documents: Vec<TranslatedDocument>,
+    pub failures: Vec<BatchFailure>,
+}
+
+/// 单个文档的处理结果
+enum DocumentOutcome {
+    Translated(TranslatedDocument),
+    Failed(AppError),
+    /// 任务取消时尚未完成，保持待处理状态
+    Skipped,
+}
+
 #[derive(Debug, Clone)]
 pub struct FolderStructure {
     pub folders: HashMap<String, Vec<TranslatedDocument>>,
@@ -85,41 +304,206 @@ impl FolderStructure {
 }
 
 pub struct BatchTranslationService {
-    jina_service: JinaService,
-    deeplx_service: DeepLXService,
+    extractor: Box<dyn Extractor>,
+    translator: Box<dyn Translator>,
     config: AppConfig,
     file_naming_service: FileNamingService,
+    previous: Option<PreviousTranslations>,
 }
 
 impl BatchTranslationService {
     pub fn new(config: &AppConfig) -> Self {
         Self {
-            jina_service: JinaService::new(config),
-            deeplx_service: DeepLXService::new(config),
+            extractor: create_extractor(config),
+            translator: create_translator(config),
             config: config.clone(),
             file_naming_service: FileNamingService::new(config.file_naming.clone()),
+            previous: None,
+        }
+    }
+
+    /// 基于上一次的翻译结果增量翻译：原文未变的文档直接沿用上次的译文
+    pub fn with_previous_translations(mut self, previous: Option<PreviousTranslations>) -> Self {
+        self.previous = previous;
+        self
+    }
+
+    /// 按请求的发现方式生成待翻译的文档列表
+    pub async fn discover_documents(
+        &self,
+        request: &BatchRequest,
+        progress_callback: impl Fn(BatchProgress) + 'static,
+    ) -> Result<Vec<DocumentLink>, String> {
+        match &request.discovery {
+            DiscoveryMode::Crawl(options) => {
+                self.crawl_documents(&request.url, options, progress_callback)
+                    .await
+            }
+            DiscoveryMode::IndexPage => self.parse_document_index(&request.url).await,
+            DiscoveryMode::Sitemap(filter) => SitemapService::new(&self.config)
+                .discover(&request.url, filter)
+                .await
+                .map_err(|e| format!("无法读取站点地图: {}", e)),
+        }
+    }
+
+    /// 从起始页面广度优先爬取同站页面
+    async fn crawl_documents(
+        &self,
+        start_url: &str,
+        options: &CrawlOptions,
+        progress_callback: impl Fn(BatchProgress) + 'static,
+    ) -> Result<Vec<DocumentLink>, String> {
+        log(&format!(
+            "=== 开始爬取: {}，深度 {}，最多 {} 个页面 ===",
+            start_url, options.max_depth, options.max_pages
+        ));
+
+        let mut crawler = Crawler::new(start_url, options.clone()).map_err(|e| e.to_string())?;
+        while let Some((url, depth)) = crawler.next_page() {
+            progress_callback(BatchProgress {
+                total: 0,
+                completed: crawler.found(),
+                current_task: format!("正在爬取第 {} 个页面: {}", crawler.visited(), url),
+                failed_count: 0,
+                status: BatchStatus::Parsing,
+            });
+
+            match self.extractor.extract(&url).await {
+                Ok(content) => {
+                    crawler.visit(
+                        &url,
+                        depth,
+                        &content.title,
+                        content.links.iter().map(|link| link.url.as_str()),
+                    );
+                }
+                Err(e) => {
+                    log(&format!("爬取失败，跳过: {} - {}", url, e));
+                }
+            }
         }
+
+        let links = crawler.into_links();
+        log(&format!("爬取完成，找到 {} 个文档", links.len()));
+        Ok(links)
     }
 
     /// 解析文档主页，提取所有链接和目录结构
     pub async fn parse_document_index(&self, index_url: &str) -> Result<Vec<DocumentLink>, String> {
-        web_sys::console::log_1(&"=== 开始解析文档索引 ===".into());
+        log("=== 开始解析文档索引 ===");
+
+        // 已知的文档生成器目录文件使用对应的解析器，解析失败时（例如按主机名识别的
+        // readthedocs 页面没有 Sphinx 侧边栏）改用通用的链接提取
+        if let Some(format) = IndexFormat::detect(index_url) {
+            match self.parse_structured_index(format, index_url).await {
+                Ok(links) if !links.is_empty() => return Ok(links),
+                Ok(_) => log(&format!(
+                    "{} 目录中没有文档链接，改用通用解析",
+                    format.name()
+                )),
+                Err(e) => log(&format!(
+                    "{} 目录解析失败，改用通用解析: {}",
+                    format.name(),
+                    e
+                )),
+            }
+        }
 
         // 提取索引页面内容
         let index_content = self
-            .jina_service
-            .extract_content(index_url, &self.config)
+            .extractor
+            .extract(index_url)
             .await
-            .map_err(|e| format!("无法提取索引页面内容: {}", e))?;
+            .map_err(|e| format!("无法提取索引页面内容: {}", e))?
+            .markdown;
 
         // 解析链接
         let links = self.extract_links_from_content(&index_content);
 
-        web_sys::console::log_1(&format!("解析完成，找到 {} 个文档链接", links.len()).into());
+        log(&format!("解析完成，找到 {} 个文档链接", links.len()));
 
         Ok(links)
     }
 
+    /// 获取目录源文件并按格式解析
+    async fn parse_structured_index(
+        &self,
+        format: IndexFormat,
+        index_url: &str,
+    ) -> Result<Vec<DocumentLink>, String> {
+        let source_url = raw_source_url(index_url);
+        log(&format!(
+            "识别为 {} 目录，获取源文件: {}",
+            format.name(),
+            source_url
+        ));
+
+        let fetcher = ReadabilityExtractor::new(&self.config);
+        let page = fetcher
+            .fetch_page(&source_url)
+            .await
+            .map_err(|e| format!("无法获取 {} 目录: {}", format.name(), e))?;
+        let mut links = format
+            .parse(&page.body, &source_url)
+            .map_err(|e| e.to_string())?;
+
+        // reStructuredText 源文件的 toctree 分散在各个子文档中
+        if format == IndexFormat::Sphinx && !page.body.contains("toctree-l1") {
+            links = self.expand_sphinx_toctree(&fetcher, links).await;
+        }
+
+        log(&format!(
+            "{} 目录解析完成，找到 {} 个文档链接",
+            format.name(),
+            links.len()
+        ));
+
+        Ok(links)
+    }
+
+    /// 深度优先获取子文档，把其中 toctree 列出的文档作为下一级插入
+    async fn expand_sphinx_toctree(
+        &self,
+        fetcher: &ReadabilityExtractor,
+        links: Vec<DocumentLink>,
+    ) -> Vec<DocumentLink> {
+        let mut seen: HashSet<String> = links.iter().map(|link| link.url.clone()).collect();
+        let mut pending: Vec<DocumentLink> = links.into_iter().rev().collect();
+        let mut expanded = Vec::new();
+        let mut fetches = 0;
+
+        while let Some(link) = pending.pop() {
+            if link.level < MAX_LEVEL && fetches < MAX_TOCTREE_FETCHES {
+                fetches += 1;
+                let children = match (
+                    fetcher.fetch_page(&link.url).await,
+                    url::Url::parse(&link.url),
+                ) {
+                    (Ok(page), Ok(base)) => parse_sphinx_toctree(&page.body, &base),
+                    (Err(e), _) => {
+                        log(&format!("获取子文档失败，跳过展开: {} - {}", link.url, e));
+                        Vec::new()
+                    }
+                    _ => Vec::new(),
+                };
+                for mut child in children.into_iter().rev() {
+                    if seen.insert(child.url.clone()) {
+                        child.level = link.level + 1;
+                        pending.push(child);
+                    }
+                }
+            }
+            expanded.push(link);
+        }
+
+        // pending 后进先出，子文档会紧跟在父文档之后；最后重新编号
+        for (order, link) in expanded.iter_mut().enumerate() {
+            link.order = order;
+        }
+        expanded
+    }
+
     /// 从内容中提取链接和目录结构
     fn extract_links_from_content(&self, content: &str) -> Vec<DocumentLink> {
         let mut links = Vec::new();
@@ -374,15 +758,15 @@ impl BatchTranslationService {
         // 首先检查制表符缩进
         let tabs = line.chars().take_while(|&c| c == '\t').count();
         let indent_level = if tabs > 0 {
-            std::cmp::min(tabs, 3)
+            std::cmp::min(tabs, MAX_LEVEL)
         } else {
             // 按空格缩进计算，每4个空格算一级
-            let base_level = std::cmp::min(leading_spaces / 4, 3);
+            let base_level = std::cmp::min(leading_spaces / 4, MAX_LEVEL);
 
             // 检查是否有列表标记，如果有，可能需要调整级别
             if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
                 // 列表项可能需要额外的缩进级别
-                std::cmp::min(base_level + (leading_spaces / 8), 3)
+                std::cmp::min(base_level + (leading_spaces / 8), MAX_LEVEL)
             } else {
                 base_level
             }
@@ -406,16 +790,30 @@ impl BatchTranslationService {
         clean
     }
 
-    /// 批量翻译文档
+    /// 批量翻译文档：按配置的并发数同时处理多个文档，结果按目录顺序返回。
+    /// 每个文档处理完后调用 `on_document`，便于调用方保存断点；
+    /// 通过 `control` 暂停或取消，取消时返回已完成的部分结果
     pub async fn batch_translate(
         &self,
         links: Vec<DocumentLink>,
+        control: &BatchControl,
         progress_callback: impl Fn(BatchProgress) + 'static,
-    ) -> Result<Vec<TranslatedDocument>, String> {
+        on_document: impl Fn(&DocumentLink, Result<&TranslatedDocument, &AppError>),
+    ) -> Result<BatchResult, String> {
         let total = links.len();
-        let mut translated_docs = Vec::new();
-        let mut failed_count = 0;
+        let workers = self.config.batch.workers();
+        let completed = Cell::new(0);
+        let failed = Cell::new(0);
+        let running_status = || match control.state() {
+            ControlState::Running => BatchStatus::Translating,
+            ControlState::Paused => BatchStatus::Paused,
+            ControlState::Cancelled => BatchStatus::Cancelled,
+        };
 
+        log(&format!(
+            "开始批量翻译 {} 个文档，并发数: {}",
+            total, workers
+        ));
         progress_callback(BatchProgress {
             total,
             completed: 0,
@@ -424,144 +822,247 @@ impl BatchTranslationService {
             status: BatchStatus::Translating,
         });
 
-        for (index, link) in links.iter().enumerate() {
-            progress_callback(BatchProgress {
-                total,
-                completed: index,
-                current_task: format!("正在翻译: {} ({}/{})", link.title, index + 1, total),
-                failed_count,
-                status: BatchStatus::Translating,
-            });
-
-            // 增加重试机制
-            let mut retry_count = 0;
-            let max_retries = 3;
+        let results = run_bounded(links, workers, |link| {
+            let completed = &completed;
+            let failed = &failed;
+            let progress_callback = &progress_callback;
+            let on_document = &on_document;
+            let running_status = &running_status;
+            async move {
+                if !control.proceed().await {
+                    return None;
+                }
+                progress_callback(BatchProgress {
+                    total,
+                    completed: completed.get(),
+                    current_task: format!("正在翻译: {}", link.title),
+                    failed_count: failed.get(),
+                    status: running_status(),
+                });
 
-            loop {
-                match self.translate_single_document(link).await {
-                    Ok(translated_doc) => {
-                        translated_docs.push(translated_doc);
-                        web_sys::console::log_1(&format!("✓ 翻译完成: {}", link.title).into());
-                        break;
+                let result = match self.translate_with_retry(&link, control).await {
+                    DocumentOutcome::Translated(doc) => {
+                        completed.set(completed.get() + 1);
+                        on_document(&link, Ok(&doc));
+                        Ok(doc)
                     }
-                    Err(e) => {
-                        retry_count += 1;
-                        web_sys::console::log_1(
-                            &format!(
-                                "✗ 翻译失败 (尝试 {}/{}): {} - {}",
-                                retry_count, max_retries, link.title, e
-                            )
-                            .into(),
-                        );
-
-                        if retry_count >= max_retries {
-                            failed_count += 1;
-                            web_sys::console::log_1(&format!("✗ 最终失败: {}", link.title).into());
-                            break;
-                        } else {
-                            // 重试前等待更长时间
-                            let retry_delay = 2000 * retry_count as u32;
-                            web_sys::console::log_1(
-                                &format!("等待 {}ms 后重试...", retry_delay).into(),
-                            );
-                            TimeoutFuture::new(retry_delay).await;
-                        }
+                    DocumentOutcome::Failed(error) => {
+                        failed.set(failed.get() + 1);
+                        on_document(&link, Err(&error));
+                        Err(BatchFailure {
+                            link: link.clone(),
+                            error,
+                        })
                     }
-                }
+                    DocumentOutcome::Skipped => return None,
+                };
+                progress_callback(BatchProgress {
+                    total,
+                    completed: completed.get(),
+                    current_task: format!(
+                        "已处理: {} ({}/{})",
+                        link.title,
+                        completed.get() + failed.get(),
+                        total
+                    ),
+                    failed_count: failed.get(),
+                    status: running_status(),
+                });
+                Some(result)
             }
+        })
+        .await;
 
-            // 每个文档之间的基本延迟
-            TimeoutFuture::new(1500).await;
-
-            // 每处理5个文档后，额外休息一下
-            if (index + 1) % 5 == 0 {
-                web_sys::console::log_1(&"批量处理中途休息...".into());
-                TimeoutFuture::new(3000).await;
+        let mut translated_docs = Vec::new();
+        let mut failures = Vec::new();
+        for result in results.into_iter().flatten() {
+            match result {
+                Ok(doc) => translated_docs.push(doc),
+                Err(failure) => failures.push(failure),
             }
         }
-
-        progress_callback(BatchProgress {
-            total,
-            completed: translated_docs.len(),
-            current_task: if failed_count > 0 {
+        translated_docs.sort_by_key(|doc| doc.link.order);
+        failures.sort_by_key(|failure| failure.link.order);
+        let failed_count = failures.len();
+
+        let (current_task, status) = if control.is_cancelled() {
+            let skipped = total - translated_docs.len() - failed_count;
+            log(&format!("批量翻译已取消，{} 个文档未处理", skipped));
+            (
+                format!(
+                    "已取消，成功: {}, 失败: {}, 未处理: {}",
+                    translated_docs.len(),
+                    failed_count,
+                    skipped
+                ),
+                BatchStatus::Cancelled,
+            )
+        } else if failed_count > 0 {
+            (
                 format!(
                     "翻译完成，成功: {}, 失败: {}",
                     translated_docs.len(),
                     failed_count
-                )
-            } else {
-                "所有文档翻译完成".to_string()
-            },
+                ),
+                BatchStatus::Completed,
+            )
+        } else {
+            ("所有文档翻译完成".to_string(), BatchStatus::Completed)
+        };
+        progress_callback(BatchProgress {
+            total,
+            completed: translated_docs.len(),
+            current_task,
             failed_count,
-            status: BatchStatus::Completed,
+            status,
         });
 
-        Ok(translated_docs)
+        Ok(BatchResult {
+            documents: translated_docs,
+            failures,
+        })
+    }
+
+    /// 翻译单个文档，失败时重试。请求速率由提取器和翻译引擎各自的限速器控制，
+    /// 所有工作者共用同一组实例
+    async fn translate_with_retry(
+        &self,
+        link: &DocumentLink,
+        control: &BatchControl,
+    ) -> DocumentOutcome {
+        let max_retries = 3;
+        let mut last_error = None;
+
+        for retry_count in 1..=max_retries {
+            if !control.proceed().await {
+                return DocumentOutcome::Skipped;
+            }
+            match self.translate_single_document(link, control).await {
+                Ok(translated_doc) => {
+                    log(&format!("✓ 翻译完成: {}", link.title));
+                    return DocumentOutcome::Translated(translated_doc);
+                }
+                Err(_) if control.is_cancelled() => return DocumentOutcome::Skipped,
+                Err(e) => {
+                    log(&format!(
+                        "✗ 翻译失败 (尝试 {}/{}): {} - {}",
+                        retry_count, max_retries, link.title, e
+                    ));
+
+                    if retry_count < max_retries {
+                        // 重试前等待更长时间，暂停或取消时不再等待，由下一轮的 proceed 处理
+                        let retry_delay = 2000 * retry_count;
+                        log(&format!("等待 {}ms 后重试...", retry_delay));
+                        let delay = sleep_ms(retry_delay).fuse();
+                        let interrupted = control.interrupted().fuse();
+                        pin_mut!(delay, interrupted);
+                        select! {
+                            () = delay => {}
+                            () = interrupted => log("重试等待被暂停或取消打断"),
+                        }
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        log(&format!("✗ 最终失败: {}", link.title));
+        DocumentOutcome::Failed(last_error.unwrap_or_else(|| AppError::translation("翻译失败")))
     }
 
     /// 翻译单个文档
     async fn translate_single_document(
         &self,
         link: &DocumentLink,
-    ) -> Result<TranslatedDocument, String> {
-        web_sys::console::log_1(&format!("开始翻译文档: {}", link.url).into());
+        control: &BatchControl,
+    ) -> AppResult<TranslatedDocument> {
+        log(&format!("开始翻译文档: {}", link.url));
 
         // 提取内容
-        let original_content = match self
-            .jina_service
-            .extract_content(&link.url, &self.config)
-            .await
-        {
+        let original_content = match self.extractor.extract(&link.url).await {
             Ok(content) => {
-                if content.trim().is_empty() {
-                    return Err("提取的内容为空".to_string());
+                if content.markdown.trim().is_empty() {
+                    return Err(AppError::extraction("提取的内容为空"));
                 }
-                content
+                content.document()
             }
-            Err(e) => return Err(format!("提取内容失败: {}", e)),
+            Err(e) => return Err(e),
         };
 
-        web_sys::console::log_1(
-            &format!("内容提取成功，长度: {} 字符", original_content.len()).into(),
-        );
+        log(&format!(
+            "内容提取成功，长度: {} 字符",
+            original_content.len()
+        ));
 
-        // 保护代码块
-        let mut content_processor = ContentProcessor::new();
-        let protected_content = content_processor.protect_code_blocks(&original_content);
-        let protection_stats = content_processor.get_protection_stats();
+        // 原文与上次翻译时相同，直接沿用上次的译文
+        let content_hash = content_hash(&original_content);
+        let change = match &self.previous {
+            Some(previous) => {
+                if let Some(reused) = previous.reusable(&link.url, &content_hash) {
+                    log(&format!("原文未变，沿用上次译文: {}", link.url));
+                    return Ok(TranslatedDocument {
+                        link: link.clone(),
+                        original_content,
+                        translated_content: reused.translated_content.clone(),
+                        file_name: reused.file_name.clone(),
+                        folder_path: reused.folder_path.clone(),
+                        selected: true,
+                        engine_usage: reused.engine_usage.clone(),
+                        content_hash,
+                        change: DocumentChange::Unchanged,
+                    });
+                }
+                previous.classify(&link.url, &content_hash)
+            }
+            None => DocumentChange::Added,
+        };
 
-        if protection_stats.total_blocks() > 0 {
-            web_sys::console::log_1(
-                &format!("代码块保护: {}", protection_stats.get_summary()).into(),
-            );
+        // 提取完成后再检查一次，暂停时不再发送翻译请求
+        if !control.proceed().await {
+            return Err(AppError::translation("任务已取消"));
         }
 
-        // 翻译内容
-        let translated_protected = match self
-            .deeplx_service
-            .translate(
-                &protected_content,
-                &self.config.default_source_lang,
-                &self.config.default_target_lang,
-                &self.config,
-            )
-            .await
-        {
-            Ok(content) => {
-                if content.trim().is_empty() {
-                    return Err("翻译结果为空".to_string());
-                }
-                content
+        // 原文有变化时只翻译变化的段落，其余段落沿用上次的译文
+        let diff = self
+            .previous
+            .as_ref()
+            .and_then(|previous| previous.document(&link.url))
+            .and_then(|doc| {
+                ParagraphDiff::new(
+                    &original_content,
+                    &doc.original_content,
+                    &doc.translated_content,
+                )
+            })
+            .filter(|diff| diff.reused_count() > 0);
+
+        let outcome = match &diff {
+            Some(diff) => {
+                log(&format!(
+                    "段落增量翻译: {} 段中 {} 段有变化",
+                    diff.paragraph_count(),
+                    diff.changed_count()
+                ));
+                translate_changed_paragraphs(
+                    diff,
+                    self.translator.as_ref(),
+                    &self.config.default_source_lang,
+                    &self.config.default_target_lang,
+                )
+                .await?
             }
-            Err(e) => return Err(format!("翻译失败: {}", e)),
+            None => self.translate_full_document(&original_content).await?,
         };
+        if outcome.text.trim().is_empty() {
+            return Err(AppError::translation("翻译结果为空"));
+        }
+        let engine_usage = outcome.engine_usage();
+        let translated_content = outcome.text;
 
-        web_sys::console::log_1(
-            &format!("翻译成功，长度: {} 字符", translated_protected.len()).into(),
-        );
-
-        // 恢复代码块
-        let translated_content = content_processor.restore_code_blocks(&translated_protected);
+        log(&format!(
+            "翻译成功，长度: {} 字符",
+            translated_content.len()
+        ));
 
         // 生成包含路径信息的文件名
         let enhanced_title = self.create_enhanced_title_with_path(&link.url, &link.title);
@@ -588,6 +1089,35 @@ impl BatchTranslationService {
             file_name: naming_result.file_name,
             folder_path,
             selected: true, // 默认选中
+            engine_usage,
+            content_hash,
+            change,
+        })
+    }
+
+    /// 保护代码块后翻译整篇文档，返回的译文已恢复代码块
+    async fn translate_full_document(&self, content: &str) -> AppResult<TranslationOutcome> {
+        let mut content_processor = ContentProcessor::new();
+        let protected_content = content_processor.protect_code_blocks(content);
+        let protection_stats = content_processor.get_protection_stats();
+
+        if protection_stats.total_blocks() > 0 {
+            log(&format!("代码块保护: {}", protection_stats.get_summary()));
+        }
+
+        let outcome = self
+            .translator
+            .translate_with_report(
+                &protected_content,
+                &self.config.default_source_lang,
+                &self.config.default_target_lang,
+                &|_| {},
+            )
+            .await?;
+
+        Ok(TranslationOutcome {
+            text: content_processor.restore_code_blocks(&outcome.text),
+            ..outcome
         })
     }
 
@@ -698,8 +1228,10 @@ impl BatchTranslationService {
     pub fn create_compressed_archive(
         &self,
         documents: &[TranslatedDocument],
+        failures: &[BatchFailure],
+        changelog: Option<&Changelog>,
     ) -> Result<Vec<u8>, String> {
-        web_sys::console::log_1(&"开始创建tar.gz归档文件".into());
+        log("开始创建tar.gz归档文件");
 
         // 只处理选中的文档，按order排序
         let mut selected_docs: Vec<&TranslatedDocument> =
@@ -718,7 +1250,7 @@ impl BatchTranslationService {
         let mut tar = Builder::new(encoder);
 
         // 添加README.md文件
-        let readme_content = self.generate_readme_content(&selected_docs);
+        let readme_content = self.generate_readme_content(&selected_docs, failures, changelog);
         let readme_bytes = readme_content.as_bytes();
         let mut header = tar::Header::new_gnu();
         header.set_size(readme_bytes.len() as u64);
@@ -728,6 +1260,23 @@ impl BatchTranslationService {
         tar.append_data(&mut header, "README.md", std::io::Cursor::new(readme_bytes))
             .map_err(|e| format!("无法添加README文件: {}", e))?;
 
+        // 增量翻译时附带变更记录
+        if let Some(changelog) = changelog {
+            let changelog_content = changelog.to_markdown();
+            let changelog_bytes = changelog_content.as_bytes();
+            let mut header = tar::Header::new_gnu();
+            header.set_size(changelog_bytes.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+
+            tar.append_data(
+                &mut header,
+                "CHANGELOG.md",
+                std::io::Cursor::new(changelog_bytes),
+            )
+            .map_err(|e| format!("无法添加变更记录: {}", e))?;
+        }
+
         // 按文件夹分组并按顺序添加文档
         for doc in &selected_docs {
             let file_path = if doc.folder_path.is_empty() || doc.folder_path == "docs" {
@@ -743,7 +1292,7 @@ impl BatchTranslationService {
                 )
             };
 
-            web_sys::console::log_1(&format!("添加文件: {}", file_path).into());
+            log(&format!("添加文件: {}", file_path));
 
             // 创建完整的文档内容，包含元数据
             let mut file_content = String::new();
@@ -776,20 +1325,22 @@ impl BatchTranslationService {
             .finish()
             .map_err(|e| format!("无法完成gzip压缩: {}", e))?;
 
-        web_sys::console::log_1(
-            &format!(
-                "tar.gz归档创建完成，包含 {} 个文档，压缩后大小: {} 字节",
-                selected_docs.len(),
-                compressed_data.len()
-            )
-            .into(),
-        );
+        log(&format!(
+            "tar.gz归档创建完成，包含 {} 个文档，压缩后大小: {} 字节",
+            selected_docs.len(),
+            compressed_data.len()
+        ));
 
         Ok(compressed_data)
     }
 
     /// 生成README内容
-    fn generate_readme_content(&self, documents: &[&TranslatedDocument]) -> String {
+    fn generate_readme_content(
+        &self,
+        documents: &[&TranslatedDocument],
+        failures: &[BatchFailure],
+        changelog: Option<&Changelog>,
+    ) -> String {
         let mut content = String::new();
 
         content.push_str("# 翻译文档归档\n\n");
@@ -799,6 +1350,31 @@ impl BatchTranslationService {
         ));
         content.push_str(&format!("文档总数: {} 个\n\n", documents.len()));
 
+        if let Some(changelog) = changelog {
+            content.push_str(&format!(
+                "## 增量翻译\n\n与 {} 的翻译相比: {}，详见 CHANGELOG.md\n\n",
+                changelog.previous_translated_at,
+                changelog.summary()
+            ));
+        }
+
+        let engine_usage = merge_engine_usage(
+            documents
+                .iter()
+                .flat_map(|doc| doc.engine_usage.iter().copied()),
+        );
+        if !engine_usage.is_empty() {
+            content.push_str("## 翻译引擎统计\n\n");
+            for usage in &engine_usage {
+                content.push_str(&format!(
+                    "- {}: {} 块\n",
+                    usage.engine.display_name(),
+                    usage.chunks
+                ));
+            }
+            content.push('\n');
+        }
+
         // 按文件夹分组显示目录
         let mut folders: HashMap<String, Vec<&TranslatedDocument>> = HashMap::new();
         for doc in documents {
@@ -817,7 +1393,7 @@ impl BatchTranslationService {
 
             for doc in docs {
                 content.push_str(&format!(
-                    "- [{}]({})\n  - 原始URL: {}\n  - 文件路径: {}/{}\n\n",
+                    "- [{}]({})\n  - 原始URL: {}\n  - 文件路径: {}/{}\n",
                     doc.link.title,
                     doc.file_name,
                     doc.link.url,
@@ -828,9 +1404,18 @@ impl BatchTranslationService {
                     },
                     doc.file_name
                 ));
+                if !doc.engine_usage.is_empty() {
+                    content.push_str(&format!(
+                        "  - 翻译引擎: {}\n",
+                        format_engine_usage(&doc.engine_usage)
+                    ));
+                }
+                content.push('\n');
             }
         }
 
+        content.push_str(&failure_report(failures));
+
         content.push_str("---\n\n");
         content.push_str("*此归档由URL翻译工具自动生成*\n");
 
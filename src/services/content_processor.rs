@@ -205,7 +205,7 @@ impl ContentProcessor {
 
     /// 保护HTML代码元素
     fn protect_html_code_elements(&mut self, content: String) -> String {
-        let mut result = content;
+        let mut result = self.protect_html_comments(content);
 
         // 保护 <code> 标签
         result = self.protect_html_tag(result, "code", CodeBlockType::HtmlCode);
@@ -222,6 +222,34 @@ impl ContentProcessor {
         result
     }
 
+    /// 保护HTML注释，例如PDF提取时保留的页码标记
+    fn protect_html_comments(&mut self, content: String) -> String {
+        let mut result = content;
+        let mut search_start = 0;
+
+        while let Some(start) = result[search_start..].find("<!--") {
+            let actual_start = search_start + start;
+            let Some(end) = result[actual_start + 4..].find("-->") else {
+                break;
+            };
+            let actual_end = actual_start + 4 + end + 3;
+
+            let placeholder = self.create_placeholder();
+            let code_block = CodeBlock {
+                id: Uuid::new_v4().to_string(),
+                content: result[actual_start..actual_end].to_string(),
+                language: Some("html".to_string()),
+                block_type: CodeBlockType::HtmlTag,
+            };
+            self.preserved_blocks
+                .insert(placeholder.clone(), code_block);
+            result.replace_range(actual_start..actual_end, &placeholder);
+            search_start = actual_start + placeholder.len();
+        }
+
+        result
+    }
+
     /// 保护特定的HTML标签
     fn protect_html_tag(
         &mut self,
@@ -0,0 +1,298 @@
+use super::batch_service::{DocumentLink, MAX_LEVEL};
+use super::index_parser::title_from_slug;
+use crate::error::{AppError, AppResult};
+use std::collections::{HashSet, VecDeque};
+use url::Url;
+
+/// 不作为文档翻译的资源扩展名
+const ASSET_EXTENSIONS: &[&str] = &[
+    ".css", ".js", ".json", ".xml", ".gz", ".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp",
+    ".ico", ".woff", ".woff2", ".ttf", ".zip",
+];
+
+/// 规范化时去掉的跟踪参数，以 `_` 结尾的表示前缀
+const TRACKING_PARAMS: &[&str] = &[
+    "utm_", "fbclid", "gclid", "msclkid", "mc_cid", "mc_eid", "_ga", "_gl", "ref", "ref_src",
+];
+
+/// 爬取范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrawlScope {
+    /// 同一协议、主机和端口下的所有页面
+    Origin,
+    /// 起始地址所在目录之下的页面
+    PathPrefix,
+}
+
+/// 递归爬取的选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrawlOptions {
+    /// 从起始页面开始跟随链接的层数，0 表示只包含起始页面
+    pub max_depth: usize,
+    pub scope: CrawlScope,
+    /// 只列出匹配任一模式的页面，为空时不限制；不匹配的页面仍会被访问以发现更深的链接
+    pub include: Vec<String>,
+    /// 匹配任一模式的页面既不访问也不列出
+    pub exclude: Vec<String>,
+    /// 最多访问的页面数
+    pub max_pages: usize,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 2,
+            scope: CrawlScope::PathPrefix,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_pages: 200,
+        }
+    }
+}
+
+/// 把多行或逗号分隔的输入拆分为模式列表
+pub fn parse_patterns(input: &str) -> Vec<String> {
+    input
+        .split(['\n', ','])
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 路径是否指向图片、样式等静态资源
+pub fn is_asset_path(path: &str) -> bool {
+    let path = path.to_lowercase();
+    ASSET_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+/// 规范化地址：去掉锚点、跟踪参数和路径末尾的斜杠，只接受 http(s)。
+/// 只用于去重，访问页面时使用 [`clean_url`] 保留的原始路径
+pub fn normalize_url(url: &str) -> Option<String> {
+    let mut url = clean_url(url)?;
+    let path = url.path().to_string();
+    if path.len() > 1 && path.ends_with('/') {
+        url.set_path(path.trim_end_matches('/'));
+    }
+    Some(url.to_string())
+}
+
+/// 去掉锚点和跟踪参数，保留路径末尾的斜杠，页面中的相对链接才能按原目录解析
+fn clean_url(url: &str) -> Option<Url> {
+    let mut url = Url::parse(url.trim()).ok()?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return None;
+    }
+    url.set_fragment(None);
+
+    let params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if params.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(params);
+    }
+    Some(url)
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_lowercase();
+    TRACKING_PARAMS.iter().any(|param| {
+        if param.ends_with('_') {
+            key.starts_with(param)
+        } else {
+            key == *param
+        }
+    })
+}
+
+/// 通配符匹配：`*` 匹配除 `/` 以外的任意字符，`**` 匹配任意字符，`?` 匹配单个字符。
+/// 包含 `://` 的模式匹配完整地址，否则匹配路径（含查询串）
+pub fn glob_matches(pattern: &str, url: &Url) -> bool {
+    let target = if pattern.contains("://") {
+        url.as_str().to_string()
+    } else {
+        match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        }
+    };
+    let pattern: Vec<char> = pattern.chars().collect();
+    let target: Vec<char> = target.chars().collect();
+    glob_match_chars(&pattern, &target)
+}
+
+fn glob_match_chars(pattern: &[char], target: &[char]) -> bool {
+    match pattern.first() {
+        None => target.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            // `/**/` 也匹配零层目录
+            if rest.first() == Some(&'/') && glob_match_chars(&rest[1..], target) {
+                return true;
+            }
+            (0..=target.len()).any(|i| glob_match_chars(rest, &target[i..]))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=target.len() {
+                if glob_match_chars(rest, &target[i..]) {
+                    return true;
+                }
+                if target.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => !target.is_empty() && glob_match_chars(&pattern[1..], &target[1..]),
+        Some(c) => target.first() == Some(c) && glob_match_chars(&pattern[1..], &target[1..]),
+    }
+}
+
+/// 广度优先的同站爬取状态：调用方获取页面后把其中的链接交回，由爬取器负责范围、过滤和去重
+pub struct Crawler {
+    options: CrawlOptions,
+    start: Url,
+    prefix: String,
+    queue: VecDeque<(String, usize)>,
+    /// 规范化后的地址，用于去重
+    seen: HashSet<String>,
+    visited: usize,
+    links: Vec<DocumentLink>,
+}
+
+impl Crawler {
+    pub fn new(start_url: &str, options: CrawlOptions) -> AppResult<Self> {
+        let start = clean_url(start_url)
+            .ok_or_else(|| AppError::validation("URL", format!("无效的起始地址: {}", start_url)))?;
+        let normalized = normalize_url(start.as_str())
+            .ok_or_else(|| AppError::validation("URL", format!("无效的起始地址: {}", start_url)))?;
+
+        // 起始地址是目录时前缀为它本身，是页面时为其所在目录
+        let path = start.path().to_string();
+        let prefix = if path.ends_with('/') {
+            path
+        } else {
+            match path.rfind('/') {
+                Some(pos) if !path[pos + 1..].contains('.') => format!("{}/", path),
+                Some(pos) => path[..=pos].to_string(),
+                None => "/".to_string(),
+            }
+        };
+
+        let mut seen = HashSet::new();
+        seen.insert(normalized);
+        Ok(Self {
+            options,
+            queue: VecDeque::from([(start.to_string(), 0)]),
+            start,
+            prefix,
+            seen,
+            visited: 0,
+            links: Vec::new(),
+        })
+    }
+
+    /// 下一个要访问的页面及其深度，达到页面上限或队列为空时返回 None
+    pub fn next_page(&mut self) -> Option<(String, usize)> {
+        if self.visited >= self.options.max_pages {
+            return None;
+        }
+        let next = self.queue.pop_front()?;
+        self.visited += 1;
+        Some(next)
+    }
+
+    /// 记录已访问的页面，并把范围内的新链接加入队列
+    pub fn visit<'a>(
+        &mut self,
+        url: &str,
+        depth: usize,
+        title: &str,
+        links: impl IntoIterator<Item = &'a str>,
+    ) {
+        if let Ok(page) = Url::parse(url) {
+            if self.is_included(&page) {
+                let title = if title.trim().is_empty() {
+                    title_from_slug(page.path())
+                } else {
+                    title.trim().to_string()
+                };
+                self.links.push(DocumentLink {
+                    title,
+                    url: url.to_string(),
+                    level: depth.min(MAX_LEVEL),
+                    order: self.links.len(),
+                });
+            }
+        }
+
+        if depth >= self.options.max_depth {
+            return;
+        }
+        for link in links {
+            let Some(parsed) = clean_url(link) else {
+                continue;
+            };
+            let Some(normalized) = normalize_url(parsed.as_str()) else {
+                continue;
+            };
+            if !self.in_scope(&parsed) || self.is_excluded(&parsed) || is_asset_path(parsed.path())
+            {
+                continue;
+            }
+            if self.seen.insert(normalized) {
+                self.queue.push_back((parsed.to_string(), depth + 1));
+            }
+        }
+    }
+
+    /// 已访问的页面数
+    pub fn visited(&self) -> usize {
+        self.visited
+    }
+
+    /// 已列入结果的页面数
+    pub fn found(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn into_links(self) -> Vec<DocumentLink> {
+        self.links
+    }
+
+    fn in_scope(&self, url: &Url) -> bool {
+        if url.origin() != self.start.origin() {
+            return false;
+        }
+        match self.options.scope {
+            CrawlScope::Origin => true,
+            CrawlScope::PathPrefix => {
+                // 目录本身不论是否带末尾斜杠都在范围内
+                let path = format!("{}/", url.path().trim_end_matches('/'));
+                path.starts_with(&self.prefix)
+            }
+        }
+    }
+
+    fn is_excluded(&self, url: &Url) -> bool {
+        self.options
+            .exclude
+            .iter()
+            .any(|pattern| glob_matches(pattern, url))
+    }
+
+    fn is_included(&self, url: &Url) -> bool {
+        !self.is_excluded(url)
+            && (self.options.include.is_empty()
+                || self
+                    .options
+                    .include
+                    .iter()
+                    .any(|pattern| glob_matches(pattern, url)))
+    }
+}
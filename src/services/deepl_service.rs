@@ -0,0 +1,280 @@
+use super::platform::log;
+use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
+use super::translator::{
+    ensure_placeholders, split_text_into_chunks, TranslateFuture, Translator,
+    TranslatorCapabilities,
+};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// DeepL单次请求最多携带的text条目数
+const MAX_TEXTS_PER_REQUEST: usize = 50;
+/// DeepL单次请求体大小上限约128KiB，这里留出余量
+const MAX_REQUEST_BYTES: usize = 120 * 1024;
+
+/// 官方DeepL API v2配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DeepLConfig {
+    /// 免费版为 https://api-free.deepl.com/v2/translate
+    pub api_url: String,
+    pub auth_key: String,
+    /// default / more / less / prefer_more / prefer_less
+    pub formality: String,
+    /// 空字符串表示不处理标签，可选 markdown / html
+    pub tag_handling: String,
+    pub preserve_formatting: bool,
+    pub glossary_id: String,
+}
+
+impl Default for DeepLConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "https://api-free.deepl.com/v2/translate".to_string(),
+            auth_key: String::new(),
+            formality: "default".to_string(),
+            tag_handling: String::new(),
+            preserve_formatting: true,
+            glossary_id: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeepLRequest {
+    pub text: Vec<String>,
+    pub target_lang: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_handling: Option<String>,
+    pub preserve_formatting: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glossary_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeepLResponse {
+    pub translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeepLTranslation {
+    pub text: String,
+}
+
+pub struct DeepLService {
+    client: Client,
+    rate_limiter: RateLimiter,
+    config: AppConfig,
+}
+
+impl DeepLService {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: Client::new(),
+            rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000),
+            config: config.clone(),
+        }
+    }
+
+    pub async fn translate_text(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<String> {
+        let chunks = split_text_into_chunks(text, self.config.max_text_length);
+        let batches = group_into_batches(&chunks);
+        log(&format!(
+            "DeepL翻译: 共 {} 块，分 {} 次请求",
+            chunks.len(),
+            batches.len()
+        ));
+
+        let mut translated_chunks = Vec::with_capacity(chunks.len());
+        for batch in &batches {
+            let retry_config = RetryConfig::default();
+            let translated = retry_with_backoff(
+                || Box::pin(self.translate_batch(batch, source_lang, target_lang)),
+                &retry_config,
+                &self.rate_limiter,
+            )
+            .await?;
+            translated_chunks.extend(translated);
+        }
+
+        Ok(translated_chunks.join("\n\n"))
+    }
+
+    /// 在一次请求中翻译多条文本，结果顺序与输入一致
+    pub async fn translate_batch(
+        &self,
+        texts: &[String],
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<Vec<String>> {
+        let deepl = &self.config.deepl;
+        if deepl.auth_key.trim().is_empty() {
+            return Err(AppError::config("未配置DeepL Auth Key"));
+        }
+
+        let request = build_deepl_request(deepl, texts, source_lang, target_lang)?;
+        let response = self
+            .client
+            .post(&deepl.api_url)
+            .header(
+                "Authorization",
+                format!("DeepL-Auth-Key {}", deepl.auth_key),
+            )
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::network(format!(
+                    "DeepL网络请求失败: {}. 可能是CORS问题或API不可用",
+                    e
+                ))
+            })?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AppError::network(format!("读取响应文本失败: {}", e)))?;
+
+        if !status.is_success() {
+            return Err(map_deepl_error(status.as_u16(), &body));
+        }
+
+        let result: DeepLResponse = serde_json::from_str(&body)
+            .map_err(|e| AppError::parse(format!("无法解析DeepL响应: {} - {}", e, body)))?;
+
+        if result.translations.len() != texts.len() {
+            return Err(AppError::translation(format!(
+                "DeepL返回了 {} 条译文，预期 {} 条",
+                result.translations.len(),
+                texts.len()
+            )));
+        }
+
+        texts
+            .iter()
+            .zip(result.translations)
+            .map(|(source, translation)| {
+                if translation.text.trim().is_empty() && !source.trim().is_empty() {
+                    Err(AppError::translation("DeepL返回了空的翻译结果"))
+                } else {
+                    ensure_placeholders(source, &translation.text)
+                }
+            })
+            .collect()
+    }
+}
+
+impl Translator for DeepLService {
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text(text, source_lang, target_lang))
+    }
+
+    fn capabilities(&self) -> TranslatorCapabilities {
+        TranslatorCapabilities {
+            engine: TranslationEngine::DeepL,
+            max_text_length: self.config.max_text_length,
+            max_requests_per_second: self.config.max_requests_per_second,
+            supports_auto_detect: true,
+            supports_streaming: false,
+        }
+    }
+}
+
+/// 构造DeepL v2请求体，未设置的可选参数不会发送
+pub fn build_deepl_request(
+    config: &DeepLConfig,
+    texts: &[String],
+    source_lang: &str,
+    target_lang: &str,
+) -> AppResult<DeepLRequest> {
+    let non_empty = |value: &str| {
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    };
+
+    let source_lang = if source_lang.eq_ignore_ascii_case("auto") {
+        None
+    } else {
+        non_empty(source_lang).map(|lang| lang.to_uppercase())
+    };
+
+    // DeepL要求使用术语表时必须指定源语言，不能静默丢弃术语表
+    let glossary_id = non_empty(&config.glossary_id);
+    if glossary_id.is_some() && source_lang.is_none() {
+        return Err(AppError::validation(
+            "源语言",
+            "DeepL使用术语表时必须指定源语言，请选择源语言或清空术语表ID",
+        ));
+    }
+
+    let formality = non_empty(&config.formality).filter(|value| value != "default");
+
+    Ok(DeepLRequest {
+        text: texts.to_vec(),
+        target_lang: target_lang.to_uppercase(),
+        source_lang,
+        formality,
+        tag_handling: non_empty(&config.tag_handling),
+        preserve_formatting: config.preserve_formatting,
+        glossary_id,
+    })
+}
+
+/// 将DeepL的HTTP错误映射为AppError
+pub fn map_deepl_error(status: u16, body: &str) -> AppError {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| value.get("message")?.as_str().map(|m| m.to_string()))
+        .unwrap_or_else(|| body.to_string());
+
+    match status {
+        456 => AppError::rate_limit(format!("DeepL额度已用完: {}", message)),
+        429 => AppError::rate_limit(format!("DeepL请求过于频繁: {}", message)),
+        401 | 403 => AppError::config(format!("DeepL Auth Key无效: {}", message)),
+        413 => AppError::translation(format!("DeepL请求体过大: {}", message)),
+        _ => AppError::api("DeepL", format!("请求失败: {} - {}", status, message)),
+    }
+}
+
+/// 按条目数和请求体大小把文本块分组
+pub fn group_into_batches(chunks: &[String]) -> Vec<Vec<String>> {
+    let mut batches = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut current_bytes = 0;
+
+    for chunk in chunks {
+        if !current.is_empty()
+            && (current.len() >= MAX_TEXTS_PER_REQUEST
+                || current_bytes + chunk.len() > MAX_REQUEST_BYTES)
+        {
+            batches.push(std::mem::take(&mut current));
+            current_bytes = 0;
+        }
+        current_bytes += chunk.len();
+        current.push(chunk.clone());
+    }
+
+    if !current.is_empty() {
+        batches.push(current);
+    }
+
+    batches
+}
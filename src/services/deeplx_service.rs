@@ -1,10 +1,21 @@
+use super::platform::log;
 use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
-use crate::types::api_types::{AppConfig, DeepLXRequest, DeepLXResponse};
+use super::translation_memory::translate_with_memory;
+use super::translator::{
+    split_text_into_chunks, translate_chunks_concurrently, TranslateFuture, Translator,
+    TranslatorCapabilities,
+};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, DeepLXRequest, DeepLXResponse, TranslationEngine};
 use reqwest::Client;
 
+/// 分块翻译时失败文本块的最多尝试轮数
+const MAX_CHUNK_ROUNDS: usize = 3;
+
 pub struct DeepLXService {
     client: Client,
     rate_limiter: RateLimiter,
+    config: AppConfig,
 }
 
 impl DeepLXService {
@@ -12,107 +23,74 @@ impl DeepLXService {
         Self {
             client: Client::new(),
             rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000), // 1秒 = 1000毫秒
+            config: config.clone(),
         }
     }
 
-    pub async fn translate(
+    /// 翻译文本，启用翻译记忆时已翻译过的段落不再发送请求
+    pub async fn translate_text(
         &self,
         text: &str,
         source_lang: &str,
         target_lang: &str,
-        config: &AppConfig,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        web_sys::console::log_1(&format!("文本总长度: {} 字符", text.len()).into());
-
-        // 如果文本长度小于等于最大长度，直接翻译
-        if text.len() <= config.max_text_length {
-            web_sys::console::log_1(&"文本较短，直接翻译".into());
+    ) -> AppResult<String> {
+        if !self.config.translation_memory.enabled {
             return self
-                .translate_chunk(text, source_lang, target_lang, config)
+                .translate_uncached(text, source_lang, target_lang)
                 .await;
         }
 
-        // 文本较长，需要分块处理
-        let chunks = self.split_text_into_chunks(
+        translate_with_memory(
             text,
-            config.max_text_length,
-            config.max_paragraphs_per_request,
-        );
-        web_sys::console::log_1(&format!("文本较长，分为 {} 块进行翻译", chunks.len()).into());
-
-        let mut translated_chunks = Vec::new();
-
-        for (i, chunk) in chunks.iter().enumerate() {
-            web_sys::console::log_1(
-                &format!("翻译第 {} 块，长度: {} 字符", i + 1, chunk.len()).into(),
-            );
-
-            let translated_chunk = self
-                .translate_chunk(chunk, source_lang, target_lang, config)
-                .await?;
-            translated_chunks.push(translated_chunk);
-        }
-
-        Ok(translated_chunks.join("\n\n"))
+            TranslationEngine::DeepLX,
+            source_lang,
+            target_lang,
+            |segment| async move {
+                self.translate_uncached(&segment, source_lang, target_lang)
+                    .await
+            },
+        )
+        .await
     }
 
-    fn split_text_into_chunks(
+    async fn translate_uncached(
         &self,
         text: &str,
-        max_length: usize,
-        _max_paragraphs: usize,
-    ) -> Vec<String> {
-        let mut chunks = Vec::new();
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<String> {
+        let config = &self.config;
+        log(&format!("文本总长度: {} 字符", text.len()));
 
-        // 简化分块逻辑：直接按字符数分割，每块5000字符
-        if text.len() <= max_length {
-            chunks.push(text.to_string());
-            return chunks;
+        // 如果文本长度小于等于最大长度，直接翻译
+        if text.len() <= config.max_text_length {
+            log("文本较短，直接翻译");
+            return self
+                .translate_chunk(text, source_lang, target_lang, config)
+                .await;
         }
 
-        let mut start = 0;
-        while start < text.len() {
-            let end = std::cmp::min(start + max_length, text.len());
-
-            // 尝试在合适的位置分割（避免在单词中间分割）
-            let mut actual_end = end;
-            if end < text.len() {
-                // 向前查找最近的空格、换行或标点符号
-                for i in (start..end).rev() {
-                    let ch = text.chars().nth(i).unwrap_or(' ');
-                    if ch == ' '
-                        || ch == '\n'
-                        || ch == '.'
-                        || ch == '!'
-                        || ch == '?'
-                        || ch == '；'
-                        || ch == '。'
-                    {
-                        actual_end = i + 1;
-                        break;
-                    }
-                }
-
-                // 如果找不到合适的分割点，就按最大长度分割
-                if actual_end == end && end - start < max_length / 2 {
-                    actual_end = end;
-                }
-            }
-
-            let chunk = text[start..actual_end].trim().to_string();
-            if !chunk.is_empty() {
-                chunks.push(chunk);
-            }
-
-            start = actual_end;
-        }
+        // 文本较长，需要分块处理
+        let chunks = split_text_into_chunks(text, config.max_text_length);
+        log(&format!(
+            "文本较长，分为 {} 块进行翻译，并发数: {}",
+            chunks.len(),
+            config.chunk_concurrency
+        ));
 
-        // 如果没有分块，返回原文本
-        if chunks.is_empty() {
-            chunks.push(text.to_string());
-        }
+        // 并发的文本块共用同一个限速器，请求速率不会超过配置
+        let translated_chunks = translate_chunks_concurrently(
+            &chunks,
+            config.chunk_concurrency,
+            MAX_CHUNK_ROUNDS,
+            |i, chunk| {
+                log(&format!("翻译第 {} 块，长度: {} 字符", i + 1, chunk.len()));
+                self.translate_chunk(chunk, source_lang, target_lang, config)
+            },
+        )
+        .await?;
 
-        chunks
+        Ok(translated_chunks.join("\n\n"))
     }
 
     async fn translate_chunk(
@@ -121,16 +99,16 @@ impl DeepLXService {
         source_lang: &str,
         target_lang: &str,
         config: &AppConfig,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> AppResult<String> {
         let request = DeepLXRequest {
             text: text.to_string(),
             source_lang: source_lang.to_string(),
             target_lang: target_lang.to_string(),
         };
 
-        web_sys::console::log_1(&format!("发送翻译请求到: {}", config.deeplx_api_url).into());
-        web_sys::console::log_1(&format!("翻译文本长度: {} 字符", text.len()).into());
-        web_sys::console::log_1(&format!("请求数据: {:?}", request).into());
+        log(&format!("发送翻译请求到: {}", config.deeplx_api_url));
+        log(&format!("翻译文本长度: {} 字符", text.len()));
+        log(&format!("请求数据: {:?}", request));
 
         let retry_config = RetryConfig::default();
         let client = &self.client;
@@ -147,9 +125,7 @@ impl DeepLXService {
                     // 检查是否是新格式的API (带有token和参数的URL)
                     let response = if config.deeplx_api_url.contains("dptrans") {
                         // 新格式：使用POST请求和JSON数据
-                        web_sys::console::log_1(
-                            &format!("使用POST JSON请求到: {}", config.deeplx_api_url).into(),
-                        );
+                        log(&format!("使用POST JSON请求到: {}", config.deeplx_api_url));
 
                         client
                             .post(&config.deeplx_api_url)
@@ -159,7 +135,10 @@ impl DeepLXService {
                             .send()
                             .await
                             .map_err(|e| {
-                                format!("DeepLX网络请求失败: {}. 可能是CORS问题或API不可用", e)
+                                AppError::network(format!(
+                                    "DeepLX网络请求失败: {}. 可能是CORS问题或API不可用",
+                                    e
+                                ))
                             })?
                     } else {
                         // 标准DeepLX格式：使用POST请求和JSON body
@@ -171,46 +150,47 @@ impl DeepLXService {
                             .send()
                             .await
                             .map_err(|e| {
-                                format!("DeepLX网络请求失败: {}. 可能是CORS问题或API不可用", e)
+                                AppError::network(format!(
+                                    "DeepLX网络请求失败: {}. 可能是CORS问题或API不可用",
+                                    e
+                                ))
                             })?
                     };
 
                     let status = response.status();
-                    web_sys::console::log_1(&format!("DeepLX响应状态: {}", status).into());
+                    log(&format!("DeepLX响应状态: {}", status));
 
                     if response.status().is_success() {
                         let response_text = response
                             .text()
                             .await
-                            .map_err(|e| format!("读取响应文本失败: {}", e))?;
+                            .map_err(|e| AppError::network(format!("读取响应文本失败: {}", e)))?;
 
-                        web_sys::console::log_1(&format!("API响应内容: {}", response_text).into());
+                        log(&format!("API响应内容: {}", response_text));
 
                         // 尝试解析为标准DeepLX格式
                         if let Ok(result) = serde_json::from_str::<DeepLXResponse>(&response_text) {
-                            web_sys::console::log_1(
-                                &format!("标准DeepLX响应代码: {}", result.code).into(),
-                            );
+                            log(&format!("标准DeepLX响应代码: {}", result.code));
 
                             if result.code == 200 {
                                 if result.data.is_empty() {
-                                    Err(Box::from("DeepLX返回了空的翻译结果")
-                                        as Box<dyn std::error::Error>)
+                                    Err(AppError::translation("DeepLX返回了空的翻译结果"))
                                 } else {
                                     Ok(result.data)
                                 }
                             } else {
-                                Err(format!(
-                                    "DeepLX翻译失败，返回代码: {}，可能是语言不支持或文本格式问题",
-                                    result.code
-                                )
-                                .into())
+                                Err(AppError::api(
+                                    "DeepLX",
+                                    format!(
+                                        "翻译失败，返回代码: {}，可能是语言不支持或文本格式问题",
+                                        result.code
+                                    ),
+                                ))
                             }
                         } else {
                             // 如果不是标准格式，检查是否是纯文本翻译结果
                             if response_text.trim().is_empty() {
-                                Err(Box::from("API返回了空的翻译结果")
-                                    as Box<dyn std::error::Error>)
+                                Err(AppError::translation("API返回了空的翻译结果"))
                             } else if response_text.starts_with("{") {
                                 // 可能是其他JSON格式，尝试提取翻译结果
                                 if let Ok(json_value) =
@@ -226,18 +206,20 @@ impl DeepLXService {
                                     {
                                         Ok(translated.to_string())
                                     } else {
-                                        Err(format!(
+                                        Err(AppError::parse(format!(
                                             "无法从JSON响应中提取翻译结果: {}",
                                             response_text
-                                        )
-                                        .into())
+                                        )))
                                     }
                                 } else {
-                                    Err(format!("无法解析JSON响应: {}", response_text).into())
+                                    Err(AppError::parse(format!(
+                                        "无法解析JSON响应: {}",
+                                        response_text
+                                    )))
                                 }
                             } else {
                                 // 假设是纯文本翻译结果
-                                web_sys::console::log_1(&"假设响应是纯文本翻译结果".into());
+                                log("假设响应是纯文本翻译结果");
                                 Ok(response_text)
                             }
                         }
@@ -246,11 +228,13 @@ impl DeepLXService {
                             .text()
                             .await
                             .unwrap_or_else(|_| "无法读取错误信息".to_string());
-                        Err(format!(
-                            "DeepLX API请求失败: {} - {}，请检查API地址是否正确",
-                            status, error_text
-                        )
-                        .into())
+                        Err(AppError::api(
+                            "DeepLX",
+                            format!(
+                                "请求失败: {} - {}，请检查API地址是否正确",
+                                status, error_text
+                            ),
+                        ))
                     }
                 })
             },
@@ -262,3 +246,24 @@ impl DeepLXService {
         Ok(result)
     }
 }
+
+impl Translator for DeepLXService {
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text(text, source_lang, target_lang))
+    }
+
+    fn capabilities(&self) -> TranslatorCapabilities {
+        TranslatorCapabilities {
+            engine: TranslationEngine::DeepLX,
+            max_text_length: self.config.max_text_length,
+            max_requests_per_second: self.config.max_requests_per_second,
+            supports_auto_detect: true,
+            supports_streaming: false,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::jina_service::JinaService;
+use super::mock_extractor::MockExtractor;
+use super::pdf_extractor::{is_pdf_url, PdfExtractor};
+use super::readability_extractor::ReadabilityExtractor;
+use crate::error::AppResult;
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+
+/// 提取器返回的异步结果
+pub type ExtractFuture<'a> = Pin<Box<dyn Future<Output = AppResult<ExtractedContent>> + 'a>>;
+
+/// 可选的内容提取方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ExtractorKind {
+    /// Jina Reader服务
+    #[default]
+    Jina,
+    /// 内置的HTML正文提取
+    Readability,
+}
+
+/// 内容提取配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ExtractorConfig {
+    pub kind: ExtractorKind,
+    /// 浏览器中获取网页使用的CORS代理，`{url}` 为编码后的目标地址
+    pub cors_proxy: String,
+}
+
+impl Default for ExtractorConfig {
+    fn default() -> Self {
+        Self {
+            kind: ExtractorKind::Jina,
+            cors_proxy: "https://api.allorigins.win/raw?url={url}".to_string(),
+        }
+    }
+}
+
+/// 页面中的链接
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractedLink {
+    pub text: String,
+    /// 已按页面地址解析为绝对地址
+    pub url: String,
+}
+
+/// 提取出的网页内容
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ExtractedContent {
+    pub title: String,
+    /// Markdown格式的正文
+    pub markdown: String,
+    /// 页面的规范地址，无法确定时为请求地址
+    pub canonical_url: String,
+    /// 页面语言，例如 "en"
+    pub language: Option<String>,
+    pub links: Vec<ExtractedLink>,
+}
+
+impl ExtractedContent {
+    /// 用于翻译的完整文档，正文没有一级标题时在开头补上标题
+    pub fn document(&self) -> String {
+        let has_heading = self
+            .markdown
+            .lines()
+            .any(|line| line.trim_start().starts_with("# "));
+        if has_heading || self.title.trim().is_empty() {
+            self.markdown.clone()
+        } else {
+            format!("# {}\n\n{}", self.title.trim(), self.markdown)
+        }
+    }
+}
+
+/// 网页内容提取器
+pub trait Extractor {
+    /// 提取指定地址的内容
+    fn extract<'a>(&'a self, url: &'a str) -> ExtractFuture<'a>;
+
+    /// 提取器名称
+    fn name(&self) -> &'static str;
+}
+
+/// 根据配置创建内容提取器，PDF地址始终交给PDF提取器；
+/// 选用模拟翻译引擎时所有地址都使用模拟提取器，不访问网络
+pub fn create_extractor(config: &AppConfig) -> Box<dyn Extractor> {
+    if config.translation_engine == TranslationEngine::Mock {
+        return Box::new(MockExtractor::new());
+    }
+    let page_extractor: Box<dyn Extractor> = match config.extractor.kind {
+        ExtractorKind::Jina => Box::new(JinaService::new(config)),
+        ExtractorKind::Readability => Box::new(ReadabilityExtractor::new(config)),
+    };
+    Box::new(PdfAwareExtractor {
+        pdf: PdfExtractor::new(config),
+        page_extractor,
+    })
+}
+
+/// 按地址选择PDF提取器或网页提取器
+struct PdfAwareExtractor {
+    pdf: PdfExtractor,
+    page_extractor: Box<dyn Extractor>,
+}
+
+impl Extractor for PdfAwareExtractor {
+    fn extract<'a>(&'a self, url: &'a str) -> ExtractFuture<'a> {
+        if is_pdf_url(url) {
+            self.pdf.extract(url)
+        } else {
+            self.page_extractor.extract(url)
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.page_extractor.name()
+    }
+}
+
+/// 提取Markdown中的链接，相对地址按 `base_url` 解析，重复的地址只保留第一次出现
+pub fn extract_markdown_links(markdown: &str, base_url: &str) -> Vec<ExtractedLink> {
+    let base = url::Url::parse(base_url).ok();
+    let mut links: Vec<ExtractedLink> = Vec::new();
+    let mut search_start = 0;
+
+    while let Some(offset) = markdown[search_start..].find('[') {
+        let open = search_start + offset;
+        search_start = open + 1;
+
+        let Some(close) = markdown[open + 1..].find("](").map(|i| open + 1 + i) else {
+            break;
+        };
+        let text = &markdown[open + 1..close];
+        // 文本中还有 [ 或换行时说明当前 [ 不是链接开头，从下一个 [ 继续
+        if text.contains('[') || text.contains('\n') {
+            continue;
+        }
+        let Some(end) = markdown[close + 2..].find(')').map(|i| close + 2 + i) else {
+            break;
+        };
+        search_start = end + 1;
+
+        // 图片不算作链接
+        if markdown[..open].ends_with('!') {
+            continue;
+        }
+
+        let target = markdown[close + 2..end]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_matches(|c| c == '<' || c == '>');
+        if target.is_empty() || target.starts_with('#') || target.starts_with("mailto:") {
+            continue;
+        }
+
+        let resolved = match &base {
+            Some(base) => match base.join(target) {
+                Ok(url) => url.to_string(),
+                Err(_) => continue,
+            },
+            None => target.to_string(),
+        };
+
+        if !links.iter().any(|link| link.url == resolved) {
+            links.push(ExtractedLink {
+                text: text.trim().to_string(),
+                url: resolved,
+            });
+        }
+    }
+
+    links
+}
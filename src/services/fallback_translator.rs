@@ -0,0 +1,163 @@
+use super::translator::{
+    create_engine_translator, engine_chain, split_text_into_chunks, ChunkRecord, TranslateFuture,
+    TranslationOutcome, Translator, TranslatorCapabilities,
+};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, TranslationEngine};
+
+/// 按顺序回退的翻译器
+///
+/// 文本按各引擎中最小的长度限制分块，每块先交给第一个引擎；
+/// 遇到可重试的错误时改用下一个引擎翻译该块，不可重试的错误直接返回。
+pub struct FallbackTranslator {
+    translators: Vec<Box<dyn Translator>>,
+}
+
+impl FallbackTranslator {
+    pub fn new(config: &AppConfig) -> Self {
+        let translators = engine_chain(config)
+            .into_iter()
+            .map(|engine| create_engine_translator(engine, config))
+            .collect();
+        Self::from_translators(translators)
+    }
+
+    /// 使用已创建的翻译器，按给定顺序回退
+    pub fn from_translators(translators: Vec<Box<dyn Translator>>) -> Self {
+        Self { translators }
+    }
+
+    pub async fn translate_chain(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        on_progress: &dyn Fn(&str),
+    ) -> AppResult<TranslationOutcome> {
+        if self.translators.is_empty() {
+            return Err(AppError::config("没有可用的翻译引擎"));
+        }
+
+        let chunks = split_text_into_chunks(text, self.capabilities().max_text_length);
+        let mut translated = String::new();
+        let mut records = Vec::with_capacity(chunks.len());
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            if !translated.is_empty() {
+                translated.push_str("\n\n");
+            }
+
+            let (part, engine) = self
+                .translate_chunk(chunk, source_lang, target_lang, &translated, on_progress)
+                .await?;
+
+            translated.push_str(&part);
+            records.push(ChunkRecord { index, engine });
+            on_progress(&translated);
+        }
+
+        Ok(TranslationOutcome {
+            text: translated,
+            chunks: records,
+        })
+    }
+
+    /// 依次尝试各引擎翻译单个文本块，返回译文和实际完成的引擎
+    async fn translate_chunk(
+        &self,
+        chunk: &str,
+        source_lang: &str,
+        target_lang: &str,
+        prefix: &str,
+        on_progress: &dyn Fn(&str),
+    ) -> AppResult<(String, TranslationEngine)> {
+        let mut failures = Vec::new();
+
+        for (position, translator) in self.translators.iter().enumerate() {
+            let report_progress = |partial: &str| on_progress(&format!("{}{}", prefix, partial));
+            match translator
+                .translate_streaming(chunk, source_lang, target_lang, &report_progress)
+                .await
+            {
+                Ok(part) => return Ok((part, translator.capabilities().engine)),
+                Err(e) if e.is_retryable() && position + 1 < self.translators.len() => {
+                    failures.push(format!("{}: {}", translator.name(), e));
+                }
+                Err(e) if failures.is_empty() => return Err(e),
+                Err(e) => {
+                    failures.push(format!("{}: {}", translator.name(), e));
+                    return Err(AppError::translation(format!(
+                        "所有翻译引擎均失败 - {}",
+                        failures.join("; ")
+                    )));
+                }
+            }
+        }
+
+        Err(AppError::config("没有可用的翻译引擎"))
+    }
+}
+
+impl Translator for FallbackTranslator {
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(async move {
+            let outcome = self
+                .translate_chain(text, source_lang, target_lang, &|_| {})
+                .await?;
+            Ok(outcome.text)
+        })
+    }
+
+    fn translate_streaming<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+        on_progress: &'a dyn Fn(&str),
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(async move {
+            let outcome = self
+                .translate_chain(text, source_lang, target_lang, on_progress)
+                .await?;
+            Ok(outcome.text)
+        })
+    }
+
+    fn translate_with_report<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+        on_progress: &'a dyn Fn(&str),
+    ) -> TranslateFuture<'a, TranslationOutcome> {
+        Box::pin(self.translate_chain(text, source_lang, target_lang, on_progress))
+    }
+
+    /// 以第一个引擎为主，长度限制取各引擎中的最小值
+    fn capabilities(&self) -> TranslatorCapabilities {
+        let mut capabilities = self
+            .translators
+            .first()
+            .map(|translator| translator.capabilities())
+            .unwrap_or_else(|| TranslatorCapabilities {
+                engine: TranslationEngine::default(),
+                max_text_length: usize::MAX,
+                max_requests_per_second: 1,
+                supports_auto_detect: false,
+                supports_streaming: false,
+            });
+
+        for translator in self.translators.iter().skip(1) {
+            let other = translator.capabilities();
+            capabilities.max_text_length = capabilities.max_text_length.min(other.max_text_length);
+            capabilities.supports_streaming |= other.supports_streaming;
+        }
+
+        capabilities
+    }
+}
@@ -446,7 +446,7 @@ mod tests {
         // Test TitleOnly mode
         let mut config = FileNamingConfig::default();
         config.mode = FileNamingMode::TitleOnly;
-        let mut service = FileNamingService::new(config);
+        let mut service = FileNamingService::new(config.clone());
         let result = service.generate_file_name(&context);
         assert_eq!(result.file_name, "getting_started_guide.md");
 
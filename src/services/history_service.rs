@@ -1,3 +1,4 @@
+use crate::services::translator::format_engine_usage;
 use crate::types::history::{
     BatchDocumentInfo, HistoryEntry, HistoryEntryType, HistoryFilter, HistorySortBy,
 };
@@ -27,9 +28,11 @@ impl HistoryService {
 
     pub fn add_entry(&self, entry: HistoryEntry) -> Result<(), Box<dyn std::error::Error>> {
         let mut entries = self.get_all_entries()?;
+        entry.prune_older_runs(&mut entries);
 
-        // 检查是否已存在相同URL的条目
-        if let Some(existing_index) = entries.iter().position(|e| e.url == entry.url) {
+        // 检查是否已存在应被覆盖的条目
+        let existing_index = entries.iter().position(|e| entry.replaces(e));
+        if let Some(existing_index) = existing_index {
             // 更新现有条目
             entries[existing_index] = entry;
         } else {
@@ -53,6 +56,48 @@ impl HistoryService {
         Ok(entries.into_iter().find(|entry| entry.id == id))
     }
 
+    /// 同一索引URL、同一语言对在 `created_before` 之前最近一次的批量翻译记录，
+    /// 任务自己写入的记录不会被当作上一次的结果
+    pub fn find_batch_entry(
+        &self,
+        index_url: &str,
+        source_lang: &str,
+        target_lang: &str,
+        created_before: &str,
+    ) -> Option<HistoryEntry> {
+        self.find_entry(index_url, source_lang, target_lang, |entry| {
+            matches!(entry.entry_type, HistoryEntryType::BatchTranslation)
+                && entry.created_before(created_before)
+        })
+    }
+
+    /// 同一URL、同一语言对最近一次的单页翻译记录
+    pub fn find_page_entry(
+        &self,
+        url: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Option<HistoryEntry> {
+        self.find_entry(url, source_lang, target_lang, |entry| {
+            matches!(entry.entry_type, HistoryEntryType::SinglePage)
+        })
+    }
+
+    fn find_entry(
+        &self,
+        url: &str,
+        source_lang: &str,
+        target_lang: &str,
+        filter: impl Fn(&HistoryEntry) -> bool,
+    ) -> Option<HistoryEntry> {
+        self.get_all_entries().ok()?.into_iter().find(|entry| {
+            filter(entry)
+                && entry.url == url
+                && entry.source_lang == source_lang
+                && entry.target_lang == target_lang
+        })
+    }
+
     pub fn delete_entry(&self, id: &str) -> Result<(), Box<dyn std::error::Error>> {
         let mut entries = self.get_all_entries()?;
         entries.retain(|entry| entry.id != id);
@@ -189,7 +234,7 @@ impl HistoryService {
                     md.push_str(&format!(
                         "## {}\n\n**URL**: {}\n\n**语言**: {} -> {}\n\n**创建时间**: {}\n\n**字数**: {}\n\n---\n\n",
                         entry.title,
-                        entry.url,
+                        entry.source_label(),
                         entry.source_lang,
                         entry.target_lang,
                         entry.get_formatted_date(),
@@ -209,12 +254,16 @@ impl HistoryService {
             .ok_or("未找到指定记录".to_string())?;
 
         match entry.entry_type {
-            HistoryEntryType::SinglePage => {
+            HistoryEntryType::SinglePage | HistoryEntryType::PastedText => {
                 let mut content = String::new();
 
                 // 添加文档头部信息
                 content.push_str(&format!("# {}\n\n", entry.title));
-                content.push_str(&format!("> **原始URL**: {}\n", entry.url));
+                if matches!(entry.entry_type, HistoryEntryType::PastedText) {
+                    content.push_str("> **来源**: 粘贴文本\n");
+                } else {
+                    content.push_str(&format!("> **原始URL**: {}\n", entry.url));
+                }
                 content.push_str(&format!("> **翻译时间**: {}\n", entry.get_formatted_date()));
                 content.push_str(&format!(
                     "> **语言**: {} -> {}\n",
@@ -274,7 +323,9 @@ impl HistoryService {
                 // 创建tar.gz归档
                 self.create_batch_archive(&entry, &docs_to_download)
             }
-            HistoryEntryType::SinglePage => Err("该记录是单页翻译，请使用单页下载功能".to_string()),
+            HistoryEntryType::SinglePage | HistoryEntryType::PastedText => {
+                Err("该记录是单页翻译，请使用单页下载功能".to_string())
+            }
         }
     }
 
@@ -374,6 +425,18 @@ impl HistoryService {
             content.push_str(&format!("- 失败文档: {}\n\n", batch_data.failed_documents));
         }
 
+        if !entry.engine_usage.is_empty() {
+            content.push_str("**翻译引擎统计**:\n");
+            for usage in &entry.engine_usage {
+                content.push_str(&format!(
+                    "- {}: {} 块\n",
+                    usage.engine.display_name(),
+                    usage.chunks
+                ));
+            }
+            content.push('\n');
+        }
+
         content.push_str("## 文档目录\n\n");
 
         // 按文件夹分组显示目录
@@ -398,7 +461,7 @@ impl HistoryService {
                 };
 
                 content.push_str(&format!(
-                    "- [{}]({})\n  - 原始URL: {}\n  - 文件路径: {}/{}\n\n",
+                    "- [{}]({})\n  - 原始URL: {}\n  - 文件路径: {}/{}\n",
                     doc.title,
                     file_name,
                     doc.url,
@@ -409,6 +472,13 @@ impl HistoryService {
                     },
                     file_name
                 ));
+                if !doc.engine_usage.is_empty() {
+                    content.push_str(&format!(
+                        "  - 翻译引擎: {}\n",
+                        format_engine_usage(&doc.engine_usage)
+                    ));
+                }
+                content.push('\n');
             }
         }
 
@@ -0,0 +1,412 @@
+use super::batch_service::{DocumentLink, TranslatedDocument};
+use super::content_processor::ContentProcessor;
+use super::translator::{ChunkRecord, TranslationOutcome, Translator};
+use crate::error::AppResult;
+use crate::types::batch_job::BatchJob;
+use crate::types::history::{BatchDocumentInfo, HistoryEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// 与上一次批量翻译相比，文档的变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DocumentChange {
+    /// 上次没有翻译过的文档
+    #[default]
+    Added,
+    /// 原文内容有变化，已重新翻译
+    Modified,
+    /// 原文内容未变，沿用上次的译文
+    Unchanged,
+    /// 上次翻译过、这次目录中已不存在
+    Removed,
+}
+
+impl DocumentChange {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocumentChange::Added => "新增",
+            DocumentChange::Modified => "更新",
+            DocumentChange::Unchanged => "未变",
+            DocumentChange::Removed => "移除",
+        }
+    }
+}
+
+/// 计算原文内容的哈希（FNV-1a 64位），忽略首尾空白；结果在不同版本间保持稳定
+pub fn content_hash(content: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = content.trim().bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+/// 上一次批量翻译的结果，按原始URL索引
+#[derive(Debug, Clone, Default)]
+pub struct PreviousTranslations {
+    translated_at: String,
+    documents: HashMap<String, BatchDocumentInfo>,
+}
+
+impl PreviousTranslations {
+    /// 从批量翻译历史记录构建，不是批量翻译记录时返回 None
+    pub fn from_history(entry: &HistoryEntry) -> Option<Self> {
+        let batch_data = entry.batch_data.as_ref()?;
+        let documents = batch_data
+            .document_list
+            .iter()
+            .filter(|doc| doc.translated)
+            .map(|doc| {
+                let mut doc = doc.clone();
+                // 旧记录没有保存哈希，用保存的原文补算
+                if doc.content_hash.is_empty() {
+                    doc.content_hash = content_hash(&doc.original_content);
+                }
+                (doc.url.clone(), doc)
+            })
+            .collect();
+        Some(Self {
+            translated_at: entry.created_at.clone(),
+            documents,
+        })
+    }
+
+    /// 任务开始前的同一站点、同一语言对的翻译结果。
+    /// 任务自己中途写入的记录（例如重试失败文档前）不作为比较基准
+    pub fn for_job(entry: &HistoryEntry, job: &BatchJob) -> Option<Self> {
+        if entry.url != job.index_url
+            || entry.source_lang != job.source_lang
+            || entry.target_lang != job.target_lang
+        {
+            return None;
+        }
+        if !entry.created_before(&job.created_at) {
+            return None;
+        }
+        Self::from_history(entry).filter(|previous| !previous.is_empty())
+    }
+
+    pub fn translated_at(&self) -> &str {
+        &self.translated_at
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// 上次翻译的同一URL的文档
+    pub fn document(&self, url: &str) -> Option<&BatchDocumentInfo> {
+        self.documents.get(url)
+    }
+
+    /// 原文哈希相同时返回可直接沿用的上次译文
+    pub fn reusable(&self, url: &str, hash: &str) -> Option<&BatchDocumentInfo> {
+        self.documents
+            .get(url)
+            .filter(|doc| doc.content_hash == hash)
+    }
+
+    /// 判断文档相对上次是新增还是更新
+    pub fn classify(&self, url: &str, hash: &str) -> DocumentChange {
+        match self.documents.get(url) {
+            None => DocumentChange::Added,
+            Some(doc) if doc.content_hash == hash => DocumentChange::Unchanged,
+            Some(_) => DocumentChange::Modified,
+        }
+    }
+
+    /// 上次翻译过、这次目录中已不存在的文档，按上次的顺序排列
+    pub fn removed(&self, links: &[DocumentLink]) -> Vec<&BatchDocumentInfo> {
+        let current: HashSet<&str> = links.iter().map(|link| link.url.as_str()).collect();
+        let mut removed: Vec<&BatchDocumentInfo> = self
+            .documents
+            .values()
+            .filter(|doc| !current.contains(doc.url.as_str()))
+            .collect();
+        removed.sort_by_key(|doc| doc.order);
+        removed
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub title: String,
+    pub url: String,
+    pub change: DocumentChange,
+}
+
+/// 相对上一次批量翻译的变更记录
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Changelog {
+    /// 上一次翻译的时间
+    pub previous_translated_at: String,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+impl Changelog {
+    /// 根据本次翻译完成的文档和目录生成变更记录
+    pub fn build(
+        previous: &PreviousTranslations,
+        links: &[DocumentLink],
+        documents: &[TranslatedDocument],
+    ) -> Self {
+        let mut entries: Vec<ChangelogEntry> = documents
+            .iter()
+            .map(|doc| ChangelogEntry {
+                title: doc.link.title.clone(),
+                url: doc.link.url.clone(),
+                change: doc.change,
+            })
+            .collect();
+        entries.extend(
+            previous
+                .removed(links)
+                .into_iter()
+                .map(|doc| ChangelogEntry {
+                    title: doc.title.clone(),
+                    url: doc.url.clone(),
+                    change: DocumentChange::Removed,
+                }),
+        );
+
+        Self {
+            previous_translated_at: previous.translated_at().to_string(),
+            entries,
+        }
+    }
+
+    pub fn count(&self, change: DocumentChange) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.change == change)
+            .count()
+    }
+
+    /// 一行摘要，例如 "新增 2, 更新 1, 未变 10, 移除 0"
+    pub fn summary(&self) -> String {
+        [
+            DocumentChange::Added,
+            DocumentChange::Modified,
+            DocumentChange::Unchanged,
+            DocumentChange::Removed,
+        ]
+        .iter()
+        .map(|change| format!("{} {}", change.label(), self.count(*change)))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// 生成 CHANGELOG.md 内容，未变的文档只计数不逐条列出
+    pub fn to_markdown(&self) -> String {
+        let mut content = String::from("# 变更记录\n\n");
+        content.push_str(&format!(
+            "与 {} 的翻译相比: {}\n\n",
+            self.previous_translated_at,
+            self.summary()
+        ));
+
+        for change in [
+            DocumentChange::Added,
+            DocumentChange::Modified,
+            DocumentChange::Removed,
+        ] {
+            let entries: Vec<&ChangelogEntry> = self
+                .entries
+                .iter()
+                .filter(|entry| entry.change == change)
+                .collect();
+            if entries.is_empty() {
+                continue;
+            }
+            content.push_str(&format!("## {} ({} 个)\n\n", change.label(), entries.len()));
+            for entry in entries {
+                content.push_str(&format!("- [{}]({})\n", entry.title, entry.url));
+            }
+            content.push('\n');
+        }
+
+        content
+    }
+}
+
+/// 新原文相对上次原文的段落差异，未变的段落沿用上次的译文
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParagraphDiff {
+    paragraphs: Vec<String>,
+    /// 与新原文段落一一对应，需要重新翻译的段落为 None
+    reused: Vec<Option<String>>,
+}
+
+impl ParagraphDiff {
+    /// 按段落对齐新旧原文。上次的译文与原文段落数不一致时无法逐段对应，返回 None
+    pub fn new(source: &str, previous_source: &str, previous_translation: &str) -> Option<Self> {
+        let previous_paragraphs = split_source_paragraphs(previous_source);
+        let previous_translations = split_source_paragraphs(previous_translation);
+        if previous_paragraphs.is_empty()
+            || previous_paragraphs.len() != previous_translations.len()
+        {
+            return None;
+        }
+
+        let paragraphs = split_source_paragraphs(source);
+        let reused = match_paragraphs(&previous_paragraphs, &paragraphs)
+            .into_iter()
+            .map(|matched| matched.map(|index| previous_translations[index].clone()))
+            .collect();
+        Some(Self { paragraphs, reused })
+    }
+
+    /// 由已查到的段落译文构建，例如来自翻译记忆；两个列表需一一对应
+    pub fn from_lookup(paragraphs: Vec<String>, reused: Vec<Option<String>>) -> Self {
+        debug_assert_eq!(paragraphs.len(), reused.len());
+        Self { paragraphs, reused }
+    }
+
+    pub fn paragraph_count(&self) -> usize {
+        self.paragraphs.len()
+    }
+
+    pub fn reused_count(&self) -> usize {
+        self.reused.iter().filter(|reused| reused.is_some()).count()
+    }
+
+    pub fn changed_count(&self) -> usize {
+        self.paragraph_count() - self.reused_count()
+    }
+
+    /// 需要翻译的文本，连续变化的段落合并为一段以保留上下文并减少请求次数
+    pub fn changed_segments(&self) -> Vec<String> {
+        self.changed_runs()
+            .into_iter()
+            .map(|run| self.paragraphs[run].join("\n\n"))
+            .collect()
+    }
+
+    /// 按顺序填入 `changed_segments` 的译文，拼出完整译文
+    pub fn assemble(&self, translated_segments: &[String]) -> String {
+        let mut segments = translated_segments.iter();
+        let mut parts: Vec<&str> = Vec::new();
+        let mut index = 0;
+        while index < self.reused.len() {
+            match &self.reused[index] {
+                Some(translation) => {
+                    parts.push(translation);
+                    index += 1;
+                }
+                None => {
+                    if let Some(segment) = segments.next() {
+                        parts.push(segment.trim());
+                    }
+                    while index < self.reused.len() && self.reused[index].is_none() {
+                        index += 1;
+                    }
+                }
+            }
+        }
+        parts.join("\n\n")
+    }
+
+    fn changed_runs(&self) -> Vec<Range<usize>> {
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        for (index, reused) in self.reused.iter().enumerate() {
+            if reused.is_some() {
+                continue;
+            }
+            match runs.last_mut() {
+                Some(run) if run.end == index => run.end = index + 1,
+                _ => runs.push(index..index + 1),
+            }
+        }
+        runs
+    }
+}
+
+/// 只翻译变化的段落，其余段落沿用上次的译文
+pub async fn translate_changed_paragraphs(
+    diff: &ParagraphDiff,
+    translator: &dyn Translator,
+    source_lang: &str,
+    target_lang: &str,
+) -> AppResult<TranslationOutcome> {
+    let mut translated_segments = Vec::new();
+    let mut chunks: Vec<ChunkRecord> = Vec::new();
+
+    for segment in diff.changed_segments() {
+        let mut content_processor = ContentProcessor::new();
+        let protected = content_processor.protect_code_blocks(&segment);
+        let outcome = translator
+            .translate_with_report(&protected, source_lang, target_lang, &|_| {})
+            .await?;
+
+        let offset = chunks.len();
+        chunks.extend(outcome.chunks.iter().map(|chunk| ChunkRecord {
+            index: offset + chunk.index,
+            engine: chunk.engine,
+        }));
+        translated_segments.push(content_processor.restore_code_blocks(&outcome.text));
+    }
+
+    Ok(TranslationOutcome {
+        text: diff.assemble(&translated_segments),
+        chunks,
+    })
+}
+
+/// 按空行分段，代码块内部的空行不拆分
+pub fn split_source_paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if trimmed.is_empty() && !in_fence {
+            if !current.is_empty() {
+                paragraphs.push(current.join("\n"));
+                current.clear();
+            }
+            continue;
+        }
+        current.push(line);
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join("\n"));
+    }
+
+    paragraphs
+}
+
+/// 最长公共子序列对齐：返回每个新段落对应的旧段落下标，没有对应的为 None
+fn match_paragraphs(previous: &[String], current: &[String]) -> Vec<Option<usize>> {
+    let (rows, cols) = (previous.len(), current.len());
+    let mut lengths = vec![vec![0u32; cols + 1]; rows + 1];
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lengths[i][j] = if previous[i] == current[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = vec![None; cols];
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < cols {
+        if previous[i] == current[j] {
+            matches[j] = Some(i);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
@@ -0,0 +1,827 @@
+use super::batch_service::{DocumentLink, MAX_LEVEL};
+use crate::error::{AppError, AppResult};
+use scraper::{Html, Selector};
+use std::collections::HashSet;
+use url::Url;
+
+/// 文档站点生成器的目录格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFormat {
+    /// mdBook 的 SUMMARY.md
+    MdBook,
+    /// GitBook 的 SUMMARY.md
+    GitBook,
+    /// Docusaurus 的 sidebars.js / sidebars.json
+    Docusaurus,
+    /// MkDocs 的 mkdocs.yml 中的 nav
+    MkDocs,
+    /// Sphinx 的 toctree，支持 .rst 源文件和生成的HTML页面
+    Sphinx,
+}
+
+impl IndexFormat {
+    /// 根据索引地址识别目录格式，无法识别时使用通用的链接解析
+    pub fn detect(url: &str) -> Option<Self> {
+        let parsed = Url::parse(url).ok()?;
+        let path = parsed.path().to_lowercase();
+        let file_name = path.rsplit('/').next().unwrap_or_default();
+        let host = parsed.host_str().unwrap_or_default().to_lowercase();
+
+        if file_name == "summary.md" {
+            return Some(if url.to_lowercase().contains("gitbook") {
+                IndexFormat::GitBook
+            } else {
+                IndexFormat::MdBook
+            });
+        }
+        if file_name.starts_with("sidebars.")
+            && [".js", ".cjs", ".mjs", ".ts", ".json"]
+                .iter()
+                .any(|ext| file_name.ends_with(ext))
+        {
+            return Some(IndexFormat::Docusaurus);
+        }
+        if file_name == "mkdocs.yml" || file_name == "mkdocs.yaml" {
+            return Some(IndexFormat::MkDocs);
+        }
+        if file_name.ends_with(".rst")
+            || file_name.ends_with(".rst.txt")
+            || host.ends_with(".readthedocs.io")
+        {
+            return Some(IndexFormat::Sphinx);
+        }
+        None
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            IndexFormat::MdBook => "mdBook",
+            IndexFormat::GitBook => "GitBook",
+            IndexFormat::Docusaurus => "Docusaurus",
+            IndexFormat::MkDocs => "MkDocs",
+            IndexFormat::Sphinx => "Sphinx",
+        }
+    }
+
+    /// 按格式解析索引文件内容
+    pub fn parse(&self, content: &str, index_url: &str) -> AppResult<Vec<DocumentLink>> {
+        let base = Url::parse(index_url)
+            .map_err(|e| AppError::validation("URL", format!("无效的索引地址: {}", e)))?;
+        let links = match self {
+            IndexFormat::MdBook | IndexFormat::GitBook => parse_summary_md(content, &base),
+            IndexFormat::Docusaurus => parse_docusaurus_sidebars(content, &base)?,
+            IndexFormat::MkDocs => parse_mkdocs_nav(content, &base),
+            IndexFormat::Sphinx if content.contains("toctree-l1") => {
+                parse_sphinx_html(content, &base)
+            }
+            IndexFormat::Sphinx => parse_sphinx_toctree(content, &base),
+        };
+
+        if links.is_empty() {
+            return Err(AppError::parse(format!(
+                "未能从 {} 目录中找到文档链接",
+                self.name()
+            )));
+        }
+        Ok(links)
+    }
+}
+
+/// GitHub 页面地址转换为原始文件地址，以便获取源文件并解析相对链接
+pub fn raw_source_url(url: &str) -> String {
+    let Ok(parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    if parsed.host_str() != Some("github.com") {
+        return url.to_string();
+    }
+    let segments: Vec<&str> = parsed.path().trim_start_matches('/').split('/').collect();
+    match segments.as_slice() {
+        [owner, repo, "blob", rest @ ..] if !rest.is_empty() => format!(
+            "https://raw.githubusercontent.com/{}/{}/{}",
+            owner,
+            repo,
+            rest.join("/")
+        ),
+        _ => url.to_string(),
+    }
+}
+
+/// 由文件名或文档ID生成标题，如 getting-started.md -> Getting started
+pub fn title_from_slug(slug: &str) -> String {
+    let last = slug
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(slug);
+    let stem = last
+        .rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or(last);
+    let words = stem.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => last.to_string(),
+    }
+}
+
+/// 按出现顺序收集链接，重复的地址只保留第一次出现的位置
+#[derive(Default)]
+struct LinkCollector {
+    links: Vec<DocumentLink>,
+    seen: HashSet<String>,
+}
+
+impl LinkCollector {
+    fn push(&mut self, title: &str, url: Url, level: usize) {
+        let mut url = url;
+        url.set_fragment(None);
+        if !self.seen.insert(url.to_string()) {
+            return;
+        }
+        self.links.push(DocumentLink {
+            title: title.trim().to_string(),
+            url: url.to_string(),
+            level: level.min(MAX_LEVEL),
+            order: self.links.len(),
+        });
+    }
+}
+
+/// 由缩进列宽计算层级：更深的缩进进入下一级，回退时找到对应的上级
+fn indent_level(stack: &mut Vec<usize>, indent: usize) -> usize {
+    while stack.last().map(|last| *last > indent).unwrap_or(false) {
+        stack.pop();
+    }
+    if stack.last() != Some(&indent) {
+        stack.push(indent);
+    }
+    stack.len() - 1
+}
+
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// 解析 mdBook / GitBook 的 SUMMARY.md：
+/// 列表缩进表示章节层级，部分标题和分隔线不是文档，草稿章节（空链接）跳过
+pub fn parse_summary_md(content: &str, base: &Url) -> Vec<DocumentLink> {
+    let mut collector = LinkCollector::default();
+    let mut stack = Vec::new();
+    let mut in_code = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let (item, is_list) = match trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "))
+        {
+            Some(item) => (item.trim(), true),
+            None => (trimmed, false),
+        };
+        let Some((title, target)) = markdown_link(item) else {
+            continue;
+        };
+        if target.is_empty() || is_external(target) {
+            continue;
+        }
+        let Ok(url) = base.join(target) else {
+            continue;
+        };
+
+        // 前言和后记章节不在列表中，位于顶层
+        let level = if is_list {
+            indent_level(&mut stack, indent_width(line))
+        } else {
+            stack.clear();
+            0
+        };
+        collector.push(&unescape_markdown(title), url, level);
+    }
+
+    collector.links
+}
+
+/// 取出行首的 Markdown 链接 [标题](地址)
+fn markdown_link(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix('[')?;
+    let mut depth = 1;
+    let mut title_end = None;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    title_end = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let title_end = title_end?;
+    let after = rest[title_end + 1..].strip_prefix('(')?;
+    let target_end = after.find(')')?;
+    let target = after[..target_end]
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_matches(|c| c == '<' || c == '>');
+    Some((&rest[..title_end], target))
+}
+
+fn unescape_markdown(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                result.push(next);
+            }
+        } else if c != '`' {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn is_external(target: &str) -> bool {
+    target.contains("://") || target.starts_with("mailto:") || target.starts_with('#')
+}
+
+/// JavaScript 对象字面量的值，保留对象键的书写顺序
+#[derive(Debug, Clone, PartialEq)]
+enum JsValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsValue>),
+    Object(Vec<(String, JsValue)>),
+    /// 变量引用、函数调用等无法静态求值的表达式
+    Other,
+}
+
+impl JsValue {
+    fn get(&self, key: &str) -> Option<&JsValue> {
+        match self {
+            JsValue::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            JsValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// 宽松的 JS/JSON 字面量解析器，支持注释、单引号、未加引号的键和尾随逗号
+struct JsParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> JsParser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+        }
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.chars.next();
+                }
+                Some('/') => {
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+                    match lookahead.peek() {
+                        Some('/') => {
+                            for c in self.chars.by_ref() {
+                                if c == '\n' {
+                                    break;
+                                }
+                            }
+                        }
+                        Some('*') => {
+                            self.chars.next();
+                            self.chars.next();
+                            let mut previous = ' ';
+                            for c in self.chars.by_ref() {
+                                if previous == '*' && c == '/' {
+                                    break;
+                                }
+                                previous = c;
+                            }
+                        }
+                        _ => return,
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// 跳过注释、字符串和其他语句，解析第一个赋值或导出的对象字面量。
+    /// 只接受位于文件开头、`=`、`default` 或 `(` 之后的 `{`，
+    /// 以免把 `import type {SidebarsConfig} from ...` 之类的语句当作对象
+    fn parse_first_object(&mut self) -> AppResult<JsValue> {
+        let mut previous = String::new();
+        loop {
+            self.skip_trivia();
+            match self.chars.peek().copied() {
+                Some('{') if matches!(previous.as_str(), "" | "=" | "default" | "(") => {
+                    return self.parse_object();
+                }
+                Some(quote @ ('"' | '\'' | '`')) => {
+                    self.chars.next();
+                    self.parse_string(quote)?;
+                    previous = quote.to_string();
+                }
+                Some(c) if c.is_alphanumeric() || c == '_' || c == '$' => {
+                    previous.clear();
+                    while let Some(c) = self.chars.peek().copied() {
+                        if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                            break;
+                        }
+                        previous.push(c);
+                        self.chars.next();
+                    }
+                }
+                Some(c) => {
+                    self.chars.next();
+                    previous = c.to_string();
+                }
+                None => return Err(js_error("未找到侧边栏对象")),
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> AppResult<JsValue> {
+        self.skip_trivia();
+        match self.chars.peek().copied() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some(quote @ ('"' | '\'' | '`')) => {
+                self.chars.next();
+                Ok(JsValue::String(self.parse_string(quote)?))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let mut number = String::new();
+                while let Some(c) = self.chars.peek().copied() {
+                    if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+' {
+                        number.push(c);
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                Ok(JsValue::Number(number))
+            }
+            Some(c) => {
+                let word = self.parse_expression();
+                if word.is_empty() {
+                    // 没有消耗任何字符，例如数组中多余的 `}`，继续解析会陷入死循环
+                    return Err(js_error(format!("意外的字符 '{}'", c)));
+                }
+                Ok(match word.as_str() {
+                    "true" => JsValue::Bool(true),
+                    "false" => JsValue::Bool(false),
+                    "null" | "undefined" => JsValue::Null,
+                    _ => JsValue::Other,
+                })
+            }
+            None => Err(js_error("内容意外结束")),
+        }
+    }
+
+    /// 跳过一个无法求值的表达式，直到同层的逗号或右括号；字符串中的括号和逗号不计入
+    fn parse_expression(&mut self) -> String {
+        let mut text = String::new();
+        let mut depth = 0usize;
+        while let Some(c) = self.chars.peek().copied() {
+            match c {
+                '"' | '\'' | '`' => {
+                    self.chars.next();
+                    let value = self.parse_string(c).unwrap_or_default();
+                    text.push(c);
+                    text.push_str(&value);
+                    text.push(c);
+                    continue;
+                }
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' if depth == 0 => break,
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => break,
+                _ => {}
+            }
+            text.push(c);
+            self.chars.next();
+        }
+        text.trim().to_string()
+    }
+
+    fn parse_string(&mut self, quote: char) -> AppResult<String> {
+        let mut value = String::new();
+        while let Some(c) = self.chars.next() {
+            match c {
+                '\\' => match self.chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(other) => value.push(other),
+                    None => break,
+                },
+                c if c == quote => return Ok(value),
+                c => value.push(c),
+            }
+        }
+        Err(js_error("字符串没有结束"))
+    }
+
+    fn parse_key(&mut self) -> AppResult<String> {
+        self.skip_trivia();
+        match self.chars.peek().copied() {
+            Some(quote @ ('"' | '\'' | '`')) => {
+                self.chars.next();
+                self.parse_string(quote)
+            }
+            Some('[') => {
+                // 计算属性名无法静态求值
+                self.chars.next();
+                let key = self.parse_expression();
+                self.expect(']')?;
+                Ok(key)
+            }
+            _ => {
+                let mut key = String::new();
+                while let Some(c) = self.chars.peek().copied() {
+                    if c.is_alphanumeric() || c == '_' || c == '$' {
+                        key.push(c);
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if key.is_empty() {
+                    return Err(js_error("缺少对象键"));
+                }
+                Ok(key)
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> AppResult<JsValue> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+        loop {
+            self.skip_trivia();
+            match self.chars.peek() {
+                Some('}') => {
+                    self.chars.next();
+                    return Ok(JsValue::Object(entries));
+                }
+                Some('.') => {
+                    // 展开语法 ...other
+                    self.parse_expression();
+                }
+                _ => {
+                    let key = self.parse_key()?;
+                    self.skip_trivia();
+                    let value = if self.chars.peek() == Some(&':') {
+                        self.chars.next();
+                        self.parse_value()?
+                    } else {
+                        // 简写属性 { docs }
+                        JsValue::Other
+                    };
+                    entries.push((key, value));
+                }
+            }
+            self.skip_trivia();
+            if self.chars.peek() == Some(&',') {
+                self.chars.next();
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> AppResult<JsValue> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia();
+            match self.chars.peek() {
+                Some(']') => {
+                    self.chars.next();
+                    return Ok(JsValue::Array(items));
+                }
+                Some('.') => {
+                    self.parse_expression();
+                }
+                _ => items.push(self.parse_value()?),
+            }
+            self.skip_trivia();
+            if self.chars.peek() == Some(&',') {
+                self.chars.next();
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> AppResult<()> {
+        self.skip_trivia();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(js_error(format!("期望 '{}'，实际为 '{}'", expected, c))),
+            None => Err(js_error("内容意外结束")),
+        }
+    }
+}
+
+fn js_error(message: impl Into<String>) -> AppError {
+    AppError::parse(format!("侧边栏配置格式错误: {}", message.into()))
+}
+
+/// 解析 Docusaurus 的侧边栏配置。
+/// 文档ID对应仓库中 docs/ 目录下的 Markdown 源文件，相对于侧边栏文件所在目录
+pub fn parse_docusaurus_sidebars(content: &str, base: &Url) -> AppResult<Vec<DocumentLink>> {
+    // 跳过注释和 module.exports = / export default / const sidebars: Type = 等前缀
+    let value = JsParser::new(content).parse_first_object()?;
+    let JsValue::Object(sidebars) = value else {
+        return Err(js_error("侧边栏配置应为对象"));
+    };
+
+    let docs_base = base
+        .join("docs/")
+        .map_err(|e| js_error(format!("无法确定文档目录: {}", e)))?;
+    let mut collector = LinkCollector::default();
+    for (_, sidebar) in &sidebars {
+        collect_sidebar_items(sidebar, 0, &docs_base, &mut collector);
+    }
+    Ok(collector.links)
+}
+
+fn collect_sidebar_items(
+    value: &JsValue,
+    level: usize,
+    docs_base: &Url,
+    collector: &mut LinkCollector,
+) {
+    match value {
+        JsValue::Array(items) => {
+            for item in items {
+                collect_sidebar_item(item, level, docs_base, collector);
+            }
+        }
+        // 简写形式 { "分类名": [条目] }
+        JsValue::Object(categories) => {
+            for (_, items) in categories {
+                collect_sidebar_items(items, level, docs_base, collector);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_sidebar_item(
+    item: &JsValue,
+    level: usize,
+    docs_base: &Url,
+    collector: &mut LinkCollector,
+) {
+    let push_doc = |collector: &mut LinkCollector, id: &str, label: Option<&str>, level| {
+        if let Ok(url) = docs_base.join(&format!("{}.md", id.trim_start_matches('/'))) {
+            let title = label
+                .map(str::to_string)
+                .unwrap_or_else(|| title_from_slug(id));
+            collector.push(&title, url, level);
+        }
+    };
+
+    match item {
+        JsValue::String(id) => push_doc(collector, id, None, level),
+        JsValue::Object(_) => match item.get("type").and_then(JsValue::as_str) {
+            Some("doc") | Some("ref") => {
+                if let Some(id) = item.get("id").and_then(JsValue::as_str) {
+                    push_doc(
+                        collector,
+                        id,
+                        item.get("label").and_then(JsValue::as_str),
+                        level,
+                    );
+                }
+            }
+            Some("category") => {
+                let label = item.get("label").and_then(JsValue::as_str);
+                // 分类本身可以链接到一篇文档
+                if let Some(link) = item.get("link") {
+                    if link.get("type").and_then(JsValue::as_str) == Some("doc") {
+                        if let Some(id) = link.get("id").and_then(JsValue::as_str) {
+                            push_doc(collector, id, label, level);
+                        }
+                    }
+                }
+                if let Some(items) = item.get("items") {
+                    collect_sidebar_items(items, level + 1, docs_base, collector);
+                }
+            }
+            // 外部链接、自动生成目录和HTML片段无法映射到源文件
+            Some(_) => {}
+            None => collect_sidebar_items(item, level, docs_base, collector),
+        },
+        _ => {}
+    }
+}
+
+/// 解析 mkdocs.yml 中的 nav，文档路径相对于 docs_dir（默认 docs）
+pub fn parse_mkdocs_nav(content: &str, base: &Url) -> Vec<DocumentLink> {
+    let docs_dir = content
+        .lines()
+        .find_map(|line| line.strip_prefix("docs_dir:"))
+        .map(|value| yaml_scalar(value).trim_end_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "docs".to_string());
+    let Ok(docs_base) = base.join(&format!("{}/", docs_dir)) else {
+        return Vec::new();
+    };
+
+    let mut collector = LinkCollector::default();
+    let mut stack = Vec::new();
+    let mut in_nav = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = indent_width(line);
+        if indent == 0 && !trimmed.starts_with('-') {
+            in_nav = trimmed == "nav:";
+            continue;
+        }
+        if !in_nav {
+            continue;
+        }
+        let Some(item) = trimmed.strip_prefix('-') else {
+            continue;
+        };
+        let level = indent_level(&mut stack, indent);
+        let item = item.trim();
+
+        let (title, target) = match split_yaml_pair(item) {
+            Some((title, value)) => (Some(yaml_scalar(title)), yaml_scalar(value)),
+            None => (None, yaml_scalar(item)),
+        };
+        // 只有标题没有路径的是分组
+        if target.is_empty() || is_external(&target) {
+            continue;
+        }
+        if let Ok(url) = docs_base.join(&target) {
+            let title = title.unwrap_or_else(|| title_from_slug(&target));
+            collector.push(&title, url, level);
+        }
+    }
+
+    collector.links
+}
+
+/// 拆分 `键: 值`，忽略引号内的冒号
+fn split_yaml_pair(item: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (i, c) in item.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, ':') if item[i + 1..].is_empty() || item[i + 1..].starts_with(' ') => {
+                return Some((&item[..i], &item[i + 1..]));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn yaml_scalar(value: &str) -> String {
+    let value = value.trim();
+    let value = match value.find(" #") {
+        Some(pos) if !value.starts_with(['"', '\'']) => value[..pos].trim(),
+        _ => value,
+    };
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// 解析 reStructuredText 中的 toctree 指令。
+/// 只读取当前文件，子文档中的 toctree 需要分别获取后展开
+pub fn parse_sphinx_toctree(content: &str, base: &Url) -> Vec<DocumentLink> {
+    let suffix = if base.path().ends_with(".rst.txt") {
+        ".rst.txt"
+    } else {
+        ".rst"
+    };
+    let mut collector = LinkCollector::default();
+    let mut lines = content.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if line.trim() != ".. toctree::" {
+            continue;
+        }
+        let directive_indent = indent_width(line);
+
+        while let Some(&next) = lines.peek() {
+            let trimmed = next.trim();
+            if !trimmed.is_empty() && indent_width(next) <= directive_indent {
+                break;
+            }
+            lines.next();
+            // 指令选项和空行
+            if trimmed.is_empty() || trimmed.starts_with(':') {
+                continue;
+            }
+
+            let (title, target) = match trimmed.strip_suffix('>').and_then(|t| t.rsplit_once('<')) {
+                Some((title, target)) => (Some(title.trim()), target.trim()),
+                None => (None, trimmed),
+            };
+            if target == "self" || target.contains('*') || is_external(target) {
+                continue;
+            }
+            let document = target.trim_start_matches('/');
+            let path = if document.ends_with(".rst") {
+                document.to_string()
+            } else {
+                format!("{}{}", document, suffix)
+            };
+            if let Ok(url) = base.join(&path) {
+                let title = title
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| title_from_slug(document));
+                collector.push(&title, url, 0);
+            }
+        }
+    }
+
+    collector.links
+}
+
+/// 解析 Sphinx 生成页面中的目录（toctree-l1、toctree-l2 …），跳过页内锚点
+pub fn parse_sphinx_html(html: &str, base: &Url) -> Vec<DocumentLink> {
+    let document = Html::parse_document(html);
+    let Ok(items) = Selector::parse(r#"li[class*="toctree-l"]"#) else {
+        return Vec::new();
+    };
+    let Ok(anchor) = Selector::parse("a") else {
+        return Vec::new();
+    };
+
+    let mut collector = LinkCollector::default();
+    for item in document.select(&items) {
+        let Some(level) = item.value().classes().find_map(|class| {
+            class
+                .strip_prefix("toctree-l")
+                .and_then(|n| n.parse::<usize>().ok())
+        }) else {
+            continue;
+        };
+        let Some(link) = item.select(&anchor).next() else {
+            continue;
+        };
+        let Some(href) = link.value().attr("href") else {
+            continue;
+        };
+        if href.contains('#') {
+            continue;
+        }
+        let Ok(url) = base.join(href) else {
+            continue;
+        };
+        if url.host_str() == base.host_str() {
+            let title = link.text().collect::<String>();
+            collector.push(&title, url, level.saturating_sub(1));
+        }
+    }
+
+    collector.links
+}
@@ -0,0 +1,128 @@
+use super::platform::log;
+use crate::error::{AppError, AppResult};
+use futures::channel::oneshot;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+const DB_NAME: &str = "url_translator";
+const DB_VERSION: u32 = 2;
+
+/// 翻译记忆库的对象仓库
+pub const TRANSLATION_MEMORY_STORE: &str = "translation_memory";
+/// 批量任务断点的对象仓库
+pub const BATCH_JOBS_STORE: &str = "batch_jobs";
+
+/// 数据库中的所有对象仓库，升级版本时创建缺少的仓库
+const STORES: &[&str] = &[TRANSLATION_MEMORY_STORE, BATCH_JOBS_STORE];
+
+/// 应用在浏览器 IndexedDB 中的本地数据库，容量远大于 localStorage，用于保存体积较大的数据
+#[derive(Clone)]
+pub struct LocalDatabase {
+    db: IdbDatabase,
+}
+
+impl LocalDatabase {
+    pub async fn open() -> AppResult<Self> {
+        let factory = web_sys::window()
+            .ok_or_else(|| AppError::file("无法获取window对象"))?
+            .indexed_db()
+            .map_err(storage_error)?
+            .ok_or_else(|| AppError::file("浏览器不支持 IndexedDB"))?;
+        let request = factory
+            .open_with_u32(DB_NAME, DB_VERSION)
+            .map_err(storage_error)?;
+
+        // 首次打开或版本升级时创建对象仓库
+        let upgrade_request = request.clone();
+        let on_upgrade = Closure::<dyn FnMut()>::new(move || {
+            if let Some(db) = upgrade_request
+                .result()
+                .ok()
+                .and_then(|result| result.dyn_into::<IdbDatabase>().ok())
+            {
+                for store in STORES {
+                    if !db.object_store_names().contains(store) {
+                        if let Err(e) = db.create_object_store(store) {
+                            log(&format!("创建对象仓库 {} 失败: {:?}", store, e));
+                        }
+                    }
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+
+        let result = await_request(&request).await;
+        request.set_onupgradeneeded(None);
+        let db = result?
+            .dyn_into::<IdbDatabase>()
+            .map_err(|_| AppError::file("无法打开本地数据库"))?;
+        Ok(Self { db })
+    }
+
+    /// 在新事务中打开对象仓库
+    pub fn object_store(
+        &self,
+        name: &str,
+        mode: IdbTransactionMode,
+    ) -> AppResult<(IdbObjectStore, IdbTransaction)> {
+        let transaction = self
+            .db
+            .transaction_with_str_and_mode(name, mode)
+            .map_err(storage_error)?;
+        let store = transaction.object_store(name).map_err(storage_error)?;
+        Ok((store, transaction))
+    }
+}
+
+pub fn storage_error(error: JsValue) -> AppError {
+    AppError::file(format!("本地数据库操作失败: {:?}", error))
+}
+
+/// 等待请求完成并返回结果
+pub async fn await_request(request: &IdbRequest) -> AppResult<JsValue> {
+    wait_for_event(|on_success, on_error| {
+        request.set_onsuccess(Some(on_success));
+        request.set_onerror(Some(on_error));
+    })
+    .await?;
+    request.result().map_err(storage_error)
+}
+
+/// 等待事务提交
+pub async fn await_transaction(transaction: &IdbTransaction) -> AppResult<()> {
+    wait_for_event(|on_complete, on_error| {
+        transaction.set_oncomplete(Some(on_complete));
+        transaction.set_onerror(Some(on_error));
+    })
+    .await
+}
+
+/// 把 IndexedDB 的成功/失败回调转成 Future，回调在等待结束前保持有效
+async fn wait_for_event(
+    register: impl FnOnce(&js_sys::Function, &js_sys::Function),
+) -> AppResult<()> {
+    let (sender, receiver) = oneshot::channel::<bool>();
+    let sender = Rc::new(RefCell::new(Some(sender)));
+    let callback = |succeeded: bool| {
+        let sender = sender.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            if let Some(sender) = sender.borrow_mut().take() {
+                let _ = sender.send(succeeded);
+            }
+        })
+    };
+    let on_success = callback(true);
+    let on_error = callback(false);
+    register(
+        on_success.as_ref().unchecked_ref(),
+        on_error.as_ref().unchecked_ref(),
+    );
+
+    match receiver.await {
+        Ok(true) => Ok(()),
+        _ => Err(AppError::file("本地数据库操作失败")),
+    }
+}
@@ -1,10 +1,53 @@
+use super::extractor::{
+    extract_markdown_links, ExtractFuture, ExtractedContent, ExtractedLink, Extractor,
+};
+use super::platform::log;
 use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
+use crate::error::{AppError, AppResult};
 use crate::types::api_types::AppConfig;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Jina Reader请求选项，对应其请求头
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct JinaConfig {
+    /// Authorization Bearer 密钥，留空则匿名访问
+    pub api_key: String,
+    /// X-Return-Format，例如 markdown、html、text，留空使用服务默认值
+    pub return_format: String,
+    /// X-Target-Selector，只提取匹配的元素
+    pub target_selector: String,
+    /// X-Remove-Selector，提取前移除匹配的元素
+    pub remove_selector: String,
+    /// X-With-Generated-Alt，为没有alt的图片生成说明
+    pub with_generated_alt: bool,
+    /// X-No-Cache，跳过Jina的缓存
+    pub no_cache: bool,
+    /// 请求JSON响应，用其中的标题和链接列表
+    pub json_mode: bool,
+}
+
+impl Default for JinaConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            return_format: String::new(),
+            target_selector: String::new(),
+            remove_selector: String::new(),
+            with_generated_alt: false,
+            no_cache: false,
+            json_mode: true,
+        }
+    }
+}
 
 pub struct JinaService {
     client: Client,
     rate_limiter: RateLimiter,
+    api_url: String,
+    options: JinaConfig,
 }
 
 impl JinaService {
@@ -12,70 +55,260 @@ impl JinaService {
         Self {
             client: Client::new(),
             rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000), // 1秒 = 1000毫秒
+            api_url: config.jina_api_url.clone(),
+            options: config.jina.clone(),
         }
     }
 
-    pub async fn extract_content(
-        &self,
-        url: &str,
-        config: &AppConfig,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        let jina_url = format!("{}/{}", config.jina_api_url, url);
+    pub async fn extract_content(&self, url: &str) -> AppResult<ExtractedContent> {
+        let jina_url = format!("{}/{}", self.api_url.trim_end_matches('/'), url);
 
         // 在控制台输出请求URL用于调试
-        web_sys::console::log_1(&format!("发送请求到: {}", jina_url).into());
+        log(&format!("发送请求到: {}", jina_url));
 
         let retry_config = RetryConfig::default();
-        let _client = &self.client;
-        let jina_url_clone = jina_url.clone();
-
-        let result = retry_with_backoff(
-            || {
-                let client = self.client.clone();
-                let jina_url = jina_url_clone.clone();
-
-                Box::pin(async move {
-                    web_sys::console::log_1(&format!("正在发送请求到: {}", jina_url).into());
-                    let response = client
-                        .get(&jina_url)
-                        .header("User-Agent", "Mozilla/5.0 (compatible; URL-Translator/1.0)")
-                        .header("Accept", "text/plain, text/markdown, text/html, */*")
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            web_sys::console::log_1(&format!("网络请求失败: {}", e).into());
-                            format!("网络请求失败: {}. 可能是CORS问题或网络连接问题", e)
-                        })?;
-
-                    let status = response.status();
-                    web_sys::console::log_1(&format!("响应状态: {}", status).into());
-
-                    if response.status().is_success() {
-                        let content = response
-                            .text()
-                            .await
-                            .map_err(|e| format!("读取响应内容失败: {}", e))?;
-
-                        if content.is_empty() {
-                            return Err(Box::from("Jina API返回了空内容，URL可能无效或无法访问")
-                                as Box<dyn std::error::Error>);
-                        }
-
-                        Ok(content)
-                    } else {
-                        let error_text = response
-                            .text()
-                            .await
-                            .unwrap_or_else(|_| "无法读取错误信息".to_string());
-                        Err(format!("Jina API请求失败: {} - {}", status, error_text).into())
-                    }
-                })
-            },
+        let content = retry_with_backoff(
+            || Box::pin(self.fetch_reader(url)),
             &retry_config,
             &self.rate_limiter,
         )
         .await?;
 
-        Ok(result)
+        log(&format!(
+            "Jina提取完成: 标题 \"{}\"，{} 个链接",
+            content.title,
+            content.links.len()
+        ));
+
+        Ok(content)
+    }
+
+    /// 请求Jina Reader并解析响应
+    pub async fn fetch_reader(&self, url: &str) -> AppResult<ExtractedContent> {
+        let jina_url = format!("{}/{}", self.api_url.trim_end_matches('/'), url);
+
+        let mut request = self.client.get(&jina_url);
+        for (name, value) in reader_headers(&self.options) {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            AppError::network(format!("网络请求失败: {}. 可能是CORS问题或网络连接问题", e))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "无法读取错误信息".to_string());
+            let error_text = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|json| {
+                    json.get("readableMessage")
+                        .or_else(|| json.get("message"))
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                })
+                .unwrap_or(body);
+            let message = format!("请求失败: {} - {}", status, error_text);
+            return Err(match status.as_u16() {
+                401 | 403 => AppError::config(format!("Jina API密钥无效或无权限: {}", message)),
+                429 => AppError::rate_limit(format!("Jina {}", message)),
+                _ => AppError::api("Jina", message),
+            });
+        }
+
+        let is_json = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("json"));
+
+        let content = response
+            .text()
+            .await
+            .map_err(|e| AppError::network(format!("读取响应内容失败: {}", e)))?;
+
+        if content.trim().is_empty() {
+            return Err(AppError::extraction(
+                "Jina API返回了空内容，URL可能无效或无法访问",
+            ));
+        }
+
+        if is_json {
+            parse_reader_json(&content, url)
+        } else {
+            Ok(parse_reader_text(&content, url))
+        }
+    }
+}
+
+impl Extractor for JinaService {
+    fn extract<'a>(&'a self, url: &'a str) -> ExtractFuture<'a> {
+        Box::pin(self.extract_content(url))
+    }
+
+    fn name(&self) -> &'static str {
+        "Jina Reader"
+    }
+}
+
+/// 解析Jina Reader的文本输出
+///
+/// 输出以 `Title:`、`URL Source:` 等头部开始，`Markdown Content:` 之后为正文；
+/// 没有这些头部时整段文本都作为正文。
+pub fn parse_reader_text(raw: &str, request_url: &str) -> ExtractedContent {
+    let mut title = String::new();
+    let mut canonical_url = String::new();
+    let mut markdown = raw.trim().to_string();
+
+    if let Some(marker) = raw.find("Markdown Content:") {
+        for line in raw[..marker].lines() {
+            if let Some(value) = line.strip_prefix("Title:") {
+                title = value.trim().to_string();
+            } else if let Some(value) = line.strip_prefix("URL Source:") {
+                canonical_url = value.trim().to_string();
+            }
+        }
+        markdown = raw[marker + "Markdown Content:".len()..].trim().to_string();
+    }
+
+    if canonical_url.is_empty() {
+        canonical_url = request_url.to_string();
+    }
+    if title.is_empty() {
+        title = markdown
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("# "))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+    }
+
+    let links = extract_markdown_links(&markdown, &canonical_url);
+
+    ExtractedContent {
+        title,
+        markdown,
+        canonical_url,
+        language: None,
+        links,
+    }
+}
+
+/// 根据配置生成Jina Reader请求头
+pub fn reader_headers(config: &JinaConfig) -> Vec<(&'static str, String)> {
+    let mut headers = Vec::new();
+
+    if config.json_mode {
+        headers.push(("Accept", "application/json".to_string()));
+        headers.push(("X-With-Links-Summary", "true".to_string()));
+    } else {
+        headers.push(("Accept", "text/plain, text/markdown, */*".to_string()));
+    }
+
+    let api_key = config.api_key.trim();
+    if !api_key.is_empty() {
+        headers.push(("Authorization", format!("Bearer {}", api_key)));
+    }
+
+    for (name, value) in [
+        ("X-Return-Format", &config.return_format),
+        ("X-Target-Selector", &config.target_selector),
+        ("X-Remove-Selector", &config.remove_selector),
+    ] {
+        let value = value.trim();
+        if !value.is_empty() {
+            headers.push((name, value.to_string()));
+        }
+    }
+
+    if config.with_generated_alt {
+        headers.push(("X-With-Generated-Alt", "true".to_string()));
+    }
+    if config.no_cache {
+        headers.push(("X-No-Cache", "true".to_string()));
+    }
+
+    headers
+}
+
+/// 解析Jina Reader的JSON响应
+///
+/// 标题、规范地址和链接列表取自 `data` 字段；没有链接摘要时从正文中提取。
+pub fn parse_reader_json(body: &str, request_url: &str) -> AppResult<ExtractedContent> {
+    let json: Value = serde_json::from_str(body)
+        .map_err(|e| AppError::parse(format!("无法解析Jina JSON响应: {}", e)))?;
+    let data = json
+        .get("data")
+        .filter(|data| data.is_object())
+        .ok_or_else(|| AppError::extraction("Jina JSON响应缺少data字段"))?;
+
+    let field = |name: &str| {
+        data.get(name)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+
+    let markdown = field("content");
+    let canonical_url = Some(field("url"))
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| request_url.to_string());
+    let language = data
+        .get("metadata")
+        .and_then(|metadata| metadata.get("lang"))
+        .and_then(Value::as_str)
+        .filter(|lang| !lang.is_empty())
+        .map(str::to_string);
+
+    let base = url::Url::parse(&canonical_url).ok();
+    let mut links: Vec<ExtractedLink> = Vec::new();
+    for (text, href) in link_pairs(data.get("links")) {
+        let url = match &base {
+            Some(base) => match base.join(&href) {
+                Ok(url) => url.to_string(),
+                Err(_) => continue,
+            },
+            None => href,
+        };
+        if !links.iter().any(|link| link.url == url) {
+            links.push(ExtractedLink { text, url });
+        }
+    }
+    if links.is_empty() {
+        links = extract_markdown_links(&markdown, &canonical_url);
+    }
+
+    Ok(ExtractedContent {
+        title: field("title"),
+        markdown,
+        canonical_url,
+        language,
+        links,
+    })
+}
+
+/// 链接摘要可能是 {"文本": "地址"} 或 [["文本", "地址"]]
+/// 对象形式依赖 serde_json 的 preserve_order 特性保持页面中的顺序
+fn link_pairs(links: Option<&Value>) -> Vec<(String, String)> {
+    match links {
+        Some(Value::Object(map)) => map
+            .iter()
+            .filter_map(|(text, url)| Some((text.trim().to_string(), url.as_str()?.to_string())))
+            .collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| {
+                let pair = item.as_array()?;
+                Some((
+                    pair.first()?.as_str()?.trim().to_string(),
+                    pair.get(1)?.as_str()?.to_string(),
+                ))
+            })
+            .collect(),
+        _ => Vec::new(),
     }
 }
@@ -0,0 +1,286 @@
+use super::platform::log;
+use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
+use super::translator::{
+    ensure_placeholders, split_text_into_chunks, TranslateFuture, Translator,
+    TranslatorCapabilities,
+};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// LibreTranslate（可自建部署）配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LibreTranslateConfig {
+    /// 服务根地址，例如 http://localhost:5000，会在其后拼接 /translate 等路径
+    pub api_url: String,
+    /// 未开启API Key校验的实例可以留空
+    pub api_key: String,
+}
+
+impl Default for LibreTranslateConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "http://localhost:5000".to_string(),
+            api_key: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LibreTranslateRequest {
+    pub q: String,
+    pub source: String,
+    pub target: String,
+    pub format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LibreTranslateResponse {
+    #[serde(rename = "translatedText")]
+    pub translated_text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LibreDetectRequest {
+    pub q: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// /languages 返回的语言条目
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LibreLanguage {
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub targets: Vec<String>,
+}
+
+/// /detect 返回的检测结果
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DetectedLanguage {
+    pub confidence: f64,
+    pub language: String,
+}
+
+pub struct LibreTranslateService {
+    client: Client,
+    rate_limiter: RateLimiter,
+    config: AppConfig,
+}
+
+impl LibreTranslateService {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: Client::new(),
+            rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000),
+            config: config.clone(),
+        }
+    }
+
+    pub async fn translate_text(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<String> {
+        let chunks = split_text_into_chunks(text, self.config.max_text_length);
+
+        // 自动检测时先用第一块确定源语言，保证各块使用同一种源语言
+        let mut source = source_lang.to_string();
+        if source_lang.eq_ignore_ascii_case("auto") {
+            if let Some(first) = chunks.first() {
+                match self.detect_language(first).await {
+                    Ok(detected) if !detected.is_empty() => {
+                        log(&format!(
+                            "LibreTranslate检测到源语言: {} (置信度 {:.0})",
+                            detected[0].language, detected[0].confidence
+                        ));
+                        source = detected[0].language.clone();
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log(&format!("LibreTranslate语言检测失败，改用auto: {}", e));
+                    }
+                }
+            }
+        }
+
+        log(&format!("LibreTranslate翻译: 共 {} 块", chunks.len()));
+
+        let mut translated_chunks = Vec::new();
+        for chunk in &chunks {
+            let retry_config = RetryConfig::default();
+            let translated = retry_with_backoff(
+                || Box::pin(self.translate_chunk(chunk, &source, target_lang)),
+                &retry_config,
+                &self.rate_limiter,
+            )
+            .await?;
+            translated_chunks.push(translated);
+        }
+
+        Ok(translated_chunks.join("\n\n"))
+    }
+
+    /// 调用 /translate 翻译单个文本块
+    pub async fn translate_chunk(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<String> {
+        let libre = &self.config.libretranslate;
+        let request = LibreTranslateRequest {
+            q: text.to_string(),
+            source: to_libre_lang(source_lang),
+            target: to_libre_lang(target_lang),
+            format: "text".to_string(),
+            api_key: self.api_key(),
+        };
+
+        let response = self
+            .client
+            .post(endpoint(&libre.api_url, "translate"))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::network(format!(
+                    "LibreTranslate网络请求失败: {}. 请检查服务地址是否可访问",
+                    e
+                ))
+            })?;
+
+        let body = read_success_body(response).await?;
+        let result: LibreTranslateResponse = serde_json::from_str(&body).map_err(|e| {
+            AppError::parse(format!("无法解析LibreTranslate响应: {} - {}", e, body))
+        })?;
+
+        if result.translated_text.trim().is_empty() {
+            return Err(AppError::translation("LibreTranslate返回了空的翻译结果"));
+        }
+
+        ensure_placeholders(text, &result.translated_text)
+    }
+
+    /// 调用 /languages 获取服务端支持的语言
+    pub async fn languages(&self) -> AppResult<Vec<LibreLanguage>> {
+        let response = self
+            .client
+            .get(endpoint(&self.config.libretranslate.api_url, "languages"))
+            .send()
+            .await
+            .map_err(|e| AppError::network(format!("获取LibreTranslate语言列表失败: {}", e)))?;
+
+        let body = read_success_body(response).await?;
+        serde_json::from_str(&body).map_err(|e| {
+            AppError::parse(format!("无法解析LibreTranslate语言列表: {} - {}", e, body))
+        })
+    }
+
+    /// 调用 /detect 检测文本语言，结果按置信度从高到低排列
+    pub async fn detect_language(&self, text: &str) -> AppResult<Vec<DetectedLanguage>> {
+        let request = LibreDetectRequest {
+            q: text.to_string(),
+            api_key: self.api_key(),
+        };
+
+        let response = self
+            .client
+            .post(endpoint(&self.config.libretranslate.api_url, "detect"))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AppError::network(format!("LibreTranslate语言检测请求失败: {}", e)))?;
+
+        let body = read_success_body(response).await?;
+        let mut detected: Vec<DetectedLanguage> = serde_json::from_str(&body).map_err(|e| {
+            AppError::parse(format!("无法解析LibreTranslate检测结果: {} - {}", e, body))
+        })?;
+        detected.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        Ok(detected)
+    }
+
+    fn api_key(&self) -> Option<String> {
+        let key = self.config.libretranslate.api_key.trim();
+        (!key.is_empty()).then(|| key.to_string())
+    }
+}
+
+impl Translator for LibreTranslateService {
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text(text, source_lang, target_lang))
+    }
+
+    fn capabilities(&self) -> TranslatorCapabilities {
+        TranslatorCapabilities {
+            engine: TranslationEngine::LibreTranslate,
+            max_text_length: self.config.max_text_length,
+            max_requests_per_second: self.config.max_requests_per_second,
+            supports_auto_detect: true,
+            supports_streaming: false,
+        }
+    }
+}
+
+/// 拼接服务根地址与接口路径
+pub fn endpoint(base_url: &str, path: &str) -> String {
+    format!("{}/{}", base_url.trim_end_matches('/'), path)
+}
+
+/// 应用内部使用大写语言代码（如 ZH），LibreTranslate使用小写代码（如 zh）
+///
+/// 带地区后缀的代码（如 zh-Hans）直接来自服务端的语言列表，保持原样。
+pub fn to_libre_lang(code: &str) -> String {
+    if code.contains('-') {
+        code.to_string()
+    } else {
+        code.to_lowercase()
+    }
+}
+
+/// 将LibreTranslate的语言代码转换为应用内部使用的代码
+pub fn from_libre_lang(code: &str) -> String {
+    if code.contains('-') {
+        code.to_string()
+    } else {
+        code.to_uppercase()
+    }
+}
+
+async fn read_success_body(response: reqwest::Response) -> AppResult<String> {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| AppError::network(format!("读取响应文本失败: {}", e)))?;
+
+    if status.is_success() {
+        return Ok(body);
+    }
+
+    let message = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|value| value.get("error")?.as_str().map(|m| m.to_string()))
+        .unwrap_or(body);
+
+    Err(match status.as_u16() {
+        429 => AppError::rate_limit(format!("LibreTranslate接口限流: {}", message)),
+        400 => AppError::validation("language", format!("LibreTranslate请求无效: {}", message)),
+        403 => AppError::config(format!("LibreTranslate API Key无效: {}", message)),
+        _ => AppError::api(
+            "LibreTranslate",
+            format!("请求失败: {} - {}", status, message),
+        ),
+    })
+}
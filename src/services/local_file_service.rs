@@ -0,0 +1,537 @@
+use super::extractor::{extract_markdown_links, ExtractedContent};
+use super::pdf_extractor::extract_from_pdf;
+use super::readability_extractor::extract_from_html;
+use crate::error::{AppError, AppResult};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+/// 文件选择框接受的扩展名
+pub const ACCEPTED_FILE_TYPES: &str = ".md,.markdown,.txt,.html,.htm,.docx,.pdf";
+
+/// 支持翻译的本地文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalFileFormat {
+    Markdown,
+    Text,
+    Html,
+    Docx,
+    Pdf,
+}
+
+impl LocalFileFormat {
+    /// 根据文件扩展名判断格式
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let extension = name.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "txt" => Some(Self::Text),
+            "html" | "htm" => Some(Self::Html),
+            "docx" => Some(Self::Docx),
+            "pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+}
+
+/// 用户上传的本地文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalFile {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// 本地文件在历史记录中的来源地址
+pub fn local_file_url(name: &str) -> String {
+    format!("file:///{}", name)
+}
+
+/// 把本地文件转换为与Jina Reader输出相同的Markdown内容
+pub fn convert_local_file(file: &LocalFile) -> AppResult<ExtractedContent> {
+    let format = LocalFileFormat::from_file_name(&file.name).ok_or_else(|| {
+        AppError::validation(
+            "文件",
+            format!(
+                "不支持的文件类型: {}，请选择 Markdown、HTML、TXT、DOCX 或 PDF 文件",
+                file.name
+            ),
+        )
+    })?;
+    let url = local_file_url(&file.name);
+
+    let mut content = match format {
+        LocalFileFormat::Markdown | LocalFileFormat::Text => {
+            markdown_content(&decode_text(&file.bytes)?, url)
+        }
+        LocalFileFormat::Html => extract_from_html(&decode_text(&file.bytes)?, &url),
+        LocalFileFormat::Pdf => extract_from_pdf(&file.bytes, &url)?,
+        LocalFileFormat::Docx => {
+            let (title, markdown) = docx_to_markdown(&file.bytes)?;
+            let links = extract_markdown_links(&markdown, &url);
+            ExtractedContent {
+                title,
+                markdown,
+                canonical_url: url,
+                language: None,
+                links,
+            }
+        }
+    };
+
+    if content.markdown.trim().is_empty() {
+        return Err(AppError::extraction(format!(
+            "文件 {} 中没有可翻译的内容",
+            file.name
+        )));
+    }
+    if content.title.is_empty() {
+        content.title = file_stem(&file.name).to_string();
+    }
+
+    Ok(content)
+}
+
+/// 把粘贴的文本或Markdown作为待翻译内容，不经过提取
+pub fn pasted_text_content(text: &str) -> AppResult<ExtractedContent> {
+    let content = markdown_content(&text.replace("\r\n", "\n"), String::new());
+    if content.markdown.is_empty() {
+        return Err(AppError::validation("文本", "请输入要翻译的文本"));
+    }
+    Ok(content)
+}
+
+/// Markdown原样保留，标题取第一个一级标题
+fn markdown_content(text: &str, url: String) -> ExtractedContent {
+    let markdown = text.trim().to_string();
+    let title = markdown
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("# "))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let links = extract_markdown_links(&markdown, &url);
+    ExtractedContent {
+        title,
+        markdown,
+        canonical_url: url,
+        language: None,
+        links,
+    }
+}
+
+/// 文件名去掉扩展名
+fn file_stem(name: &str) -> &str {
+    name.rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or(name)
+}
+
+/// 按UTF-8解码文本，去掉BOM并统一换行符
+fn decode_text(bytes: &[u8]) -> AppResult<String> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| AppError::file("文件不是有效的UTF-8文本，请转换编码后重试"))?;
+    Ok(text.replace("\r\n", "\n"))
+}
+
+/// 把DOCX文档转换为Markdown，返回 (标题, 正文)
+///
+/// 支持标题样式、列表、粗体斜体、超链接和表格；图片等其他内容会被忽略。
+pub fn docx_to_markdown(bytes: &[u8]) -> AppResult<(String, String)> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| AppError::file(format!("无法读取DOCX文件: {}", e)))?;
+
+    let document = read_zip_entry(&mut archive, "word/document.xml")?
+        .ok_or_else(|| AppError::file("DOCX文件缺少 word/document.xml"))?;
+    let relationships = read_zip_entry(&mut archive, "word/_rels/document.xml.rels")?
+        .map(|xml| parse_relationships(&xml))
+        .transpose()?
+        .unwrap_or_default();
+    let core_title = read_zip_entry(&mut archive, "docProps/core.xml")?
+        .map(|xml| parse_core_title(&xml))
+        .transpose()?
+        .unwrap_or_default();
+
+    let blocks = parse_document(&document, &relationships)?;
+    let title = if core_title.is_empty() {
+        blocks
+            .iter()
+            .find_map(|block| match block {
+                DocxBlock::Heading(_, text) => Some(text.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    } else {
+        core_title
+    };
+
+    Ok((title, render_blocks(&blocks)))
+}
+
+fn read_zip_entry(
+    archive: &mut zip::ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> AppResult<Option<String>> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(AppError::file(format!("无法读取DOCX中的 {}: {}", name, e))),
+    };
+    let mut xml = String::new();
+    entry
+        .read_to_string(&mut xml)
+        .map_err(|e| AppError::file(format!("无法读取DOCX中的 {}: {}", name, e)))?;
+    Ok(Some(xml))
+}
+
+fn xml_error(e: quick_xml::Error) -> AppError {
+    AppError::parse(format!("DOCX内容格式错误: {}", e))
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == name)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+/// 超链接关系：Id -> 目标地址
+fn parse_relationships(xml: &str) -> AppResult<HashMap<String, String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut relationships = HashMap::new();
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(e) | Event::Empty(e) if e.name().as_ref() == b"Relationship" => {
+                if let (Some(id), Some(target)) = (attribute(&e, b"Id"), attribute(&e, b"Target")) {
+                    relationships.insert(id, target);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(relationships)
+}
+
+/// 文档属性中的标题
+fn parse_core_title(xml: &str) -> AppResult<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut in_title = false;
+    let mut title = String::new();
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(e) if e.name().as_ref() == b"dc:title" => in_title = true,
+            Event::End(e) if e.name().as_ref() == b"dc:title" => in_title = false,
+            Event::Text(text) if in_title => title.push_str(&text.unescape().map_err(xml_error)?),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(title.trim().to_string())
+}
+
+#[derive(Debug)]
+enum DocxBlock {
+    Heading(usize, String),
+    Paragraph(String),
+    ListItem(usize, String),
+    Code(String),
+    Table(Vec<Vec<String>>),
+}
+
+/// 段落中格式相同的一段文字
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Segment {
+    text: String,
+    bold: bool,
+    italic: bool,
+    link: Option<String>,
+}
+
+#[derive(Default)]
+struct ParagraphState {
+    style: String,
+    list_level: Option<usize>,
+    segments: Vec<Segment>,
+}
+
+impl ParagraphState {
+    fn push(&mut self, segment: Segment) {
+        if segment.text.is_empty() {
+            return;
+        }
+        match self.segments.last_mut() {
+            Some(last)
+                if last.bold == segment.bold
+                    && last.italic == segment.italic
+                    && last.link == segment.link =>
+            {
+                last.text.push_str(&segment.text);
+            }
+            _ => self.segments.push(segment),
+        }
+    }
+
+    fn into_block(self) -> Option<DocxBlock> {
+        let style = self.style.to_ascii_lowercase();
+        if style.contains("code") || style.contains("preformatted") {
+            let text: String = self.segments.iter().map(|s| s.text.as_str()).collect();
+            return Some(DocxBlock::Code(text));
+        }
+
+        let text = render_segments(&self.segments);
+        if text.trim().is_empty() {
+            return None;
+        }
+        let text = text.trim().to_string();
+
+        if let Some(level) = heading_level(&style) {
+            return Some(DocxBlock::Heading(level, text));
+        }
+        match self.list_level {
+            Some(level) => Some(DocxBlock::ListItem(level, text)),
+            None if style.starts_with("listparagraph") || style.starts_with("listbullet") => {
+                Some(DocxBlock::ListItem(0, text))
+            }
+            None => Some(DocxBlock::Paragraph(text)),
+        }
+    }
+}
+
+/// Title 样式视为一级标题，HeadingN 视为N级标题
+fn heading_level(style: &str) -> Option<usize> {
+    if style == "title" {
+        return Some(1);
+    }
+    let level: usize = style
+        .strip_prefix("heading")?
+        .trim()
+        .parse()
+        .ok()
+        .filter(|level| (1..=6).contains(level))?;
+    Some(level)
+}
+
+fn render_segments(segments: &[Segment]) -> String {
+    let mut result = String::new();
+    let mut index = 0;
+    while index < segments.len() {
+        let link = segments[index].link.clone();
+        let end = segments[index..]
+            .iter()
+            .position(|segment| segment.link != link)
+            .map_or(segments.len(), |offset| index + offset);
+
+        let text: String = segments[index..end].iter().map(format_segment).collect();
+        match link {
+            Some(target) if !text.trim().is_empty() => {
+                result.push_str(&format!("[{}]({})", text.trim(), target));
+            }
+            _ => result.push_str(&text),
+        }
+        index = end;
+    }
+    result
+}
+
+fn format_segment(segment: &Segment) -> String {
+    let trimmed = segment.text.trim();
+    if trimmed.is_empty() || !(segment.bold || segment.italic) {
+        return segment.text.clone();
+    }
+    let marker = match (segment.bold, segment.italic) {
+        (true, true) => "***",
+        (true, false) => "**",
+        _ => "*",
+    };
+    // 标记放在空白内侧，否则Markdown不会识别
+    let leading = &segment.text[..segment.text.len() - segment.text.trim_start().len()];
+    let trailing = &segment.text[segment.text.trim_end().len()..];
+    format!("{}{}{}{}{}", leading, marker, trimmed, marker, trailing)
+}
+
+/// 开关属性，例如 `<w:b/>` 或 `<w:b w:val="0"/>`
+fn toggle_on(element: &BytesStart) -> bool {
+    !matches!(
+        attribute(element, b"w:val").as_deref(),
+        Some("0" | "false" | "none")
+    )
+}
+
+fn parse_document(xml: &str, relationships: &HashMap<String, String>) -> AppResult<Vec<DocxBlock>> {
+    let mut reader = Reader::from_str(xml);
+    let mut blocks = Vec::new();
+
+    let mut paragraph: Option<ParagraphState> = None;
+    let mut run = Segment::default();
+    let mut link: Option<String> = None;
+    let mut in_text = false;
+
+    // 表格只处理最外层，嵌套表格的文字并入所在单元格
+    let mut table_depth = 0usize;
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut cell: Vec<String> = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(xml_error)?;
+        let is_empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Start(e) | Event::Empty(e) => match e.name().as_ref() {
+                b"w:p" if !is_empty => paragraph = Some(ParagraphState::default()),
+                b"w:pStyle" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.style = attribute(&e, b"w:val").unwrap_or_default();
+                    }
+                }
+                b"w:numPr" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.list_level.get_or_insert(0);
+                    }
+                }
+                b"w:ilvl" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        let level = attribute(&e, b"w:val")
+                            .and_then(|value| value.parse().ok())
+                            .unwrap_or(0);
+                        paragraph.list_level = Some(level);
+                    }
+                }
+                b"w:r" if !is_empty => {
+                    run = Segment {
+                        link: link.clone(),
+                        ..Segment::default()
+                    }
+                }
+                b"w:b" => run.bold = toggle_on(&e),
+                b"w:i" => run.italic = toggle_on(&e),
+                b"w:t" if !is_empty => in_text = true,
+                b"w:tab" => run.text.push('\t'),
+                b"w:br" | b"w:cr" => run.text.push('\n'),
+                b"w:hyperlink" if !is_empty => {
+                    link = attribute(&e, b"r:id")
+                        .and_then(|id| relationships.get(&id).cloned())
+                        .or_else(|| {
+                            attribute(&e, b"w:anchor").map(|anchor| format!("#{}", anchor))
+                        });
+                }
+                b"w:tbl" if !is_empty => {
+                    table_depth += 1;
+                    if table_depth == 1 {
+                        rows.clear();
+                    }
+                }
+                b"w:tr" if !is_empty && table_depth == 1 => row.clear(),
+                b"w:tc" if !is_empty && table_depth == 1 => cell.clear(),
+                _ => {}
+            },
+            Event::Text(text) if in_text => {
+                run.text.push_str(&text.unescape().map_err(xml_error)?);
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"w:t" => in_text = false,
+                b"w:r" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.push(std::mem::take(&mut run));
+                    }
+                }
+                b"w:hyperlink" => link = None,
+                b"w:p" => {
+                    let Some(block) = paragraph.take().and_then(ParagraphState::into_block) else {
+                        continue;
+                    };
+                    if table_depth > 0 {
+                        cell.push(block_text(&block));
+                    } else {
+                        blocks.push(block);
+                    }
+                }
+                b"w:tc" if table_depth == 1 => {
+                    row.push(cell.join(" ").replace('|', "\\|"));
+                }
+                b"w:tr" if table_depth == 1 => rows.push(std::mem::take(&mut row)),
+                b"w:tbl" => {
+                    table_depth = table_depth.saturating_sub(1);
+                    if table_depth == 0 && !rows.is_empty() {
+                        blocks.push(DocxBlock::Table(std::mem::take(&mut rows)));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(blocks)
+}
+
+/// 表格单元格中的段落只保留文字
+fn block_text(block: &DocxBlock) -> String {
+    match block {
+        DocxBlock::Heading(_, text)
+        | DocxBlock::Paragraph(text)
+        | DocxBlock::ListItem(_, text)
+        | DocxBlock::Code(text) => text.replace('\n', " "),
+        DocxBlock::Table(_) => String::new(),
+    }
+}
+
+/// 连续的列表项和代码行合并为一块，其余块之间空一行
+fn render_blocks(blocks: &[DocxBlock]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut index = 0;
+
+    while index < blocks.len() {
+        match &blocks[index] {
+            DocxBlock::Heading(level, text) => {
+                parts.push(format!("{} {}", "#".repeat(*level), text));
+            }
+            DocxBlock::Paragraph(text) => parts.push(text.clone()),
+            DocxBlock::ListItem(..) => {
+                let mut items = Vec::new();
+                while let Some(DocxBlock::ListItem(level, text)) = blocks.get(index) {
+                    items.push(format!("{}- {}", "  ".repeat(*level), text));
+                    index += 1;
+                }
+                parts.push(items.join("\n"));
+                continue;
+            }
+            DocxBlock::Code(_) => {
+                let mut lines = Vec::new();
+                while let Some(DocxBlock::Code(text)) = blocks.get(index) {
+                    lines.push(text.clone());
+                    index += 1;
+                }
+                parts.push(format!("```\n{}\n```", lines.join("\n")));
+                continue;
+            }
+            DocxBlock::Table(rows) => parts.push(render_table(rows)),
+        }
+        index += 1;
+    }
+
+    parts.join("\n\n")
+}
+
+/// 第一行作为表头
+fn render_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let render_row = |row: &[String]| {
+        let cells: Vec<&str> = (0..columns)
+            .map(|i| row.get(i).map_or("", |cell| cell.trim()))
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    let mut lines = vec![
+        render_row(&rows[0]),
+        format!("|{}", " --- |".repeat(columns)),
+    ];
+    lines.extend(rows[1..].iter().map(|row| render_row(row)));
+    lines.join("\n")
+}
@@ -0,0 +1,84 @@
+use super::extractor::{ExtractFuture, ExtractedContent, ExtractedLink, Extractor};
+use super::index_parser::title_from_slug;
+use crate::error::{AppError, AppResult};
+use url::Url;
+
+/// 示例页面的链接，按页面地址解析：目录地址（以 `/` 结尾）下为子页面，其他页面为同级页面，
+/// 因此从任意地址开始爬取得到的页面数都是有限的
+const SAMPLE_PAGES: &[(&str, &str)] = &[
+    ("Getting started", "getting-started"),
+    ("Configuration", "configuration"),
+    ("FAQ", "faq"),
+];
+
+/// 确定性的模拟提取器，选用模拟翻译引擎时代替网络提取，整个翻译流程都可以离线运行
+pub struct MockExtractor;
+
+impl MockExtractor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 同一地址总是返回相同的内容
+    pub fn sample_content(&self, url: &str) -> AppResult<ExtractedContent> {
+        let page = Url::parse(url.trim())
+            .map_err(|e| AppError::validation("URL", format!("无效的地址: {}", e)))?;
+
+        let title = match page.path() {
+            "/" | "" => page.host_str().unwrap_or("Home").to_string(),
+            path => title_from_slug(path),
+        };
+        let links: Vec<ExtractedLink> = SAMPLE_PAGES
+            .iter()
+            .filter_map(|(text, slug)| {
+                Some(ExtractedLink {
+                    text: text.to_string(),
+                    url: page.join(slug).ok()?.to_string(),
+                })
+            })
+            .collect();
+
+        let link_list = links
+            .iter()
+            .map(|link| format!("- [{}]({})", link.text, link.url))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let markdown = format!(
+            "# {title}\n\n\
+             This is offline sample content for {url}. It lets you try the translation flow without network access.\n\n\
+             ## Overview\n\n\
+             Each sample page has a few paragraphs, a code block and links to other sample pages, \
+             so batch translation and crawling also work offline.\n\n\
+             ```rust\nfn main() {{\n    println!(\"Hello, world!\");\n}}\n```\n\n\
+             ## Pages\n\n\
+             {link_list}",
+            title = title,
+            url = page,
+            link_list = link_list,
+        );
+
+        Ok(ExtractedContent {
+            title,
+            markdown,
+            canonical_url: page.to_string(),
+            language: Some("en".to_string()),
+            links,
+        })
+    }
+}
+
+impl Default for MockExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Extractor for MockExtractor {
+    fn extract<'a>(&'a self, url: &'a str) -> ExtractFuture<'a> {
+        Box::pin(async move { self.sample_content(url) })
+    }
+
+    fn name(&self) -> &'static str {
+        "模拟提取器"
+    }
+}
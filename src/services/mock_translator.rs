@@ -0,0 +1,258 @@
+use super::platform::sleep_ms;
+use super::translator::{
+    ensure_placeholders, find_placeholders, split_text_into_chunks, TranslateFuture, Translator,
+    TranslatorCapabilities,
+};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// 模拟翻译的输出方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum MockMode {
+    /// 伪本地化：字母替换为带重音的字符并加长，便于检查界面和遗漏的文本
+    #[default]
+    PseudoLocalize,
+    /// 原样返回
+    Echo,
+    /// 逐行反转字符
+    Reverse,
+}
+
+/// 注入错误的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum MockErrorKind {
+    #[default]
+    Network,
+    RateLimit,
+    Api,
+    Config,
+}
+
+/// 模拟翻译引擎配置，无需网络
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MockConfig {
+    pub mode: MockMode,
+    /// 每块翻译前等待的毫秒数
+    pub latency_ms: u32,
+    /// 每N次请求失败一次，0表示不注入错误
+    pub fail_every: u32,
+    pub error_kind: MockErrorKind,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            mode: MockMode::PseudoLocalize,
+            latency_ms: 0,
+            fail_every: 0,
+            error_kind: MockErrorKind::Network,
+        }
+    }
+}
+
+/// 确定性的模拟翻译器，用于离线开发和测试
+pub struct MockTranslator {
+    config: MockConfig,
+    max_text_length: usize,
+    max_requests_per_second: u32,
+    request_count: Cell<u32>,
+}
+
+impl MockTranslator {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            config: config.mock.clone(),
+            max_text_length: config.max_text_length,
+            max_requests_per_second: config.max_requests_per_second,
+            request_count: Cell::new(0),
+        }
+    }
+
+    pub async fn translate_text_streaming(
+        &self,
+        text: &str,
+        on_progress: &dyn Fn(&str),
+    ) -> AppResult<String> {
+        let chunks = split_text_into_chunks(text, self.max_text_length);
+
+        let mut translated = String::new();
+        for chunk in &chunks {
+            if !translated.is_empty() {
+                translated.push_str("\n\n");
+            }
+            translated.push_str(&self.translate_chunk(chunk).await?);
+            on_progress(&translated);
+        }
+
+        Ok(translated)
+    }
+
+    /// 模拟一次翻译请求
+    pub async fn translate_chunk(&self, text: &str) -> AppResult<String> {
+        let count = self.request_count.get() + 1;
+        self.request_count.set(count);
+
+        if self.config.latency_ms > 0 {
+            sleep_ms(self.config.latency_ms).await;
+        }
+
+        if self.config.fail_every > 0 && count.is_multiple_of(self.config.fail_every) {
+            return Err(injected_error(self.config.error_kind, count));
+        }
+
+        let translated = match self.config.mode {
+            MockMode::PseudoLocalize => pseudo_localize(text),
+            MockMode::Echo => text.to_string(),
+            MockMode::Reverse => reverse_text(text),
+        };
+
+        ensure_placeholders(text, &translated)
+    }
+}
+
+impl Translator for MockTranslator {
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        _source_lang: &'a str,
+        _target_lang: &'a str,
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text_streaming(text, &ignore_progress))
+    }
+
+    fn translate_streaming<'a>(
+        &'a self,
+        text: &'a str,
+        _source_lang: &'a str,
+        _target_lang: &'a str,
+        on_progress: &'a dyn Fn(&str),
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text_streaming(text, on_progress))
+    }
+
+    fn capabilities(&self) -> TranslatorCapabilities {
+        TranslatorCapabilities {
+            engine: TranslationEngine::Mock,
+            max_text_length: self.max_text_length,
+            max_requests_per_second: self.max_requests_per_second,
+            supports_auto_detect: true,
+            supports_streaming: true,
+        }
+    }
+}
+
+fn ignore_progress(_: &str) {}
+
+fn injected_error(kind: MockErrorKind, count: u32) -> AppError {
+    let message = format!("模拟引擎注入的错误（第 {} 次请求）", count);
+    match kind {
+        MockErrorKind::Network => AppError::network(message),
+        MockErrorKind::RateLimit => AppError::rate_limit(message),
+        MockErrorKind::Api => AppError::api("Mock", message),
+        MockErrorKind::Config => AppError::config(message),
+    }
+}
+
+/// 伪本地化：替换为带重音的字母，每个非空行用方括号包住并按长度补足约30%
+///
+/// 代码块占位符、链接地址和行首的Markdown标记保持不变。
+pub fn pseudo_localize(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let content_start = line.len() - line.trim_start().len();
+            let marker_len = markdown_marker_len(&line[content_start..]);
+            let (prefix, body) = line.split_at(content_start + marker_len);
+            if body.trim().is_empty() {
+                return line.to_string();
+            }
+
+            let accented =
+                map_outside_protected(body, |segment| segment.chars().map(accent_char).collect());
+            let padding = "~".repeat(body.chars().count().div_ceil(3));
+            format!("{}[{} {}]", prefix, accented, padding)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 逐行反转字符，代码块占位符和链接地址保持不变
+pub fn reverse_text(text: &str) -> String {
+    text.split('\n')
+        .map(|line| map_outside_protected(line, |segment| segment.chars().rev().collect()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 对占位符和URL以外的片段应用转换
+fn map_outside_protected(text: &str, transform: impl Fn(&str) -> String) -> String {
+    let mut protected: Vec<(usize, usize)> = find_placeholders(text)
+        .iter()
+        .flat_map(|placeholder| {
+            text.match_indices(placeholder.as_str())
+                .map(|(start, matched)| (start, start + matched.len()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    for scheme in ["http://", "https://"] {
+        for (start, _) in text.match_indices(scheme) {
+            let end = text[start..]
+                .find(|c: char| c.is_whitespace() || matches!(c, ')' | '>' | ']' | '"'))
+                .map_or(text.len(), |offset| start + offset);
+            protected.push((start, end));
+        }
+    }
+    protected.sort_unstable();
+
+    let mut result = String::new();
+    let mut cursor = 0;
+    for (start, end) in protected {
+        if start < cursor {
+            continue;
+        }
+        result.push_str(&transform(&text[cursor..start]));
+        result.push_str(&text[start..end]);
+        cursor = end;
+    }
+    result.push_str(&transform(&text[cursor..]));
+    result
+}
+
+/// 行首Markdown标记（标题、列表、引用）的字节长度
+fn markdown_marker_len(line: &str) -> usize {
+    let marker = line
+        .find(|c: char| !matches!(c, '#' | '>' | '-' | '*' | '+' | ' '))
+        .unwrap_or(line.len());
+    let digits = line[marker..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(line.len() - marker);
+    if digits > 0 && line[marker + digits..].starts_with(". ") {
+        marker + digits + 2
+    } else {
+        marker
+    }
+}
+
+fn accent_char(c: char) -> char {
+    match c {
+        'a' => 'á',
+        'c' => 'ç',
+        'e' => 'é',
+        'i' => 'í',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'u' => 'ü',
+        'y' => 'ý',
+        'A' => 'Å',
+        'C' => 'Ç',
+        'E' => 'É',
+        'I' => 'Î',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'U' => 'Ü',
+        'Y' => 'Ý',
+        other => other,
+    }
+}
@@ -1,9 +1,29 @@
+pub mod batch_job_service;
 pub mod batch_service;
 pub mod config_service;
 pub mod content_processor;
+pub mod crawler;
+pub mod deepl_service;
 pub mod deeplx_service;
+pub mod extractor;
+pub mod fallback_translator;
 pub mod file_naming_service;
 pub mod history_service;
+pub mod incremental;
+pub mod index_parser;
+pub mod indexed_db;
 pub mod jina_service;
+pub mod libretranslate_service;
+pub mod local_file_service;
+pub mod mock_extractor;
+pub mod mock_translator;
+pub mod ollama_service;
+pub mod openai_service;
+pub mod pdf_extractor;
+pub mod platform;
 pub mod preview_service;
 pub mod rate_limiter;
+pub mod readability_extractor;
+pub mod sitemap_service;
+pub mod translation_memory;
+pub mod translator;
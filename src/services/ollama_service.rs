@@ -0,0 +1,369 @@
+use super::openai_service::{strip_code_fence, ChatMessage, DEFAULT_SYSTEM_PROMPT};
+use super::platform::log;
+use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
+use super::translator::{
+    ensure_placeholders, language_name, split_text_into_chunks, TranslateFuture, Translator,
+    TranslatorCapabilities,
+};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use futures::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// Ollama使用的接口
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum OllamaEndpoint {
+    /// /api/chat，使用system和user消息
+    #[default]
+    Chat,
+    /// /api/generate，使用system和prompt字段
+    Generate,
+}
+
+/// Ollama本地模型配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OllamaConfig {
+    /// 服务根地址，例如 http://localhost:11434
+    pub api_url: String,
+    pub model: String,
+    pub endpoint: OllamaEndpoint,
+    /// 系统提示词，支持 {source_lang} 和 {target_lang} 占位
+    pub system_prompt: String,
+    pub temperature: f32,
+}
+
+impl Default for OllamaConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "http://localhost:11434".to_string(),
+            model: "qwen2.5:7b".to_string(),
+            endpoint: OllamaEndpoint::Chat,
+            system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+            temperature: 0.2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaOptions {
+    pub temperature: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaChatRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    pub stream: bool,
+    pub options: OllamaOptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaGenerateRequest {
+    pub model: String,
+    pub system: String,
+    pub prompt: String,
+    pub stream: bool,
+    pub options: OllamaOptions,
+}
+
+/// 流式响应中的一行（NDJSON）
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct OllamaStreamLine {
+    /// /api/generate 返回的增量文本
+    #[serde(default)]
+    pub response: String,
+    /// /api/chat 返回的增量消息
+    #[serde(default)]
+    pub message: Option<OllamaStreamMessage>,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct OllamaStreamMessage {
+    #[serde(default)]
+    pub content: String,
+}
+
+impl OllamaStreamLine {
+    /// 本行携带的增量文本
+    pub fn content(&self) -> &str {
+        match &self.message {
+            Some(message) => &message.content,
+            None => &self.response,
+        }
+    }
+}
+
+/// 按行切分流式响应，跨数据块的半行和被截断的UTF-8字符会留到下一次
+#[derive(Debug, Default)]
+pub struct NdjsonLineBuffer {
+    buffer: Vec<u8>,
+}
+
+impl NdjsonLineBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加数据，返回其中已完整的行
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line).trim().to_string();
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        }
+        lines
+    }
+
+    /// 取出流结束时剩余的最后一行
+    pub fn finish(&mut self) -> Option<String> {
+        let line = String::from_utf8_lossy(&self.buffer).trim().to_string();
+        self.buffer.clear();
+        (!line.is_empty()).then_some(line)
+    }
+}
+
+pub struct OllamaService {
+    client: Client,
+    rate_limiter: RateLimiter,
+    config: AppConfig,
+}
+
+impl OllamaService {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: Client::new(),
+            rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000),
+            config: config.clone(),
+        }
+    }
+
+    pub async fn translate_text_streaming(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        on_progress: &dyn Fn(&str),
+    ) -> AppResult<String> {
+        let chunks = split_text_into_chunks(text, self.config.max_text_length);
+        log(&format!(
+            "Ollama流式翻译: 模型 {}，共 {} 块",
+            self.config.ollama.model,
+            chunks.len()
+        ));
+
+        let mut translated = String::new();
+        for chunk in &chunks {
+            if !translated.is_empty() {
+                translated.push_str("\n\n");
+            }
+
+            // 重试时从本块开头重新输出，之前已完成的块保持不变
+            let retry_config = RetryConfig::default();
+            let part = retry_with_backoff(
+                || {
+                    Box::pin(self.stream_chunk(
+                        chunk,
+                        source_lang,
+                        target_lang,
+                        &translated,
+                        on_progress,
+                    ))
+                },
+                &retry_config,
+                &self.rate_limiter,
+            )
+            .await?;
+
+            translated.push_str(&part);
+            on_progress(&translated);
+        }
+
+        Ok(translated)
+    }
+
+    /// 流式翻译单个文本块，回调参数为 `prefix` 加上本块目前的译文
+    pub async fn stream_chunk(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        prefix: &str,
+        on_progress: &dyn Fn(&str),
+    ) -> AppResult<String> {
+        let ollama = &self.config.ollama;
+        let base_url = ollama.api_url.trim_end_matches('/');
+
+        let request = match ollama.endpoint {
+            OllamaEndpoint::Chat => self
+                .client
+                .post(format!("{}/api/chat", base_url))
+                .json(&build_chat_request(ollama, text, source_lang, target_lang)),
+            OllamaEndpoint::Generate => {
+                self.client.post(format!("{}/api/generate", base_url)).json(
+                    &build_generate_request(ollama, text, source_lang, target_lang),
+                )
+            }
+        };
+
+        let response = request.send().await.map_err(|e| {
+            AppError::network(format!(
+                "Ollama网络请求失败: {}. 请确认Ollama已启动并允许跨域访问(OLLAMA_ORIGINS)",
+                e
+            ))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = parse_stream_line(&body)
+                .ok()
+                .and_then(|line| line.error)
+                .unwrap_or(body);
+            return Err(match status.as_u16() {
+                404 => AppError::config(format!(
+                    "Ollama模型 {} 不存在，请先执行 ollama pull: {}",
+                    ollama.model, message
+                )),
+                _ => AppError::api("Ollama", format!("请求失败: {} - {}", status, message)),
+            });
+        }
+
+        let mut partial = String::new();
+        let mut lines = NdjsonLineBuffer::new();
+        let mut stream = response.bytes_stream();
+        let mut done = false;
+
+        while let Some(bytes) = stream.next().await {
+            let bytes =
+                bytes.map_err(|e| AppError::network(format!("读取Ollama流式响应失败: {}", e)))?;
+            for line in lines.push(&bytes) {
+                done |= apply_stream_line(&line, &mut partial)?;
+            }
+            on_progress(&format!("{}{}", prefix, partial));
+            if done {
+                break;
+            }
+        }
+        if let Some(line) = lines.finish() {
+            apply_stream_line(&line, &mut partial)?;
+        }
+
+        let translated = strip_code_fence(partial.trim());
+        if translated.is_empty() {
+            return Err(AppError::translation("Ollama返回了空的翻译结果"));
+        }
+
+        ensure_placeholders(text, translated)
+    }
+}
+
+impl Translator for OllamaService {
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text_streaming(text, source_lang, target_lang, &ignore_progress))
+    }
+
+    fn translate_streaming<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+        on_progress: &'a dyn Fn(&str),
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text_streaming(text, source_lang, target_lang, on_progress))
+    }
+
+    fn capabilities(&self) -> TranslatorCapabilities {
+        TranslatorCapabilities {
+            engine: TranslationEngine::Ollama,
+            max_text_length: self.config.max_text_length,
+            max_requests_per_second: self.config.max_requests_per_second,
+            supports_auto_detect: true,
+            supports_streaming: true,
+        }
+    }
+}
+
+fn ignore_progress(_: &str) {}
+
+fn system_prompt(config: &OllamaConfig, source_lang: &str, target_lang: &str) -> String {
+    config
+        .system_prompt
+        .replace("{source_lang}", language_name(source_lang))
+        .replace("{target_lang}", language_name(target_lang))
+}
+
+/// 构造 /api/chat 请求体
+pub fn build_chat_request(
+    config: &OllamaConfig,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+) -> OllamaChatRequest {
+    OllamaChatRequest {
+        model: config.model.clone(),
+        messages: vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: system_prompt(config, source_lang, target_lang),
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: text.to_string(),
+            },
+        ],
+        stream: true,
+        options: OllamaOptions {
+            temperature: config.temperature,
+        },
+    }
+}
+
+/// 构造 /api/generate 请求体
+pub fn build_generate_request(
+    config: &OllamaConfig,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+) -> OllamaGenerateRequest {
+    OllamaGenerateRequest {
+        model: config.model.clone(),
+        system: system_prompt(config, source_lang, target_lang),
+        prompt: text.to_string(),
+        stream: true,
+        options: OllamaOptions {
+            temperature: config.temperature,
+        },
+    }
+}
+
+/// 解析流式响应中的一行
+pub fn parse_stream_line(line: &str) -> AppResult<OllamaStreamLine> {
+    serde_json::from_str(line)
+        .map_err(|e| AppError::parse(format!("无法解析Ollama流式响应: {} - {}", e, line)))
+}
+
+/// 将一行的增量文本追加到译文，返回是否已结束
+fn apply_stream_line(line: &str, partial: &mut String) -> AppResult<bool> {
+    let line = parse_stream_line(line)?;
+    if let Some(error) = line.error {
+        return Err(AppError::api("Ollama", error));
+    }
+    partial.push_str(line.content());
+    Ok(line.done)
+}
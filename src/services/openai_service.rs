@@ -0,0 +1,251 @@
+use super::platform::log;
+use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
+use super::translator::{
+    ensure_placeholders, language_name, split_text_into_chunks, TranslateFuture, Translator,
+    TranslatorCapabilities,
+};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// OpenAI兼容接口配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OpenAIConfig {
+    /// 完整的chat completions地址，例如 https://api.openai.com/v1/chat/completions
+    pub api_url: String,
+    pub api_key: String,
+    pub model: String,
+    /// 系统提示词，支持 {source_lang} 和 {target_lang} 占位
+    pub system_prompt: String,
+    pub temperature: f32,
+}
+
+impl Default for OpenAIConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "https://api.openai.com/v1/chat/completions".to_string(),
+            api_key: String::new(),
+            model: "gpt-4o-mini".to_string(),
+            system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+            temperature: 0.2,
+        }
+    }
+}
+
+pub const DEFAULT_SYSTEM_PROMPT: &str = "You are a professional technical translator. \
+Translate the Markdown document provided by the user from {source_lang} to {target_lang}. \
+Keep the Markdown structure, links and line breaks unchanged. \
+Tokens that look like __CODE_BLOCK_xxx__ are placeholders: copy every one of them exactly as-is. \
+Output only the translated document, without explanations or surrounding code fences.";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    pub temperature: f32,
+    pub stream: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionResponse {
+    pub choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatChoice {
+    pub message: ChatMessage,
+}
+
+pub struct OpenAIService {
+    client: Client,
+    rate_limiter: RateLimiter,
+    config: AppConfig,
+}
+
+impl OpenAIService {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: Client::new(),
+            rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000),
+            config: config.clone(),
+        }
+    }
+
+    pub async fn translate_text(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<String> {
+        let chunks = split_text_into_chunks(text, self.config.max_text_length);
+        log(&format!(
+            "OpenAI翻译: 模型 {}，共 {} 块",
+            self.config.openai.model,
+            chunks.len()
+        ));
+
+        let mut translated_chunks = Vec::new();
+        for chunk in &chunks {
+            let retry_config = RetryConfig::default();
+            let translated = retry_with_backoff(
+                || Box::pin(self.complete(chunk, source_lang, target_lang)),
+                &retry_config,
+                &self.rate_limiter,
+            )
+            .await?;
+            translated_chunks.push(translated);
+        }
+
+        Ok(translated_chunks.join("\n\n"))
+    }
+
+    /// 发送单次chat completions请求，不做分块和重试
+    pub async fn complete(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<String> {
+        let openai = &self.config.openai;
+        let request = build_chat_request(openai, text, source_lang, target_lang);
+
+        let mut builder = self
+            .client
+            .post(&openai.api_url)
+            .header("Content-Type", "application/json")
+            .json(&request);
+        if !openai.api_key.is_empty() {
+            builder = builder.bearer_auth(&openai.api_key);
+        }
+
+        let response = builder.send().await.map_err(|e| {
+            AppError::network(format!(
+                "OpenAI网络请求失败: {}. 可能是CORS问题或API不可用",
+                e
+            ))
+        })?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AppError::network(format!("读取响应文本失败: {}", e)))?;
+
+        if !status.is_success() {
+            let message = extract_error_message(&body).unwrap_or(body);
+            return Err(match status.as_u16() {
+                429 => AppError::rate_limit(format!("OpenAI接口限流: {}", message)),
+                401 | 403 => AppError::config(format!("OpenAI API Key无效: {}", message)),
+                _ => AppError::api("OpenAI", format!("请求失败: {} - {}", status, message)),
+            });
+        }
+
+        let translated = parse_chat_response(&body)?;
+        ensure_placeholders(text, &translated)
+    }
+}
+
+impl Translator for OpenAIService {
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(self.translate_text(text, source_lang, target_lang))
+    }
+
+    fn capabilities(&self) -> TranslatorCapabilities {
+        TranslatorCapabilities {
+            engine: TranslationEngine::OpenAI,
+            max_text_length: self.config.max_text_length,
+            max_requests_per_second: self.config.max_requests_per_second,
+            supports_auto_detect: true,
+            supports_streaming: false,
+        }
+    }
+}
+
+/// 构造chat completions请求体
+pub fn build_chat_request(
+    config: &OpenAIConfig,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+) -> ChatCompletionRequest {
+    let system_prompt = config
+        .system_prompt
+        .replace("{source_lang}", language_name(source_lang))
+        .replace("{target_lang}", language_name(target_lang));
+
+    ChatCompletionRequest {
+        model: config.model.clone(),
+        messages: vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: system_prompt,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: text.to_string(),
+            },
+        ],
+        temperature: config.temperature,
+        stream: false,
+    }
+}
+
+/// 解析chat completions响应，返回去除包裹的译文
+pub fn parse_chat_response(body: &str) -> AppResult<String> {
+    let response: ChatCompletionResponse = serde_json::from_str(body)
+        .map_err(|e| AppError::parse(format!("无法解析OpenAI响应: {} - {}", e, body)))?;
+
+    let content = response
+        .choices
+        .into_iter()
+        .next()
+        .map(|choice| choice.message.content)
+        .ok_or_else(|| AppError::translation("OpenAI响应中没有choices"))?;
+
+    let content = strip_code_fence(content.trim());
+    if content.is_empty() {
+        return Err(AppError::translation("OpenAI返回了空的翻译结果"));
+    }
+
+    Ok(content.to_string())
+}
+
+/// 模型有时会用```把整个译文包起来，这里去掉最外层的围栏
+pub fn strip_code_fence(content: &str) -> &str {
+    if !content.starts_with("```") || !content.ends_with("```") || content.len() < 6 {
+        return content;
+    }
+
+    let inner = &content[3..content.len() - 3];
+    // 外层围栏内部不能再有围栏，否则说明是正文里的代码块
+    if inner.contains("```") {
+        return content;
+    }
+
+    match inner.find('\n') {
+        Some(pos) => inner[pos + 1..].trim(),
+        None => inner.trim(),
+    }
+}
+
+fn extract_error_message(body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    value
+        .get("error")
+        .and_then(|error| error.get("message").or(Some(error)))
+        .and_then(|message| message.as_str())
+        .map(|message| message.to_string())
+}
@@ -0,0 +1,615 @@
+use super::extractor::{extract_markdown_links, ExtractFuture, ExtractedContent, Extractor};
+use super::platform::log;
+use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
+use super::readability_extractor::proxied_url;
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::AppConfig;
+use lopdf::content::Content;
+use lopdf::{Document, Encoding, Object, ObjectId};
+use reqwest::Client;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// 字号达到正文的这个倍数才视为标题
+const HEADING_SIZE_RATIO: f32 = 1.2;
+
+/// 超过这个长度的行不视为标题
+const MAX_HEADING_LENGTH: usize = 120;
+
+/// 行距超过字号的这个倍数时开始新段落
+const PARAGRAPH_GAP_RATIO: f32 = 1.75;
+
+/// 每页顶部和底部参与页眉页脚判断的行数
+const MARGIN_LINES: usize = 2;
+
+/// 页眉页脚所在区域占页面高度的比例
+const MARGIN_RATIO: f32 = 0.12;
+
+/// 没有 MediaBox 时使用的页面高度（Letter）
+const DEFAULT_PAGE_HEIGHT: f32 = 792.0;
+
+/// 列表项的项目符号
+const BULLETS: &[char] = &['•', '●', '▪', '◦', '‣', '–', '-', '*'];
+
+/// 页面中的一行文字
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfLine {
+    pub text: String,
+    /// 实际显示的字号
+    pub font_size: f32,
+    /// 基线纵坐标，原点在页面左下角
+    pub y: f32,
+}
+
+/// 按内容流顺序排列的一页文字
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfPage {
+    pub number: u32,
+    pub height: f32,
+    pub lines: Vec<PdfLine>,
+}
+
+/// PDF提取器：下载PDF，逐页提取文字并重建标题和段落
+pub struct PdfExtractor {
+    client: Client,
+    rate_limiter: RateLimiter,
+    cors_proxy: String,
+}
+
+impl PdfExtractor {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: Client::new(),
+            rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000),
+            cors_proxy: config.extractor.cors_proxy.clone(),
+        }
+    }
+
+    pub async fn extract_content(&self, url: &str) -> AppResult<ExtractedContent> {
+        log(&format!("PDF提取器请求: {}", self.request_url(url)));
+
+        let retry_config = RetryConfig::default();
+        let content = retry_with_backoff(
+            || Box::pin(self.fetch_pdf(url)),
+            &retry_config,
+            &self.rate_limiter,
+        )
+        .await?;
+
+        log(&format!(
+            "PDF提取完成: 标题 \"{}\"，正文 {} 字符",
+            content.title,
+            content.markdown.len()
+        ));
+
+        Ok(content)
+    }
+
+    /// 下载并解析PDF
+    pub async fn fetch_pdf(&self, url: &str) -> AppResult<ExtractedContent> {
+        let response = self
+            .client
+            .get(self.request_url(url))
+            .header("Accept", "application/pdf, */*")
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::network(format!("获取PDF失败: {}. 浏览器中请确认CORS代理可用", e))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = format!("获取PDF失败: {}", status);
+            return Err(match status.as_u16() {
+                429 => AppError::rate_limit(message),
+                _ => AppError::extraction(message),
+            });
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| AppError::network(format!("读取PDF内容失败: {}", e)))?;
+
+        extract_from_pdf(&bytes, url)
+    }
+
+    /// 浏览器中经由CORS代理访问，原生环境直接访问
+    fn request_url(&self, url: &str) -> String {
+        if cfg!(target_arch = "wasm32") {
+            proxied_url(&self.cors_proxy, url)
+        } else {
+            url.to_string()
+        }
+    }
+}
+
+impl Extractor for PdfExtractor {
+    fn extract<'a>(&'a self, url: &'a str) -> ExtractFuture<'a> {
+        Box::pin(self.extract_content(url))
+    }
+
+    fn name(&self) -> &'static str {
+        "PDF提取器"
+    }
+}
+
+/// 地址路径以 .pdf 结尾时视为PDF文档
+pub fn is_pdf_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .map(|parsed| parsed.path().to_ascii_lowercase().ends_with(".pdf"))
+        .unwrap_or(false)
+}
+
+/// 从PDF中提取Markdown正文，每页前保留 `<!-- page N -->` 标记
+pub fn extract_from_pdf(bytes: &[u8], url: &str) -> AppResult<ExtractedContent> {
+    let document = Document::load_mem(bytes)
+        .map_err(|e| AppError::extraction(format!("无法解析PDF文件: {}", e)))?;
+    if document.is_encrypted() {
+        return Err(AppError::extraction("PDF文件已加密，无法提取文字"));
+    }
+
+    let mut pages = Vec::new();
+    for (number, page_id) in document.get_pages() {
+        pages.push(read_page(&document, number, page_id)?);
+    }
+
+    let markdown = pages_to_markdown(&pages);
+    if !pages.iter().any(|page| !page.lines.is_empty()) {
+        return Err(AppError::extraction("PDF中没有可提取的文字，可能是扫描件"));
+    }
+
+    let title = document_title(&document).unwrap_or_else(|| {
+        markdown
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    });
+
+    Ok(ExtractedContent {
+        title,
+        links: extract_markdown_links(&markdown, url),
+        markdown,
+        canonical_url: url.to_string(),
+        language: None,
+    })
+}
+
+/// 文档信息字典中的标题
+fn document_title(document: &Document) -> Option<String> {
+    let info = match document.trailer.get(b"Info").ok()? {
+        Object::Reference(id) => document.get_dictionary(*id).ok()?,
+        Object::Dictionary(dictionary) => dictionary,
+        _ => return None,
+    };
+    lopdf::decode_text_string(info.get(b"Title").ok()?)
+        .ok()
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+type Matrix = [f32; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// PDF的矩阵乘法，`first` 先作用
+fn multiply(first: &Matrix, second: &Matrix) -> Matrix {
+    [
+        first[0] * second[0] + first[1] * second[2],
+        first[0] * second[1] + first[1] * second[3],
+        first[2] * second[0] + first[3] * second[2],
+        first[2] * second[1] + first[3] * second[3],
+        first[4] * second[0] + first[5] * second[2] + second[4],
+        first[4] * second[1] + first[5] * second[3] + second[5],
+    ]
+}
+
+fn translation(tx: f32, ty: f32) -> Matrix {
+    [1.0, 0.0, 0.0, 1.0, tx, ty]
+}
+
+fn numbers(operands: &[Object]) -> Vec<f32> {
+    operands
+        .iter()
+        .filter_map(|operand| operand.as_float().ok())
+        .collect()
+}
+
+/// 一次文字绘制
+struct TextRun {
+    text: String,
+    x: f32,
+    y: f32,
+    font_size: f32,
+}
+
+/// 执行内容流中的文字相关操作，记录每段文字的位置和字号
+fn read_page(document: &Document, number: u32, page_id: ObjectId) -> AppResult<PdfPage> {
+    let parse_error =
+        |e: lopdf::Error| AppError::parse(format!("PDF第 {} 页解析失败: {}", number, e));
+
+    // 无法识别编码的字体直接跳过，不影响其他文字
+    let encodings: BTreeMap<Vec<u8>, Encoding> = document
+        .get_page_fonts(page_id)
+        .map_err(parse_error)?
+        .into_iter()
+        .filter_map(|(name, font)| Some((name, font.get_font_encoding(document).ok()?)))
+        .collect();
+    let content = document
+        .get_page_content(page_id)
+        .and_then(|data| Content::decode(&data))
+        .map_err(parse_error)?;
+
+    let mut runs = Vec::new();
+    let mut ctm = IDENTITY;
+    let mut saved_ctm = Vec::new();
+    let mut text_matrix = IDENTITY;
+    let mut line_matrix = IDENTITY;
+    let mut leading = 0.0;
+    let mut font_size = 0.0;
+    let mut encoding = None;
+
+    for operation in &content.operations {
+        let operands = &operation.operands;
+        match operation.operator.as_str() {
+            "q" => saved_ctm.push(ctm),
+            "Q" => ctm = saved_ctm.pop().unwrap_or(IDENTITY),
+            "cm" => {
+                if let [a, b, c, d, e, f] = numbers(operands)[..] {
+                    ctm = multiply(&[a, b, c, d, e, f], &ctm);
+                }
+            }
+            "BT" => {
+                text_matrix = IDENTITY;
+                line_matrix = IDENTITY;
+            }
+            "Tf" => {
+                encoding = operands
+                    .first()
+                    .and_then(|name| name.as_name().ok())
+                    .and_then(|name| encodings.get(name));
+                font_size = operands
+                    .get(1)
+                    .and_then(|size| size.as_float().ok())
+                    .unwrap_or(0.0);
+            }
+            "TL" => leading = numbers(operands).first().copied().unwrap_or(leading),
+            "Td" | "TD" => {
+                if let [tx, ty] = numbers(operands)[..] {
+                    if operation.operator == "TD" {
+                        leading = -ty;
+                    }
+                    line_matrix = multiply(&translation(tx, ty), &line_matrix);
+                    text_matrix = line_matrix;
+                }
+            }
+            "Tm" => {
+                if let [a, b, c, d, e, f] = numbers(operands)[..] {
+                    line_matrix = [a, b, c, d, e, f];
+                    text_matrix = line_matrix;
+                }
+            }
+            "T*" | "'" | "\"" | "Tj" | "TJ" => {
+                if matches!(operation.operator.as_str(), "T*" | "'" | "\"") {
+                    line_matrix = multiply(&translation(0.0, -leading), &line_matrix);
+                    text_matrix = line_matrix;
+                }
+                if operation.operator == "T*" {
+                    continue;
+                }
+                let Some(encoding) = encoding else {
+                    continue;
+                };
+
+                let mut text = String::new();
+                collect_text(&mut text, encoding, operands);
+                if text.is_empty() {
+                    continue;
+                }
+
+                let rendering = multiply(&text_matrix, &ctm);
+                let scale = (rendering[2] * rendering[2] + rendering[3] * rendering[3]).sqrt();
+                runs.push(TextRun {
+                    x: rendering[4],
+                    y: rendering[5],
+                    font_size: font_size * scale,
+                    text: text.clone(),
+                });
+
+                // 没有字宽信息，按半个字号估算前进距离
+                let advance = text.chars().count() as f32 * font_size * 0.5;
+                text_matrix = multiply(&translation(advance, 0.0), &text_matrix);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(PdfPage {
+        number,
+        height: page_height(document, page_id),
+        lines: group_lines(runs),
+    })
+}
+
+/// MediaBox 的高度，页面没有时沿 Parent 向上查找
+fn page_height(document: &Document, page_id: ObjectId) -> f32 {
+    let mut current = document.get_dictionary(page_id).ok();
+    while let Some(dictionary) = current {
+        let media_box = dictionary
+            .get(b"MediaBox")
+            .ok()
+            .and_then(|value| document.dereference(value).ok())
+            .and_then(|(_, value)| value.as_array().ok())
+            .map(|values| numbers(values));
+        if let Some([_, bottom, _, top]) = media_box.as_deref() {
+            return (top - bottom).abs();
+        }
+        current = dictionary
+            .get(b"Parent")
+            .and_then(Object::as_reference)
+            .and_then(|parent| document.get_dictionary(parent))
+            .ok();
+    }
+    DEFAULT_PAGE_HEIGHT
+}
+
+/// 解码 Tj/TJ 的字符串，TJ 中较大的负间距视为空格
+fn collect_text(text: &mut String, encoding: &Encoding, operands: &[Object]) {
+    for operand in operands {
+        match operand {
+            Object::String(bytes, _) => {
+                if let Ok(decoded) = Document::decode_text(encoding, bytes) {
+                    text.push_str(&decoded);
+                }
+            }
+            Object::Array(items) => collect_text(text, encoding, items),
+            Object::Integer(_) | Object::Real(_)
+                if operand.as_float().is_ok_and(|offset| offset < -200.0)
+                    && !text.ends_with(' ') =>
+            {
+                text.push(' ');
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 基线相同的文字合并为一行
+fn group_lines(runs: Vec<TextRun>) -> Vec<PdfLine> {
+    let mut lines: Vec<PdfLine> = Vec::new();
+    let mut line_end = 0.0;
+
+    for run in runs {
+        let size = run.font_size.max(1.0);
+        match lines.last_mut() {
+            Some(line) if (line.y - run.y).abs() <= size * 0.4 => {
+                let gap = run.x - line_end;
+                if gap > size * 0.15
+                    && !line.text.ends_with(char::is_whitespace)
+                    && !run.text.starts_with(char::is_whitespace)
+                {
+                    line.text.push(' ');
+                }
+                line.text.push_str(&run.text);
+                line.font_size = line.font_size.max(run.font_size);
+            }
+            _ => lines.push(PdfLine {
+                text: run.text.clone(),
+                font_size: run.font_size,
+                y: run.y,
+            }),
+        }
+        line_end = run.x + run.text.chars().count() as f32 * size * 0.5;
+    }
+
+    lines
+        .into_iter()
+        .map(|line| PdfLine {
+            text: line.text.split_whitespace().collect::<Vec<_>>().join(" "),
+            ..line
+        })
+        .filter(|line| !line.text.is_empty())
+        .collect()
+}
+
+/// 把提取出的页面转换为Markdown：去掉页眉页脚，按字号识别标题，合并段落
+pub fn pages_to_markdown(pages: &[PdfPage]) -> String {
+    let running = running_lines(pages);
+    let pages: Vec<Vec<&PdfLine>> = pages
+        .iter()
+        .map(|page| {
+            page.lines
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !running.contains(&(page.number, *index)))
+                .map(|(_, line)| line)
+                .collect()
+        })
+        .collect();
+
+    let body_size = body_font_size(pages.iter().flatten().copied());
+    let mut heading_sizes: Vec<f32> = pages
+        .iter()
+        .flatten()
+        .filter(|line| is_heading(line, body_size))
+        .map(|line| round_size(line.font_size))
+        .collect();
+    heading_sizes.sort_by(|a, b| b.total_cmp(a));
+    heading_sizes.dedup();
+
+    let mut sections = Vec::new();
+    for (page, lines) in pages.iter().enumerate() {
+        let mut blocks: Vec<String> = Vec::new();
+        let mut previous: Option<(&PdfLine, bool)> = None;
+
+        for line in lines {
+            let heading = is_heading(line, body_size);
+            let continues = previous.is_some_and(|(last, last_heading)| {
+                let gap = last.y - line.y;
+                last_heading == heading
+                    && (round_size(last.font_size) - round_size(line.font_size)).abs() < 0.5
+                    && gap > 0.0
+                    && gap <= line.font_size * PARAGRAPH_GAP_RATIO
+                    && !(heading || starts_with_bullet(&line.text))
+            });
+
+            match blocks.last_mut() {
+                Some(block) if continues => join_line(block, &line.text),
+                _ if heading => {
+                    let level = heading_sizes
+                        .iter()
+                        .position(|size| *size == round_size(line.font_size))
+                        .map_or(1, |index| index + 1)
+                        .min(6);
+                    blocks.push(format!("{} {}", "#".repeat(level), line.text));
+                }
+                _ => blocks.push(bullet_to_markdown(&line.text)),
+            }
+            previous = Some((line, heading));
+        }
+
+        let number = page + 1;
+        if blocks.is_empty() {
+            sections.push(format!("<!-- page {} -->", number));
+        } else {
+            sections.push(format!(
+                "<!-- page {} -->\n\n{}",
+                number,
+                blocks.join("\n\n")
+            ));
+        }
+    }
+
+    sections.join("\n\n")
+}
+
+/// 找出页眉页脚：页面上下边缘在多数页面重复出现的行（数字视为相同），以及单独的页码
+fn running_lines(pages: &[PdfPage]) -> HashSet<(u32, usize)> {
+    let margin_candidates = |page: &PdfPage| {
+        let mut indices: Vec<usize> = (0..page.lines.len()).collect();
+        indices.sort_by(|a, b| page.lines[*b].y.total_cmp(&page.lines[*a].y));
+        let margin = page.height * MARGIN_RATIO;
+        let mut candidates: Vec<usize> = indices
+            .iter()
+            .take(MARGIN_LINES)
+            .filter(|index| page.lines[**index].y >= page.height - margin)
+            .copied()
+            .collect();
+        candidates.extend(
+            indices
+                .iter()
+                .rev()
+                .take(MARGIN_LINES)
+                .filter(|index| page.lines[**index].y <= margin),
+        );
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    };
+
+    let mut occurrences: HashMap<String, HashSet<u32>> = HashMap::new();
+    for page in pages {
+        for index in margin_candidates(page) {
+            occurrences
+                .entry(normalize_running(&page.lines[index].text))
+                .or_default()
+                .insert(page.number);
+        }
+    }
+
+    let threshold = pages.len().div_ceil(2).max(2);
+    let mut running = HashSet::new();
+    for page in pages {
+        for index in margin_candidates(page) {
+            let text = &page.lines[index].text;
+            let repeated = occurrences
+                .get(&normalize_running(text))
+                .is_some_and(|pages| pages.len() >= threshold);
+            if repeated || is_page_number(text) {
+                running.insert((page.number, index));
+            }
+        }
+    }
+    running
+}
+
+fn normalize_running(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii_digit() { '#' } else { c })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// 例如 "3"、"- 3 -"、"Page 3 of 10"、"第 3 页"
+fn is_page_number(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    let rest = ["page", "of", "第", "页"]
+        .iter()
+        .fold(lower, |rest, word| rest.replace(word, ""));
+    text.chars().any(|c| c.is_ascii_digit())
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_whitespace() || matches!(c, '-' | '/' | '|' | '–'))
+}
+
+fn round_size(size: f32) -> f32 {
+    (size * 2.0).round() / 2.0
+}
+
+/// 按字符数加权出现最多的字号
+fn body_font_size<'a>(lines: impl Iterator<Item = &'a PdfLine>) -> f32 {
+    let mut weights: Vec<(f32, usize)> = Vec::new();
+    for line in lines {
+        let size = round_size(line.font_size);
+        let chars = line.text.chars().count();
+        match weights.iter_mut().find(|(known, _)| *known == size) {
+            Some((_, weight)) => *weight += chars,
+            None => weights.push((size, chars)),
+        }
+    }
+    weights
+        .into_iter()
+        .max_by_key(|(_, weight)| *weight)
+        .map_or(0.0, |(size, _)| size)
+}
+
+fn is_heading(line: &PdfLine, body_size: f32) -> bool {
+    body_size > 0.0
+        && round_size(line.font_size) >= body_size * HEADING_SIZE_RATIO
+        && line.text.chars().count() <= MAX_HEADING_LENGTH
+}
+
+fn starts_with_bullet(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| BULLETS.contains(&c)) && chars.next() == Some(' ')
+}
+
+fn bullet_to_markdown(text: &str) -> String {
+    if starts_with_bullet(text) {
+        let rest: String = text.chars().skip(2).collect();
+        format!("- {}", rest.trim_start())
+    } else {
+        text.to_string()
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x3000..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF)
+}
+
+/// 把下一行接到段落末尾：去掉断词连字符，中日韩文字之间不加空格
+fn join_line(block: &mut String, text: &str) {
+    let last = block.chars().last();
+    let next = text.chars().next();
+
+    let hyphenated = block.ends_with('-')
+        && block.chars().rev().nth(1).is_some_and(char::is_alphabetic)
+        && next.is_some_and(char::is_lowercase);
+    if hyphenated {
+        block.pop();
+    } else if !(last.is_some_and(is_cjk) || next.is_some_and(is_cjk)) {
+        block.push(' ');
+    }
+    block.push_str(text);
+}
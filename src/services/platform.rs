@@ -0,0 +1,38 @@
+//! 浏览器和本机环境下的时间、等待与日志。
+//! 浏览器中使用 JS 接口；本机（集成测试）中 JS 接口不可用，改用标准库和 tokio
+
+/// 输出调试日志
+#[cfg(target_arch = "wasm32")]
+pub fn log(message: &str) {
+    web_sys::console::log_1(&message.into());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn log(message: &str) {
+    eprintln!("{}", message);
+}
+
+/// 当前时间戳（毫秒）
+#[cfg(target_arch = "wasm32")]
+pub fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+        .unwrap_or_default()
+}
+
+/// 等待指定的毫秒数
+#[cfg(target_arch = "wasm32")]
+pub async fn sleep_ms(duration_ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(duration_ms).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sleep_ms(duration_ms: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(duration_ms.into())).await;
+}
@@ -1,7 +1,16 @@
+use super::platform::log;
+use crate::error::AppResult;
 use crate::services::{
-    content_processor::ContentProcessor, deeplx_service::DeepLXService, jina_service::JinaService,
+    content_processor::ContentProcessor,
+    extractor::{create_extractor, Extractor},
+    incremental::split_source_paragraphs,
+    translator::{
+        create_engine_translator, create_translator, find_placeholders, merge_engine_usage,
+        translate_chunks_concurrently, EngineUsage, Translator,
+    },
 };
-use crate::types::api_types::AppConfig;
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use futures::future::join_all;
 
 #[derive(Debug, Clone)]
 pub struct PreviewContent {
@@ -29,16 +38,93 @@ impl Default for PreviewOptions {
     }
 }
 
+/// 多引擎对比中单个引擎的译文
+#[derive(Debug, Clone)]
+pub struct ComparisonColumn {
+    pub engine: TranslationEngine,
+    /// 与原文段落一一对应的译文段落
+    pub paragraphs: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// 多引擎对比结果
+#[derive(Debug, Clone)]
+pub struct ComparisonContent {
+    /// 提取器给出的文档标题，未提供时为空
+    pub title: String,
+    pub original_text: String,
+    pub original_paragraphs: Vec<String>,
+    pub columns: Vec<ComparisonColumn>,
+}
+
+impl ComparisonContent {
+    /// 第一个成功的引擎所在列，作为默认选择
+    pub fn default_column(&self) -> usize {
+        self.columns
+            .iter()
+            .position(|column| column.error.is_none())
+            .unwrap_or(0)
+    }
+
+    /// 每段默认选择第一个成功的引擎
+    pub fn default_choices(&self) -> Vec<usize> {
+        vec![self.default_column(); self.original_paragraphs.len()]
+    }
+
+    /// 按每段选择的列拼出最终译文，无效的选择改用默认列
+    pub fn compose(&self, choices: &[usize]) -> String {
+        (0..self.original_paragraphs.len())
+            .map(|index| self.paragraph(index, self.resolve_choice(choices, index)))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// 每段实际采用的引擎
+    pub fn chosen_engines(&self, choices: &[usize]) -> Vec<TranslationEngine> {
+        (0..self.original_paragraphs.len())
+            .map(|index| self.columns[self.resolve_choice(choices, index)].engine)
+            .collect()
+    }
+
+    /// 按引擎统计采用的段落数
+    pub fn engine_usage(&self, choices: &[usize]) -> Vec<EngineUsage> {
+        merge_engine_usage(
+            self.chosen_engines(choices)
+                .into_iter()
+                .map(|engine| EngineUsage { engine, chunks: 1 }),
+        )
+    }
+
+    fn resolve_choice(&self, choices: &[usize], index: usize) -> usize {
+        match choices.get(index) {
+            Some(&column)
+                if column < self.columns.len() && self.columns[column].error.is_none() =>
+            {
+                column
+            }
+            _ => self.default_column(),
+        }
+    }
+
+    fn paragraph(&self, index: usize, column: usize) -> String {
+        self.columns
+            .get(column)
+            .and_then(|column| column.paragraphs.get(index))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
 pub struct PreviewService {
-    jina_service: JinaService,
-    deeplx_service: DeepLXService,
+    extractor: Box<dyn Extractor>,
+    translator: Box<dyn Translator>,
 }
 
 impl PreviewService {
     pub fn new(config: &AppConfig) -> Self {
         Self {
-            jina_service: JinaService::new(config),
-            deeplx_service: DeepLXService::new(config),
+            extractor: create_extractor(config),
+            translator: create_translator(config),
         }
     }
 
@@ -49,22 +135,23 @@ impl PreviewService {
         config: &AppConfig,
         options: &PreviewOptions,
     ) -> Result<PreviewContent, String> {
-        web_sys::console::log_1(&"=== 开始生成翻译预览 ===".into());
-        web_sys::console::log_1(&format!("URL: {}", url).into());
+        log("=== 开始生成翻译预览 ===");
+        log(&format!("URL: {}", url));
 
         // 提取完整内容
         let full_content = self
-            .jina_service
-            .extract_content(url, config)
+            .extractor
+            .extract(url)
             .await
-            .map_err(|e| format!("无法提取网页内容: {}", e))?;
+            .map_err(|e| format!("无法提取网页内容: {}", e))?
+            .document();
 
-        web_sys::console::log_1(&format!("完整内容长度: {} 字符", full_content.len()).into());
+        log(&format!("完整内容长度: {} 字符", full_content.len()));
 
         // 提取预览部分
         let preview_text = self.extract_preview_text(&full_content, options);
 
-        web_sys::console::log_1(&format!("预览内容长度: {} 字符", preview_text.len()).into());
+        log(&format!("预览内容长度: {} 字符", preview_text.len()));
 
         if preview_text.trim().is_empty() {
             return Err("无法提取有效的预览内容".to_string());
@@ -76,20 +163,17 @@ impl PreviewService {
 
         let protection_stats = content_processor.get_protection_stats();
         if protection_stats.total_blocks() > 0 {
-            web_sys::console::log_1(
-                &format!("代码块保护: {}", protection_stats.get_summary()).into(),
-            );
+            log(&format!("代码块保护: {}", protection_stats.get_summary()));
         }
 
         // 翻译预览内容
-        web_sys::console::log_1(&"开始翻译预览内容...".into());
+        log("开始翻译预览内容...");
         let translated_protected = self
-            .deeplx_service
+            .translator
             .translate(
                 &protected_content,
                 &config.default_source_lang,
                 &config.default_target_lang,
-                config,
             )
             .await
             .map_err(|e| format!("翻译失败: {}", e))?;
@@ -97,7 +181,7 @@ impl PreviewService {
         // 恢复代码块
         let translated_text = content_processor.restore_code_blocks(&translated_protected);
 
-        web_sys::console::log_1(&"预览翻译完成".into());
+        log("预览翻译完成");
 
         // 统计信息
         let word_count = self.count_words(&preview_text);
@@ -112,6 +196,97 @@ impl PreviewService {
         })
     }
 
+    /// 用多个引擎逐段翻译完整文档，译文与原文段落一一对应以便逐段比较
+    pub async fn generate_comparison(
+        &self,
+        url: &str,
+        config: &AppConfig,
+        engines: &[TranslationEngine],
+    ) -> Result<ComparisonContent, String> {
+        log("=== 开始多引擎对比翻译 ===");
+
+        if engines.is_empty() {
+            return Err("请至少选择一个翻译引擎".to_string());
+        }
+
+        let extracted = self
+            .extractor
+            .extract(url)
+            .await
+            .map_err(|e| format!("无法提取网页内容: {}", e))?;
+        let full_content = extracted.document();
+
+        if full_content.trim().is_empty() {
+            return Err("提取的内容为空".to_string());
+        }
+
+        // 先保护代码块再分段，避免代码块内部的空行把一段拆开
+        let mut content_processor = ContentProcessor::new();
+        let protected_content = content_processor.protect_code_blocks(&full_content);
+        let protected_paragraphs = split_source_paragraphs(&protected_content);
+
+        let translators: Vec<Box<dyn Translator>> = engines
+            .iter()
+            .map(|engine| create_engine_translator(*engine, config))
+            .collect();
+
+        log(&format!(
+            "共 {} 段，使用 {} 个引擎同时翻译",
+            protected_paragraphs.len(),
+            translators.len()
+        ));
+
+        let results = join_all(translators.iter().map(|translator| {
+            translate_paragraphs(
+                translator.as_ref(),
+                &protected_paragraphs,
+                &config.default_source_lang,
+                &config.default_target_lang,
+                config.chunk_concurrency,
+            )
+        }))
+        .await;
+
+        let columns: Vec<ComparisonColumn> = engines
+            .iter()
+            .zip(results)
+            .map(|(engine, result)| match result {
+                Ok(translated) => ComparisonColumn {
+                    engine: *engine,
+                    paragraphs: translated
+                        .iter()
+                        .map(|paragraph| content_processor.restore_code_blocks(paragraph))
+                        .collect(),
+                    error: None,
+                },
+                Err(e) => {
+                    log(&format!("{} 翻译失败: {}", engine.display_name(), e));
+                    ComparisonColumn {
+                        engine: *engine,
+                        paragraphs: Vec::new(),
+                        error: Some(e.to_string()),
+                    }
+                }
+            })
+            .collect();
+
+        if columns.iter().all(|column| column.error.is_some()) {
+            return Err("所有引擎均翻译失败".to_string());
+        }
+
+        log("多引擎对比翻译完成");
+
+        Ok(ComparisonContent {
+            original_paragraphs: protected_paragraphs
+                .iter()
+                .map(|paragraph| content_processor.restore_code_blocks(paragraph))
+                .collect(),
+            title: extracted.title,
+            original_text: full_content,
+            columns,
+        })
+    }
+
     /// 提取预览文本
     fn extract_preview_text(&self, content: &str, options: &PreviewOptions) -> String {
         let mut preview = String::new();
@@ -240,7 +415,7 @@ impl PreviewService {
 
     /// 快速验证URL和配置
     pub async fn quick_validate(&self, url: &str, config: &AppConfig) -> Result<String, String> {
-        web_sys::console::log_1(&"快速验证URL和配置...".into());
+        log("快速验证URL和配置...");
 
         // 尝试提取很少的内容进行测试
         let options = PreviewOptions {
@@ -259,3 +434,33 @@ impl PreviewService {
         }
     }
 }
+
+/// 逐段翻译，结果与 `paragraphs` 一一对应，不受引擎合并、拆分段落或分块的影响。
+/// 只含代码块占位符的段落不发送请求
+pub async fn translate_paragraphs(
+    translator: &dyn Translator,
+    paragraphs: &[String],
+    source_lang: &str,
+    target_lang: &str,
+    concurrency: usize,
+) -> AppResult<Vec<String>> {
+    translate_chunks_concurrently(paragraphs, concurrency, 1, |_, paragraph| async move {
+        if is_placeholder_only(paragraph) {
+            return Ok(paragraph.to_string());
+        }
+        let translated = translator
+            .translate(paragraph, source_lang, target_lang)
+            .await?;
+        Ok(translated.trim().to_string())
+    })
+    .await
+}
+
+fn is_placeholder_only(paragraph: &str) -> bool {
+    let remaining = find_placeholders(paragraph)
+        .iter()
+        .fold(paragraph.to_string(), |text, placeholder| {
+            text.replace(placeholder, "")
+        });
+    remaining.trim().is_empty()
+}
@@ -1,6 +1,5 @@
-use js_sys::Promise;
+use super::platform::{log, now_ms, sleep_ms};
 use std::sync::Arc;
-use wasm_bindgen_futures::JsFuture;
 
 pub struct RateLimiter {
     max_requests: u32,
@@ -17,59 +16,33 @@ impl RateLimiter {
         }
     }
 
+    /// 等待直到时间窗口内还有请求额度，并记录这次请求的时间
     pub async fn acquire(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let now = js_sys::Date::now(); // 获取当前时间戳（毫秒）
+        let max_requests = self.max_requests.max(1) as usize;
+        let window = self.window_duration_ms as f64;
 
-        // 清理过期的请求记录
-        {
-            let mut requests = self.last_requests.lock().unwrap();
-            requests.retain(|&time| now - time < self.window_duration_ms as f64);
-        }
+        loop {
+            let now = now_ms(); // 获取当前时间戳（毫秒）
 
-        // 检查是否超过速率限制
-        let current_count = {
-            let requests = self.last_requests.lock().unwrap();
-            requests.len() as u32
-        };
-
-        if current_count >= self.max_requests {
-            // 计算需要等待的时间
-            let oldest_request = {
-                let requests = self.last_requests.lock().unwrap();
-                requests.first().copied()
-            };
+            // 清理过期的请求记录；检查和记录之间没有等待，并发调用不会同时占用最后一个额度
+            let wait_time_ms = {
+                let mut requests = self.last_requests.lock().unwrap();
+                requests.retain(|&time| now - time < window);
 
-            if let Some(oldest) = oldest_request {
-                let wait_time_ms = self.window_duration_ms as f64 - (now - oldest);
-                if wait_time_ms > 0.0 {
-                    // 最少等待100ms，避免过长等待
-                    let actual_wait = std::cmp::min(wait_time_ms as u32, 500);
-                    web_sys::console::log_1(
-                        &format!("速率限制触发，等待 {}ms", actual_wait).into(),
-                    );
-                    self.sleep_ms(actual_wait).await;
+                if requests.len() < max_requests {
+                    requests.push(now);
+                    return Ok(());
                 }
-            }
-        }
-
-        // 记录这次请求
-        {
-            let mut requests = self.last_requests.lock().unwrap();
-            requests.push(now);
-        }
 
-        Ok(())
-    }
-
-    async fn sleep_ms(&self, duration_ms: u32) {
-        let promise = Promise::new(&mut |resolve, _| {
-            web_sys::window()
-                .unwrap()
-                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, duration_ms as i32)
-                .unwrap();
-        });
+                // 等到最早的一次请求移出时间窗口
+                let oldest = requests.iter().copied().fold(now, f64::min);
+                window - (now - oldest)
+            };
 
-        let _ = JsFuture::from(promise).await;
+            let actual_wait = (wait_time_ms.ceil() as u32).max(1);
+            log(&format!("速率限制触发，等待 {}ms", actual_wait));
+            sleep_ms(actual_wait).await;
+        }
     }
 }
 
@@ -91,13 +64,13 @@ impl Default for RetryConfig {
     }
 }
 
-pub async fn retry_with_backoff<F, T, E>(
+pub async fn retry_with_backoff<'a, F, T, E>(
     operation: F,
     config: &RetryConfig,
     rate_limiter: &RateLimiter,
 ) -> Result<T, E>
 where
-    F: Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, E>>>>,
+    F: Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, E>> + 'a>>,
     E: std::fmt::Display,
 {
     let mut delay_ms = config.base_delay_ms;
@@ -105,46 +78,33 @@ where
     for attempt in 1..=config.max_attempts {
         // 应用速率限制
         if let Err(e) = rate_limiter.acquire().await {
-            web_sys::console::log_1(&format!("速率限制错误: {}", e).into());
+            log(&format!("速率限制错误: {}", e));
         }
 
-        web_sys::console::log_1(&format!("尝试请求 (第 {} 次)", attempt).into());
+        log(&format!("尝试请求 (第 {} 次)", attempt));
 
         let result = operation().await;
 
         match result {
             Ok(value) => {
                 if attempt > 1 {
-                    web_sys::console::log_1(&format!("重试成功 (第 {} 次尝试)", attempt).into());
+                    log(&format!("重试成功 (第 {} 次尝试)", attempt));
                 }
                 return Ok(value);
             }
             Err(e) => {
                 if attempt == config.max_attempts {
-                    web_sys::console::log_1(&format!("所有重试都失败了: {}", e).into());
+                    log(&format!("所有重试都失败了: {}", e));
                     return Err(e);
                 }
 
-                web_sys::console::log_1(
-                    &format!(
-                        "第 {} 次尝试失败: {}，等待 {}ms 后重试",
-                        attempt, e, delay_ms
-                    )
-                    .into(),
-                );
+                log(&format!(
+                    "第 {} 次尝试失败: {}，等待 {}ms 后重试",
+                    attempt, e, delay_ms
+                ));
 
                 // 指数退避延迟
-                let promise = Promise::new(&mut |resolve, _| {
-                    web_sys::window()
-                        .unwrap()
-                        .set_timeout_with_callback_and_timeout_and_arguments_0(
-                            &resolve,
-                            delay_ms as i32,
-                        )
-                        .unwrap();
-                });
-
-                let _ = JsFuture::from(promise).await;
+                sleep_ms(delay_ms).await;
 
                 delay_ms = std::cmp::min(
                     (delay_ms as f64 * config.backoff_multiplier) as u32,
@@ -0,0 +1,790 @@
+use super::extractor::{extract_markdown_links, ExtractFuture, ExtractedContent, Extractor};
+use super::platform::log;
+use super::rate_limiter::{retry_with_backoff, RateLimiter, RetryConfig};
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::AppConfig;
+use ego_tree::{NodeId, NodeRef};
+use reqwest::Client;
+use scraper::{ElementRef, Html, Node, Selector};
+use std::collections::HashMap;
+use url::Url;
+
+/// 不参与正文输出的标签
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "iframe", "svg", "canvas", "template", "form", "button",
+    "input", "select", "textarea", "nav", "aside", "footer",
+];
+
+/// class或id包含这些词的元素通常不是正文
+const UNLIKELY_PATTERNS: &[&str] = &[
+    "banner",
+    "breadcrumb",
+    "combx",
+    "comment",
+    "community",
+    "cookie",
+    "disqus",
+    "footer",
+    "gdpr",
+    "header",
+    "menu",
+    "nav",
+    "pager",
+    "pagination",
+    "popup",
+    "related",
+    "remark",
+    "replies",
+    "share",
+    "shoutbox",
+    "sidebar",
+    "skyscraper",
+    "social",
+    "sponsor",
+];
+
+/// 与 `UNLIKELY_PATTERNS` 同时出现时仍可能是正文
+const MAYBE_PATTERNS: &[&str] = &["and", "article", "body", "column", "content", "main"];
+
+const POSITIVE_PATTERNS: &[&str] = &[
+    "article", "blog", "body", "content", "doc", "entry", "main", "markdown", "page", "post",
+    "prose", "story", "text",
+];
+
+const NEGATIVE_PATTERNS: &[&str] = &[
+    "-ad-", "banner", "comment", "contact", "footer", "hidden", "masthead", "menu", "meta", "nav",
+    "promo", "related", "share", "sidebar", "sponsor", "tags", "toc", "widget",
+];
+
+/// 不计入正文的ARIA角色
+const UNLIKELY_ROLES: &[&str] = &[
+    "banner",
+    "complementary",
+    "contentinfo",
+    "menu",
+    "menubar",
+    "navigation",
+];
+
+/// 构成段落的最短文本长度
+const MIN_PARAGRAPH_LENGTH: usize = 25;
+
+/// 列表项和代码块在Markdown中的缩进，先用占位字符标记，最后统一替换为空格
+const INDENT: char = '\u{1}';
+
+/// 获取到的页面
+#[derive(Debug, Clone)]
+pub struct FetchedPage {
+    pub content_type: String,
+    pub body: String,
+}
+
+/// 内置的网页提取器：直接获取HTML，按正文评分找出主体内容并转换为Markdown
+pub struct ReadabilityExtractor {
+    client: Client,
+    rate_limiter: RateLimiter,
+    cors_proxy: String,
+}
+
+impl ReadabilityExtractor {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: Client::new(),
+            rate_limiter: RateLimiter::new(config.max_requests_per_second, 1000),
+            cors_proxy: config.extractor.cors_proxy.clone(),
+        }
+    }
+
+    pub async fn extract_content(&self, url: &str) -> AppResult<ExtractedContent> {
+        log(&format!("内置提取器请求: {}", self.request_url(url)));
+
+        let retry_config = RetryConfig::default();
+        let page = retry_with_backoff(
+            || Box::pin(self.fetch_page(url)),
+            &retry_config,
+            &self.rate_limiter,
+        )
+        .await?;
+
+        let content = parse_page(&page, url);
+        if content.markdown.trim().is_empty() {
+            return Err(AppError::extraction("未能从页面中找到正文内容"));
+        }
+
+        log(&format!(
+            "内置提取完成: 标题 \"{}\"，正文 {} 字符，{} 个链接",
+            content.title,
+            content.markdown.len(),
+            content.links.len()
+        ));
+
+        Ok(content)
+    }
+
+    /// 获取页面原文
+    pub async fn fetch_page(&self, url: &str) -> AppResult<FetchedPage> {
+        let response = self
+            .client
+            .get(self.request_url(url))
+            .header(
+                "Accept",
+                "text/html, application/xhtml+xml, text/markdown, */*",
+            )
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::network(format!("获取页面失败: {}. 浏览器中请确认CORS代理可用", e))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("获取页面失败: {} - {}", status, body);
+            return Err(match status.as_u16() {
+                429 => AppError::rate_limit(message),
+                _ => AppError::extraction(message),
+            });
+        }
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AppError::network(format!("读取页面内容失败: {}", e)))?;
+
+        Ok(FetchedPage { content_type, body })
+    }
+
+    /// 浏览器中经由CORS代理访问，原生环境直接访问
+    fn request_url(&self, url: &str) -> String {
+        if cfg!(target_arch = "wasm32") {
+            proxied_url(&self.cors_proxy, url)
+        } else {
+            url.to_string()
+        }
+    }
+}
+
+impl Extractor for ReadabilityExtractor {
+    fn extract<'a>(&'a self, url: &'a str) -> ExtractFuture<'a> {
+        Box::pin(self.extract_content(url))
+    }
+
+    fn name(&self) -> &'static str {
+        "内置提取器"
+    }
+}
+
+/// 拼接CORS代理地址，`{url}` 会被替换为编码后的目标地址，没有占位时追加在末尾
+pub fn proxied_url(proxy: &str, url: &str) -> String {
+    let proxy = proxy.trim();
+    if proxy.is_empty() {
+        return url.to_string();
+    }
+
+    let encoded = urlencoding::encode(url);
+    if proxy.contains("{url}") {
+        proxy.replace("{url}", &encoded)
+    } else {
+        format!("{}{}", proxy, encoded)
+    }
+}
+
+/// 按内容类型解析页面，纯文本和Markdown原样作为正文
+pub fn parse_page(page: &FetchedPage, url: &str) -> ExtractedContent {
+    let content_type = page.content_type.to_lowercase();
+    if content_type.contains("markdown") || content_type.starts_with("text/plain") {
+        let markdown = page.body.trim().to_string();
+        return ExtractedContent {
+            title: markdown
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("# "))
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            links: extract_markdown_links(&markdown, url),
+            canonical_url: url.to_string(),
+            language: None,
+            markdown,
+        };
+    }
+
+    extract_from_html(&page.body, url)
+}
+
+/// 从HTML中找出正文并转换为Markdown
+pub fn extract_from_html(html: &str, url: &str) -> ExtractedContent {
+    let document = Html::parse_document(html);
+    let base = Url::parse(url).ok();
+
+    let canonical_url = select_attr(&document, "link[rel~=\"canonical\"]", "href")
+        .or_else(|| select_attr(&document, "meta[property=\"og:url\"]", "content"))
+        .and_then(|href| resolve_url(base.as_ref(), &href))
+        .unwrap_or_else(|| url.to_string());
+    let base = Url::parse(&canonical_url).ok().or(base);
+
+    let title = select_attr(&document, "meta[property=\"og:title\"]", "content")
+        .or_else(|| select_text(&document, "title"))
+        .or_else(|| select_text(&document, "h1"))
+        .unwrap_or_default();
+    let language = select_attr(&document, "html", "lang").filter(|lang| !lang.is_empty());
+
+    let renderer = MarkdownRenderer {
+        base: base.as_ref(),
+    };
+    let markdown = match find_main_content(&document) {
+        Some(nodes) => {
+            let rendered: String = nodes
+                .into_iter()
+                .map(|node| renderer.render_node(node))
+                .collect();
+            finish_markdown(&rendered)
+        }
+        None => String::new(),
+    };
+
+    ExtractedContent {
+        title,
+        links: extract_markdown_links(&markdown, &canonical_url),
+        markdown,
+        canonical_url,
+        language,
+    }
+}
+
+/// 按段落评分找出正文所在的元素，返回得分最高的元素及其应一并保留的兄弟元素
+fn find_main_content(document: &Html) -> Option<Vec<NodeRef<'_, Node>>> {
+    let paragraph_selector = Selector::parse("p, pre, td, blockquote, div").expect("有效的选择器");
+    let mut scores: HashMap<NodeId, f64> = HashMap::new();
+    // 按首次出现的顺序记录候选元素，保证同分时结果稳定
+    let mut candidates: Vec<NodeId> = Vec::new();
+
+    for element in document.select(&paragraph_selector) {
+        if element.value().name() == "div" && has_block_children(element) {
+            continue;
+        }
+        if is_excluded(element)
+            || element
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(is_excluded)
+        {
+            continue;
+        }
+
+        let text = collapse_whitespace(&element.text().collect::<String>());
+        let length = text.trim().chars().count();
+        if length < MIN_PARAGRAPH_LENGTH {
+            continue;
+        }
+
+        let commas = text.matches([',', '，', '、']).count() as f64;
+        let content_score = 1.0 + commas + (length as f64 / 100.0).min(3.0);
+
+        for (level, ancestor) in element
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .take(3)
+            .enumerate()
+        {
+            let divider = match level {
+                0 => 1.0,
+                1 => 2.0,
+                _ => level as f64 * 3.0,
+            };
+            let score = scores.entry(ancestor.id()).or_insert_with(|| {
+                candidates.push(ancestor.id());
+                initial_score(ancestor)
+            });
+            *score += content_score / divider;
+        }
+    }
+
+    let (top_id, top_score) = candidates
+        .iter()
+        .filter_map(|id| {
+            let element = ElementRef::wrap(document.tree.get(*id)?)?;
+            Some((*id, scores[id] * (1.0 - link_density(element))))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .or_else(|| {
+            let body = Selector::parse("body").expect("有效的选择器");
+            document
+                .select(&body)
+                .next()
+                .map(|element| (element.id(), 0.0))
+        })?;
+
+    let top = document.tree.get(top_id)?;
+    let Some(parent) = top
+        .parent()
+        .filter(|parent| ElementRef::wrap(*parent).is_some())
+    else {
+        return Some(vec![top]);
+    };
+
+    // 得分接近的兄弟元素多半是被拆开的同一篇正文
+    let threshold = (top_score * 0.2).max(10.0);
+    let nodes = parent
+        .children()
+        .filter(|sibling| {
+            if sibling.id() == top_id {
+                return true;
+            }
+            let Some(element) = ElementRef::wrap(*sibling) else {
+                return false;
+            };
+            if is_excluded(element) {
+                return false;
+            }
+            let score =
+                scores.get(&sibling.id()).copied().unwrap_or(0.0) * (1.0 - link_density(element));
+            if score >= threshold {
+                return true;
+            }
+            if element.value().name() == "p" {
+                let length = collapse_whitespace(&element.text().collect::<String>())
+                    .trim()
+                    .chars()
+                    .count();
+                return length > 80 && link_density(element) < 0.25;
+            }
+            false
+        })
+        .collect();
+
+    Some(nodes)
+}
+
+fn initial_score(element: ElementRef) -> f64 {
+    let tag_score = match element.value().name() {
+        "article" => 10.0,
+        "div" | "main" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    tag_score + class_weight(element)
+}
+
+/// 根据class和id判断是否像正文
+fn class_weight(element: ElementRef) -> f64 {
+    let mut weight = 0.0;
+    for value in [element.value().attr("class"), element.value().attr("id")]
+        .into_iter()
+        .flatten()
+    {
+        let value = value.to_lowercase();
+        if NEGATIVE_PATTERNS.iter().any(|p| value.contains(p)) {
+            weight -= 25.0;
+        }
+        if POSITIVE_PATTERNS.iter().any(|p| value.contains(p)) {
+            weight += 25.0;
+        }
+    }
+    weight
+}
+
+/// 导航、页脚、隐藏元素等不计入正文
+fn is_excluded(element: ElementRef) -> bool {
+    let value = element.value();
+    let name = value.name();
+    if SKIPPED_TAGS.contains(&name) || is_hidden(element) {
+        return true;
+    }
+    if value
+        .attr("role")
+        .is_some_and(|role| UNLIKELY_ROLES.contains(&role))
+    {
+        return true;
+    }
+    if matches!(
+        name,
+        "html" | "body" | "main" | "article" | "a" | "pre" | "code"
+    ) {
+        return false;
+    }
+
+    let signature = format!(
+        "{} {}",
+        value.attr("class").unwrap_or_default(),
+        value.attr("id").unwrap_or_default()
+    )
+    .to_lowercase();
+    UNLIKELY_PATTERNS.iter().any(|p| signature.contains(p))
+        && !MAYBE_PATTERNS.iter().any(|p| signature.contains(p))
+}
+
+fn is_hidden(element: ElementRef) -> bool {
+    let value = element.value();
+    value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+        || value.attr("style").is_some_and(|style| {
+            let style = style.replace(' ', "").to_lowercase();
+            style.contains("display:none") || style.contains("visibility:hidden")
+        })
+}
+
+fn has_block_children(element: ElementRef) -> bool {
+    element
+        .children()
+        .filter_map(ElementRef::wrap)
+        .any(|child| {
+            matches!(
+                child.value().name(),
+                "p" | "div"
+                    | "pre"
+                    | "table"
+                    | "ul"
+                    | "ol"
+                    | "blockquote"
+                    | "section"
+                    | "article"
+                    | "h1"
+                    | "h2"
+                    | "h3"
+                    | "h4"
+                    | "h5"
+                    | "h6"
+            )
+        })
+}
+
+/// 链接文本在元素文本中所占的比例
+fn link_density(element: ElementRef) -> f64 {
+    let total = element.text().map(|text| text.trim().len()).sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let link_selector = Selector::parse("a").expect("有效的选择器");
+    let links = element
+        .select(&link_selector)
+        .flat_map(|link| link.text())
+        .map(|text| text.trim().len())
+        .sum::<usize>();
+    links as f64 / total as f64
+}
+
+fn select_attr(document: &Html, selector: &str, attr: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .find_map(|element| element.value().attr(attr))
+        .map(|value| value.trim().to_string())
+}
+
+fn select_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .map(|element| collapse_whitespace(&element.text().collect::<String>()))
+        .map(|text| text.trim().to_string())
+        .find(|text| !text.is_empty())
+}
+
+fn resolve_url(base: Option<&Url>, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() {
+        return None;
+    }
+    match base {
+        Some(base) => base.join(href).ok().map(|url| url.to_string()),
+        None => Some(href.to_string()),
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                result.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            result.push(c);
+            last_was_space = false;
+        }
+    }
+    result
+}
+
+/// 将块内的行内Markdown整理为一行
+fn inline_text(text: &str) -> String {
+    collapse_whitespace(text).trim().to_string()
+}
+
+struct MarkdownRenderer<'a> {
+    base: Option<&'a Url>,
+}
+
+impl MarkdownRenderer<'_> {
+    fn render_children(&self, element: ElementRef) -> String {
+        element
+            .children()
+            .map(|child| self.render_node(child))
+            .collect()
+    }
+
+    fn render_node(&self, node: NodeRef<Node>) -> String {
+        match node.value() {
+            Node::Text(text) => collapse_whitespace(text),
+            Node::Element(_) => ElementRef::wrap(node)
+                .map(|element| self.render_element(element))
+                .unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    fn render_element(&self, element: ElementRef) -> String {
+        if is_excluded(element) {
+            return String::new();
+        }
+
+        let name = element.value().name();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                let text = inline_text(&self.render_children(element));
+                if text.is_empty() {
+                    String::new()
+                } else {
+                    format!("\n\n{} {}\n\n", "#".repeat(level), text)
+                }
+            }
+            "br" => "\n".to_string(),
+            "hr" => "\n\n---\n\n".to_string(),
+            "strong" | "b" => wrap_inline(&self.render_children(element), "**"),
+            "em" | "i" => wrap_inline(&self.render_children(element), "*"),
+            "del" | "s" => wrap_inline(&self.render_children(element), "~~"),
+            "code" | "kbd" | "samp" => {
+                let code = collapse_whitespace(&element.text().collect::<String>());
+                let fence = if code.contains('`') { "``" } else { "`" };
+                format!("{}{}{}", fence, code.trim(), fence)
+            }
+            "a" => self.render_link(element),
+            "img" => self.render_image(element),
+            "pre" => render_code_block(element),
+            "ul" | "ol" => self.render_list(element, name == "ol"),
+            "blockquote" => {
+                let inner = normalize_block(&self.render_children(element));
+                let quoted: Vec<String> = inner
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            ">".to_string()
+                        } else {
+                            format!("> {}", line)
+                        }
+                    })
+                    .collect();
+                format!("\n\n{}\n\n", quoted.join("\n"))
+            }
+            "table" => self.render_table(element),
+            "p" | "div" | "section" | "article" | "main" | "header" | "figure" | "figcaption"
+            | "details" | "summary" | "dl" | "dt" | "dd" | "address" | "body" => {
+                format!("\n\n{}\n\n", self.render_children(element))
+            }
+            _ => self.render_children(element),
+        }
+    }
+
+    fn render_link(&self, element: ElementRef) -> String {
+        let text = inline_text(&self.render_children(element));
+        let href = element.value().attr("href").unwrap_or_default().trim();
+        if text.is_empty() {
+            return String::new();
+        }
+        if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+            return text;
+        }
+        match resolve_url(self.base, href) {
+            Some(url) => format!("[{}]({})", text, url),
+            None => text,
+        }
+    }
+
+    fn render_image(&self, element: ElementRef) -> String {
+        let value = element.value();
+        let src = value
+            .attr("src")
+            .filter(|src| !src.starts_with("data:"))
+            .or_else(|| value.attr("data-src"))
+            .unwrap_or_default();
+        match resolve_url(self.base, src) {
+            Some(url) => format!(
+                "![{}]({})",
+                inline_text(value.attr("alt").unwrap_or_default()),
+                url
+            ),
+            None => String::new(),
+        }
+    }
+
+    fn render_list(&self, element: ElementRef, ordered: bool) -> String {
+        let start = element
+            .value()
+            .attr("start")
+            .and_then(|start| start.parse::<usize>().ok())
+            .unwrap_or(1);
+
+        let items: Vec<String> = element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "li")
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let body = normalize_block(&self.render_children(item));
+                if body.is_empty() {
+                    return None;
+                }
+                let marker = if ordered {
+                    format!("{}. ", start + index)
+                } else {
+                    "- ".to_string()
+                };
+                let indent: String = std::iter::repeat_n(INDENT, marker.len()).collect();
+                let lines: Vec<String> = body
+                    .lines()
+                    .enumerate()
+                    .map(|(line_index, line)| match (line_index, line.is_empty()) {
+                        (0, _) => format!("{}{}", marker, line),
+                        (_, true) => String::new(),
+                        _ => format!("{}{}", indent, line),
+                    })
+                    .collect();
+                Some(lines.join("\n"))
+            })
+            .collect();
+
+        format!("\n\n{}\n\n", items.join("\n"))
+    }
+
+    fn render_table(&self, element: ElementRef) -> String {
+        let row_selector = Selector::parse("tr").expect("有效的选择器");
+        let rows: Vec<Vec<String>> = element
+            .select(&row_selector)
+            .map(|row| {
+                row.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+                    .map(|cell| inline_text(&self.render_children(cell)).replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return String::new();
+        }
+
+        let format_row = |row: &[String]| {
+            let cells: Vec<&str> = (0..columns)
+                .map(|i| row.get(i).map_or("", String::as_str))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![format_row(&rows[0])];
+        lines.push(format!("|{}", " --- |".repeat(columns)));
+        lines.extend(rows[1..].iter().map(|row| format_row(row)));
+        format!("\n\n{}\n\n", lines.join("\n"))
+    }
+}
+
+fn wrap_inline(inner: &str, marker: &str) -> String {
+    let trimmed = inner.trim();
+    if trimmed.is_empty() {
+        return inner.to_string();
+    }
+    let leading = if inner.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let trailing = if inner.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!("{}{}{}{}{}", leading, marker, trimmed, marker, trailing)
+}
+
+/// 将 `<pre>` 转换为带语言标记的代码围栏
+fn render_code_block(element: ElementRef) -> String {
+    let code = element.text().collect::<String>();
+    let code = code.trim_matches('\n').trim_end();
+    if code.trim().is_empty() {
+        return String::new();
+    }
+
+    let language = std::iter::once(element)
+        .chain(
+            element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|child| child.value().name() == "code"),
+        )
+        .chain(element.ancestors().filter_map(ElementRef::wrap).take(2))
+        .find_map(code_language)
+        .unwrap_or_default();
+
+    let mut fence = "```".to_string();
+    while code.contains(&fence) {
+        fence.push('`');
+    }
+    format!("\n\n{}{}\n{}\n{}\n\n", fence, language, code, fence)
+}
+
+/// 从 `language-xxx`、`lang-xxx` 或 `highlight-source-xxx` 样式类中取出语言
+fn code_language(element: ElementRef) -> Option<String> {
+    element.value().classes().find_map(|class| {
+        ["language-", "lang-", "highlight-source-"]
+            .iter()
+            .find_map(|prefix| class.strip_prefix(prefix))
+            .filter(|language| !language.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// 去掉多余的空行和行首尾空白，代码围栏内保持原样
+fn normalize_block(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in text.split('\n') {
+        let marker = line.trim_start_matches([' ', INDENT]);
+        if marker.starts_with("```") {
+            in_fence = !in_fence;
+            lines.push(line.trim().to_string());
+            continue;
+        }
+        if in_fence {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let line = line.trim();
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+fn finish_markdown(text: &str) -> String {
+    normalize_block(text).replace(INDENT, " ")
+}
@@ -0,0 +1,519 @@
+use super::batch_service::{DocumentLink, MAX_LEVEL};
+use super::crawler::is_asset_path;
+use super::index_parser::title_from_slug;
+use super::platform::log;
+use super::readability_extractor::proxied_url;
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::AppConfig;
+use chrono::{DateTime, NaiveDate};
+use flate2::read::GzDecoder;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::Client;
+use std::collections::HashSet;
+use std::io::Read;
+use url::Url;
+
+/// 站点地图索引最多展开的子站点地图数量
+const MAX_SITEMAP_FILES: usize = 50;
+
+/// 站点地图索引的最大嵌套深度
+const MAX_SITEMAP_DEPTH: usize = 3;
+
+/// 站点地图中的一条记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub lastmod: Option<String>,
+}
+
+/// 解析后的站点地图：页面列表或指向其他站点地图的索引
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sitemap {
+    UrlSet(Vec<SitemapEntry>),
+    Index(Vec<SitemapEntry>),
+}
+
+/// 站点地图发现的过滤条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapFilter {
+    /// 只保留路径以此开头的页面，为空时使用输入地址的路径
+    pub path_prefix: String,
+    /// 只保留在此日期及之后修改的页面，没有 lastmod 的页面始终保留
+    pub modified_since: Option<NaiveDate>,
+    /// 是否遵守 robots.txt 中的 Disallow 规则
+    pub respect_robots: bool,
+}
+
+impl Default for SitemapFilter {
+    fn default() -> Self {
+        Self {
+            path_prefix: String::new(),
+            modified_since: None,
+            respect_robots: true,
+        }
+    }
+}
+
+/// robots.txt 中适用于所有爬虫（User-agent: *）的规则
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    /// (是否允许, 路径模式)
+    rules: Vec<(bool, String)>,
+    pub sitemaps: Vec<String>,
+}
+
+impl RobotsRules {
+    pub fn parse(text: &str) -> Self {
+        let mut robots = RobotsRules::default();
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    // 规则之后出现的 User-agent 开始新的分组
+                    if in_rules {
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_string());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // 空的 Disallow 表示不限制
+                    if value.is_empty() || !group_agents.iter().any(|agent| agent == "*") {
+                        continue;
+                    }
+                    robots.rules.push((key == "allow", value.to_string()));
+                }
+                // Sitemap 指令不属于任何分组
+                "sitemap" if !value.is_empty() => robots.sitemaps.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        robots
+    }
+
+    /// 最长匹配的规则生效，长度相同时 Allow 优先
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| robots_pattern_matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .map(|(allow, _)| *allow)
+            .unwrap_or(true)
+    }
+}
+
+/// 支持 `*` 通配符和 `$` 结尾锚点的 robots 路径匹配
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let Some(rest) = path.strip_prefix(parts[0]) else {
+        return false;
+    };
+    if parts.len() == 1 {
+        return !anchored || rest.is_empty();
+    }
+
+    let mut rest = rest;
+    for (i, part) in parts.iter().enumerate().skip(1) {
+        let is_last = i == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// 解析 sitemap.xml 或站点地图索引
+pub fn parse_sitemap(xml: &str) -> AppResult<Sitemap> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut is_index = None;
+    let mut entries = Vec::new();
+    let mut current: Option<SitemapEntry> = None;
+    let mut field: Option<Vec<u8>> = None;
+
+    loop {
+        match reader.read_event().map_err(sitemap_error)? {
+            Event::Start(e) => {
+                let name = e.local_name().as_ref().to_vec();
+                match name.as_slice() {
+                    b"urlset" if is_index.is_none() => is_index = Some(false),
+                    b"sitemapindex" if is_index.is_none() => is_index = Some(true),
+                    b"url" | b"sitemap" => {
+                        current = Some(SitemapEntry {
+                            loc: String::new(),
+                            lastmod: None,
+                        })
+                    }
+                    b"loc" | b"lastmod" => field = Some(name),
+                    _ => {}
+                }
+            }
+            Event::Text(text) => {
+                let value = text.unescape().map_err(sitemap_error)?;
+                append_field(&mut current, field.as_deref(), value.trim());
+            }
+            Event::CData(data) => {
+                let value = String::from_utf8_lossy(&data.into_inner()).into_owned();
+                append_field(&mut current, field.as_deref(), value.trim());
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"loc" | b"lastmod" => field = None,
+                b"url" | b"sitemap" => {
+                    if let Some(entry) = current.take() {
+                        if !entry.loc.is_empty() {
+                            entries.push(entry);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    match is_index {
+        Some(true) => Ok(Sitemap::Index(entries)),
+        Some(false) => Ok(Sitemap::UrlSet(entries)),
+        None => Err(AppError::parse(
+            "不是有效的站点地图：缺少 urlset 或 sitemapindex",
+        )),
+    }
+}
+
+fn append_field(current: &mut Option<SitemapEntry>, field: Option<&[u8]>, value: &str) {
+    let Some(entry) = current.as_mut() else {
+        return;
+    };
+    match field {
+        Some(b"loc") => entry.loc.push_str(value),
+        Some(b"lastmod") => entry
+            .lastmod
+            .get_or_insert_with(String::new)
+            .push_str(value),
+        _ => {}
+    }
+}
+
+fn sitemap_error(e: quick_xml::Error) -> AppError {
+    AppError::parse(format!("站点地图格式错误: {}", e))
+}
+
+/// 读取 lastmod 的日期部分，支持纯日期和 W3C 日期时间格式
+pub fn parse_lastmod(lastmod: &str) -> Option<NaiveDate> {
+    let lastmod = lastmod.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(lastmod) {
+        return Some(datetime.date_naive());
+    }
+    lastmod
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+/// 地址是否指向站点地图文件
+pub fn is_sitemap_url(url: &str) -> bool {
+    Url::parse(url)
+        .map(|parsed| {
+            let path = parsed.path().to_lowercase();
+            path.ends_with(".xml") || path.ends_with(".xml.gz")
+        })
+        .unwrap_or(false)
+}
+
+/// 按过滤条件筛选站点地图记录，并按路径层级生成文档链接
+pub fn sitemap_links(
+    entries: &[SitemapEntry],
+    site_url: &str,
+    filter: &SitemapFilter,
+    robots: Option<&RobotsRules>,
+) -> Vec<DocumentLink> {
+    let Ok(site) = Url::parse(site_url) else {
+        return Vec::new();
+    };
+    let prefix = effective_prefix(&site, filter);
+
+    let mut seen = HashSet::new();
+    let mut pages: Vec<(Vec<String>, Url)> = entries
+        .iter()
+        .filter_map(|entry| {
+            let mut url = Url::parse(entry.loc.trim()).ok()?;
+            url.set_fragment(None);
+            if url.host_str() != site.host_str() || is_asset_path(url.path()) {
+                return None;
+            }
+            if !url.path().starts_with(&prefix) {
+                return None;
+            }
+            if let (Some(since), Some(lastmod)) = (
+                filter.modified_since,
+                entry.lastmod.as_deref().and_then(parse_lastmod),
+            ) {
+                if lastmod < since {
+                    return None;
+                }
+            }
+            if let Some(robots) = robots {
+                let target = match url.query() {
+                    Some(query) => format!("{}?{}", url.path(), query),
+                    None => url.path().to_string(),
+                };
+                if !robots.is_allowed(&target) {
+                    return None;
+                }
+            }
+            if !seen.insert(url.to_string()) {
+                return None;
+            }
+            Some((relative_segments(url.path(), &prefix), url))
+        })
+        .collect();
+
+    // 按路径排序，使父目录排在子页面之前
+    pages.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.as_str().cmp(b.1.as_str())));
+
+    pages
+        .into_iter()
+        .enumerate()
+        .map(|(order, (segments, url))| DocumentLink {
+            title: title_from_segments(&segments, &url),
+            url: url.to_string(),
+            level: segments.len().saturating_sub(1).min(MAX_LEVEL),
+            order,
+        })
+        .collect()
+}
+
+/// 过滤用的路径前缀：未指定时，输入的不是站点地图则使用其所在目录
+fn effective_prefix(site: &Url, filter: &SitemapFilter) -> String {
+    let prefix = filter.path_prefix.trim();
+    if !prefix.is_empty() {
+        return if prefix.starts_with('/') {
+            prefix.to_string()
+        } else {
+            format!("/{}", prefix)
+        };
+    }
+    if is_sitemap_url(site.as_str()) {
+        return "/".to_string();
+    }
+    let path = site.path();
+    match path.rfind('/') {
+        Some(pos) => path[..=pos].to_string(),
+        None => "/".to_string(),
+    }
+}
+
+/// 前缀之后的路径段，忽略结尾的 index 页面
+fn relative_segments(path: &str, prefix: &str) -> Vec<String> {
+    let relative = path.strip_prefix(prefix).unwrap_or(path);
+    let mut segments: Vec<String> = relative
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            urlencoding::decode(segment)
+                .map(|decoded| decoded.into_owned())
+                .unwrap_or_else(|_| segment.to_string())
+        })
+        .collect();
+    if segments
+        .last()
+        .map(|last| last.to_lowercase().starts_with("index."))
+        .unwrap_or(false)
+    {
+        segments.pop();
+    }
+    segments
+}
+
+fn title_from_segments(segments: &[String], url: &Url) -> String {
+    match segments.last() {
+        Some(last) => title_from_slug(last),
+        None => url.host_str().unwrap_or("index").to_string(),
+    }
+}
+
+/// 站点地图发现服务：读取 robots.txt 和 sitemap.xml，生成批量翻译的文档列表
+pub struct SitemapService {
+    client: Client,
+    cors_proxy: String,
+}
+
+impl SitemapService {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: Client::new(),
+            cors_proxy: config.extractor.cors_proxy.clone(),
+        }
+    }
+
+    pub async fn discover(
+        &self,
+        site_url: &str,
+        filter: &SitemapFilter,
+    ) -> AppResult<Vec<DocumentLink>> {
+        log(&format!("站点地图发现: {}", site_url));
+
+        let links = self.discover_links(site_url, filter).await?;
+
+        log(&format!(
+            "站点地图发现完成: 前缀 {}，找到 {} 个页面",
+            Url::parse(site_url)
+                .map(|site| effective_prefix(&site, filter))
+                .unwrap_or_default(),
+            links.len()
+        ));
+
+        Ok(links)
+    }
+
+    /// 读取 robots.txt 找到站点地图（没有声明时使用 /sitemap.xml），展开索引后筛选页面
+    pub async fn discover_links(
+        &self,
+        site_url: &str,
+        filter: &SitemapFilter,
+    ) -> AppResult<Vec<DocumentLink>> {
+        let site = Url::parse(site_url)
+            .map_err(|e| AppError::validation("URL", format!("无效的地址 {}: {}", site_url, e)))?;
+
+        let robots_url = site
+            .join("/robots.txt")
+            .map_err(|e| AppError::validation("URL", e.to_string()))?;
+        let robots = match self.fetch_bytes(robots_url.as_str()).await {
+            Ok(Some(bytes)) => RobotsRules::parse(&String::from_utf8_lossy(&bytes)),
+            // robots.txt 不存在或无法访问时不做限制
+            _ => RobotsRules::default(),
+        };
+
+        let mut pending: Vec<(String, usize)> = if is_sitemap_url(site_url) {
+            vec![(site_url.to_string(), 0)]
+        } else if !robots.sitemaps.is_empty() {
+            robots.sitemaps.iter().map(|url| (url.clone(), 0)).collect()
+        } else {
+            let default = site
+                .join("/sitemap.xml")
+                .map_err(|e| AppError::validation("URL", e.to_string()))?;
+            vec![(default.to_string(), 0)]
+        };
+
+        // 单个站点地图失效或格式错误时跳过，只有一个都读不到时才失败
+        let mut visited = HashSet::new();
+        let mut entries = Vec::new();
+        let mut read_any = false;
+        let mut last_error = None;
+        while let Some((sitemap_url, depth)) = pending.pop() {
+            if !visited.insert(sitemap_url.clone()) || visited.len() > MAX_SITEMAP_FILES {
+                continue;
+            }
+            let sitemap = match self.read_sitemap(&sitemap_url).await {
+                Ok(sitemap) => sitemap,
+                Err(e) => {
+                    log(&format!("跳过无法读取的站点地图: {}", e));
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+            read_any = true;
+            match sitemap {
+                Sitemap::UrlSet(urls) => entries.extend(urls),
+                Sitemap::Index(sitemaps) if depth + 1 < MAX_SITEMAP_DEPTH => {
+                    // 倒序压栈，保持索引中的顺序
+                    pending.extend(sitemaps.into_iter().rev().map(|s| (s.loc, depth + 1)));
+                }
+                Sitemap::Index(_) => {}
+            }
+        }
+        if !read_any {
+            return Err(
+                last_error.unwrap_or_else(|| AppError::extraction("没有找到可读取的站点地图"))
+            );
+        }
+
+        let robots = filter.respect_robots.then_some(&robots);
+        Ok(sitemap_links(&entries, site_url, filter, robots))
+    }
+
+    /// 获取并解析一个站点地图文件
+    async fn read_sitemap(&self, url: &str) -> AppResult<Sitemap> {
+        let bytes = self
+            .fetch_bytes(url)
+            .await?
+            .ok_or_else(|| AppError::extraction(format!("站点地图不存在: {}", url)))?;
+        parse_sitemap(&decode_sitemap(&bytes)?)
+    }
+
+    /// 获取文件内容，404 返回 None
+    pub async fn fetch_bytes(&self, url: &str) -> AppResult<Option<Vec<u8>>> {
+        let response = self
+            .client
+            .get(self.request_url(url))
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::network(format!(
+                    "获取 {} 失败: {}. 浏览器中请确认CORS代理可用",
+                    url, e
+                ))
+            })?;
+
+        let status = response.status();
+        if status.as_u16() == 404 || status.as_u16() == 410 {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let message = format!("获取 {} 失败: {}", url, status);
+            return Err(match status.as_u16() {
+                429 => AppError::rate_limit(message),
+                _ => AppError::extraction(message),
+            });
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| AppError::network(format!("读取 {} 失败: {}", url, e)))?;
+        Ok(Some(bytes.to_vec()))
+    }
+
+    /// 浏览器中经由CORS代理访问，原生环境直接访问
+    fn request_url(&self, url: &str) -> String {
+        if cfg!(target_arch = "wasm32") {
+            proxied_url(&self.cors_proxy, url)
+        } else {
+            url.to_string()
+        }
+    }
+}
+
+/// 站点地图可能经过 gzip 压缩
+fn decode_sitemap(bytes: &[u8]) -> AppResult<String> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut xml = String::new();
+        GzDecoder::new(bytes)
+            .read_to_string(&mut xml)
+            .map_err(|e| AppError::parse(format!("无法解压站点地图: {}", e)))?;
+        return Ok(xml);
+    }
+    String::from_utf8(bytes.to_vec()).map_err(|_| AppError::parse("站点地图不是有效的UTF-8文本"))
+}
@@ -0,0 +1,428 @@
+use super::incremental::{content_hash, split_source_paragraphs, ParagraphDiff};
+use super::indexed_db::{
+    await_request, await_transaction, storage_error, LocalDatabase, TRANSLATION_MEMORY_STORE,
+};
+use super::platform::log;
+use super::translator::find_placeholders;
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::TranslationEngine;
+use chrono::{DateTime, Duration, Utc};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::future::Future;
+use wasm_bindgen::prelude::*;
+use web_sys::{IdbObjectStore, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+/// 翻译记忆配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TranslationMemoryConfig {
+    /// 翻译前先查询翻译记忆，命中的段落不再发送翻译请求
+    pub enabled: bool,
+}
+
+impl Default for TranslationMemoryConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// 翻译记忆中的一条记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryEntry {
+    pub key: String,
+    pub engine: TranslationEngine,
+    pub source_lang: String,
+    pub target_lang: String,
+    /// 规范化后的原文片段
+    pub source: String,
+    pub translation: String,
+    pub created_at: String,
+    pub last_used_at: String,
+    pub hits: u32,
+}
+
+/// 本次会话的命中统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl MemoryStats {
+    pub fn lookups(&self) -> usize {
+        self.hits + self.misses
+    }
+
+    /// 命中率（百分比），没有查询时为 0
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups() == 0 {
+            0.0
+        } else {
+            self.hits as f64 * 100.0 / self.lookups() as f64
+        }
+    }
+}
+
+thread_local! {
+    static SESSION_STATS: Cell<MemoryStats> = Cell::new(MemoryStats::default());
+}
+
+/// 自上次重置以来的命中统计
+pub fn session_stats() -> MemoryStats {
+    SESSION_STATS.with(Cell::get)
+}
+
+/// 开始新的翻译任务时清零命中统计
+pub fn reset_session_stats() {
+    SESSION_STATS.with(|stats| stats.set(MemoryStats::default()));
+}
+
+fn record_lookups(hits: usize, misses: usize) {
+    SESSION_STATS.with(|stats| {
+        let current = stats.get();
+        stats.set(MemoryStats {
+            hits: current.hits + hits,
+            misses: current.misses + misses,
+        });
+    });
+}
+
+/// 记忆库内容概览
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MemorySummary {
+    pub entries: usize,
+    pub total_hits: u64,
+    /// 按 "引擎 源语言 -> 目标语言" 统计的条目数，条目多的在前
+    pub by_pair: Vec<(String, usize)>,
+}
+
+impl MemorySummary {
+    pub fn from_entries(entries: &[MemoryEntry]) -> Self {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for entry in entries {
+            let pair = format!(
+                "{} {} -> {}",
+                entry.engine.display_name(),
+                entry.source_lang,
+                entry.target_lang
+            );
+            *counts.entry(pair).or_insert(0) += 1;
+        }
+        let mut by_pair: Vec<(String, usize)> = counts.into_iter().collect();
+        by_pair.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Self {
+            entries: entries.len(),
+            total_hits: entries.iter().map(|entry| entry.hits as u64).sum(),
+            by_pair,
+        }
+    }
+}
+
+/// 规范化的原文片段：去掉行尾空白、合并行内连续空白，代码块占位符按出现顺序编号
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemorySegment {
+    normalized: String,
+    /// 原文中的占位符，下标即规范化后的编号
+    placeholders: Vec<String>,
+}
+
+impl MemorySegment {
+    pub fn new(text: &str) -> Self {
+        let placeholders = find_placeholders(text);
+        let mut canonical = text.to_string();
+        for (index, placeholder) in placeholders.iter().enumerate() {
+            canonical = canonical.replace(placeholder, &canonical_placeholder(index));
+        }
+        let normalized = canonical
+            .trim()
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Self {
+            normalized,
+            placeholders,
+        }
+    }
+
+    pub fn normalized(&self) -> &str {
+        &self.normalized
+    }
+
+    /// 记忆库中的键，区分语言对和翻译引擎
+    pub fn key(&self, engine: TranslationEngine, source_lang: &str, target_lang: &str) -> String {
+        format!(
+            "{:?}:{}:{}:{}",
+            engine,
+            source_lang,
+            target_lang,
+            content_hash(&self.normalized)
+        )
+    }
+
+    /// 把译文中的占位符换成编号后存入记忆库；译文含有原文没有的占位符时不保存
+    pub fn canonical_translation(&self, translation: &str) -> Option<String> {
+        let mut canonical = translation.trim().to_string();
+        for placeholder in find_placeholders(translation) {
+            let index = self.placeholders.iter().position(|p| *p == placeholder)?;
+            canonical = canonical.replace(&placeholder, &canonical_placeholder(index));
+        }
+        Some(canonical)
+    }
+
+    /// 把记忆库中的译文还原为当前原文的占位符
+    pub fn restore_translation(&self, stored: &str) -> String {
+        let mut restored = stored.to_string();
+        for (index, placeholder) in self.placeholders.iter().enumerate() {
+            restored = restored.replace(&canonical_placeholder(index), placeholder);
+        }
+        restored
+    }
+}
+
+fn canonical_placeholder(index: usize) -> String {
+    format!("__CODE_BLOCK_{}__", index)
+}
+
+/// 最后使用时间早于 `now - days` 天的条目
+pub fn stale_entries(entries: &[MemoryEntry], now: DateTime<Utc>, days: i64) -> Vec<String> {
+    let cutoff = now - Duration::days(days);
+    entries
+        .iter()
+        .filter(|entry| {
+            DateTime::parse_from_rfc3339(&entry.last_used_at)
+                .map(|used| used.with_timezone(&Utc) < cutoff)
+                .unwrap_or(true)
+        })
+        .map(|entry| entry.key.clone())
+        .collect()
+}
+
+/// 先查翻译记忆，只把未命中的段落交给 `translate`，新的译文写回记忆库。
+/// 记忆库不可用时直接翻译全文
+pub async fn translate_with_memory<F, Fut>(
+    text: &str,
+    engine: TranslationEngine,
+    source_lang: &str,
+    target_lang: &str,
+    translate: F,
+) -> AppResult<String>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = AppResult<String>>,
+{
+    let memory = match TranslationMemory::open().await {
+        Ok(memory) => memory,
+        Err(e) => {
+            log(&format!("翻译记忆不可用，直接翻译: {}", e));
+            return translate(text.to_string()).await;
+        }
+    };
+
+    let paragraphs = split_source_paragraphs(text);
+    let hits = match memory
+        .lookup(&paragraphs, engine, source_lang, target_lang)
+        .await
+    {
+        Ok(hits) => hits,
+        Err(e) => {
+            log(&format!("查询翻译记忆失败: {}", e));
+            vec![None; paragraphs.len()]
+        }
+    };
+
+    let diff = ParagraphDiff::from_lookup(paragraphs, hits);
+    record_lookups(diff.reused_count(), diff.changed_count());
+    log(&format!(
+        "翻译记忆: {} 段中命中 {} 段",
+        diff.paragraph_count(),
+        diff.reused_count()
+    ));
+
+    let segments = diff.changed_segments();
+    let mut translated_segments = Vec::with_capacity(segments.len());
+    let mut learned = Vec::new();
+    for segment in segments {
+        let translated = translate(segment.clone()).await?;
+        // 段落数一致时才能逐段对应写入记忆库
+        let sources = split_source_paragraphs(&segment);
+        let translations = split_source_paragraphs(&translated);
+        if sources.len() == translations.len() {
+            learned.extend(sources.into_iter().zip(translations));
+        }
+        translated_segments.push(translated);
+    }
+
+    if !learned.is_empty() {
+        if let Err(e) = memory
+            .store(&learned, engine, source_lang, target_lang)
+            .await
+        {
+            log(&format!("写入翻译记忆失败: {}", e));
+        }
+    }
+
+    Ok(diff.assemble(&translated_segments))
+}
+
+/// 保存在 IndexedDB 中的翻译记忆库
+pub struct TranslationMemory {
+    db: LocalDatabase,
+}
+
+impl TranslationMemory {
+    pub async fn open() -> AppResult<Self> {
+        Ok(Self {
+            db: LocalDatabase::open().await?,
+        })
+    }
+
+    /// 按段落查询译文，命中的条目更新使用时间和次数
+    pub async fn lookup(
+        &self,
+        paragraphs: &[String],
+        engine: TranslationEngine,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<Vec<Option<String>>> {
+        let segments: Vec<MemorySegment> = paragraphs
+            .iter()
+            .map(|paragraph| MemorySegment::new(paragraph))
+            .collect();
+
+        let (store, _) = self.object_store(IdbTransactionMode::Readonly)?;
+        let requests = segments
+            .iter()
+            .map(|segment| {
+                store
+                    .get(&JsValue::from_str(&segment.key(
+                        engine,
+                        source_lang,
+                        target_lang,
+                    )))
+                    .map_err(storage_error)
+            })
+            .collect::<AppResult<Vec<IdbRequest>>>()?;
+        let values = join_all(requests.iter().map(await_request)).await;
+
+        let now = Utc::now().to_rfc3339();
+        let mut used = Vec::new();
+        let translations = segments
+            .iter()
+            .zip(values)
+            .map(|(segment, value)| {
+                let mut entry = parse_entry(&value.ok()?)?;
+                // 键是哈希，核对原文以防碰撞
+                if entry.source != segment.normalized() {
+                    return None;
+                }
+                let translation = segment.restore_translation(&entry.translation);
+                entry.hits += 1;
+                entry.last_used_at = now.clone();
+                used.push(entry);
+                Some(translation)
+            })
+            .collect();
+
+        if !used.is_empty() {
+            self.put_entries(&used).await?;
+        }
+        Ok(translations)
+    }
+
+    /// 保存原文段落和译文段落
+    pub async fn store(
+        &self,
+        pairs: &[(String, String)],
+        engine: TranslationEngine,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+        let entries: Vec<MemoryEntry> = pairs
+            .iter()
+            .filter_map(|(source, translation)| {
+                let segment = MemorySegment::new(source);
+                let translation = segment.canonical_translation(translation)?;
+                if segment.normalized().is_empty() || translation.is_empty() {
+                    return None;
+                }
+                Some(MemoryEntry {
+                    key: segment.key(engine, source_lang, target_lang),
+                    engine,
+                    source_lang: source_lang.to_string(),
+                    target_lang: target_lang.to_string(),
+                    source: segment.normalized().to_string(),
+                    translation,
+                    created_at: now.clone(),
+                    last_used_at: now.clone(),
+                    hits: 0,
+                })
+            })
+            .collect();
+        self.put_entries(&entries).await
+    }
+
+    /// 所有条目，最近使用的在前
+    pub async fn entries(&self) -> AppResult<Vec<MemoryEntry>> {
+        let (store, _) = self.object_store(IdbTransactionMode::Readonly)?;
+        let request = store.get_all().map_err(storage_error)?;
+        let values = js_sys::Array::from(&await_request(&request).await?);
+        let mut entries: Vec<MemoryEntry> = values.iter().filter_map(|v| parse_entry(&v)).collect();
+        entries.sort_by(|a, b| b.last_used_at.cmp(&a.last_used_at));
+        Ok(entries)
+    }
+
+    /// 删除超过 `days` 天未使用的条目，返回删除的条目数
+    pub async fn prune_unused(&self, days: i64) -> AppResult<usize> {
+        let stale = stale_entries(&self.entries().await?, Utc::now(), days);
+        if stale.is_empty() {
+            return Ok(0);
+        }
+        let (store, transaction) = self.object_store(IdbTransactionMode::Readwrite)?;
+        for key in &stale {
+            store
+                .delete(&JsValue::from_str(key))
+                .map_err(storage_error)?;
+        }
+        await_transaction(&transaction).await?;
+        Ok(stale.len())
+    }
+
+    pub async fn clear(&self) -> AppResult<()> {
+        let (store, transaction) = self.object_store(IdbTransactionMode::Readwrite)?;
+        store.clear().map_err(storage_error)?;
+        await_transaction(&transaction).await
+    }
+
+    async fn put_entries(&self, entries: &[MemoryEntry]) -> AppResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let (store, transaction) = self.object_store(IdbTransactionMode::Readwrite)?;
+        for entry in entries {
+            let value = serde_json::to_string(entry)
+                .map_err(|e| AppError::parse(format!("序列化翻译记忆失败: {}", e)))?;
+            store
+                .put_with_key(&JsValue::from_str(&value), &JsValue::from_str(&entry.key))
+                .map_err(storage_error)?;
+        }
+        await_transaction(&transaction).await
+    }
+
+    fn object_store(
+        &self,
+        mode: IdbTransactionMode,
+    ) -> AppResult<(IdbObjectStore, IdbTransaction)> {
+        self.db.object_store(TRANSLATION_MEMORY_STORE, mode)
+    }
+}
+
+fn parse_entry(value: &JsValue) -> Option<MemoryEntry> {
+    serde_json::from_str(&value.as_string()?).ok()
+}
@@ -0,0 +1,351 @@
+use super::deepl_service::DeepLService;
+use super::deeplx_service::DeepLXService;
+use super::fallback_translator::FallbackTranslator;
+use super::libretranslate_service::LibreTranslateService;
+use super::mock_translator::MockTranslator;
+use super::ollama_service::OllamaService;
+use super::openai_service::OpenAIService;
+use crate::error::{AppError, AppResult};
+use crate::types::api_types::{AppConfig, TranslationEngine};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+
+/// 翻译器返回的异步结果
+pub type TranslateFuture<'a, T> = Pin<Box<dyn Future<Output = AppResult<T>> + 'a>>;
+
+/// 翻译引擎的能力与限制
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslatorCapabilities {
+    pub engine: TranslationEngine,
+    pub max_text_length: usize,
+    pub max_requests_per_second: u32,
+    pub supports_auto_detect: bool,
+    pub supports_streaming: bool,
+}
+
+/// 单个文本块由哪个引擎翻译
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkRecord {
+    pub index: usize,
+    pub engine: TranslationEngine,
+}
+
+/// 带分块来源记录的翻译结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationOutcome {
+    pub text: String,
+    pub chunks: Vec<ChunkRecord>,
+}
+
+impl TranslationOutcome {
+    /// 按引擎统计完成的文本块数
+    pub fn engine_usage(&self) -> Vec<EngineUsage> {
+        merge_engine_usage(self.chunks.iter().map(|chunk| EngineUsage {
+            engine: chunk.engine,
+            chunks: 1,
+        }))
+    }
+}
+
+/// 某个引擎完成的文本块数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineUsage {
+    pub engine: TranslationEngine,
+    pub chunks: usize,
+}
+
+/// 翻译引擎统一接口，新的翻译后端只需实现该trait即可接入翻译流程
+pub trait Translator {
+    /// 翻译文本，过长的文本由实现自行分块
+    fn translate<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+    ) -> TranslateFuture<'a, String>;
+
+    /// 流式翻译，每收到新内容时用目前为止的完整译文调用 `on_progress`
+    ///
+    /// 默认实现在翻译完成后回调一次，支持流式输出的引擎应覆盖该方法。
+    fn translate_streaming<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+        on_progress: &'a dyn Fn(&str),
+    ) -> TranslateFuture<'a, String> {
+        Box::pin(async move {
+            let translated = self.translate(text, source_lang, target_lang).await?;
+            on_progress(&translated);
+            Ok(translated)
+        })
+    }
+
+    /// 翻译并记录每个文本块由哪个引擎完成，进度回调同 `translate_streaming`
+    ///
+    /// 默认实现把所有文本块都记在当前引擎名下。
+    fn translate_with_report<'a>(
+        &'a self,
+        text: &'a str,
+        source_lang: &'a str,
+        target_lang: &'a str,
+        on_progress: &'a dyn Fn(&str),
+    ) -> TranslateFuture<'a, TranslationOutcome> {
+        Box::pin(async move {
+            let translated = self
+                .translate_streaming(text, source_lang, target_lang, on_progress)
+                .await?;
+            let capabilities = self.capabilities();
+            let chunk_count = split_text_into_chunks(text, capabilities.max_text_length).len();
+
+            Ok(TranslationOutcome {
+                text: translated,
+                chunks: (0..chunk_count)
+                    .map(|index| ChunkRecord {
+                        index,
+                        engine: capabilities.engine,
+                    })
+                    .collect(),
+            })
+        })
+    }
+
+    /// 报告引擎能力与限制
+    fn capabilities(&self) -> TranslatorCapabilities;
+
+    /// 引擎显示名称
+    fn name(&self) -> &'static str {
+        self.capabilities().engine.display_name()
+    }
+}
+
+/// 根据配置创建翻译器，配置了备用引擎时返回按顺序回退的翻译器
+pub fn create_translator(config: &AppConfig) -> Box<dyn Translator> {
+    if engine_chain(config).len() > 1 {
+        Box::new(FallbackTranslator::new(config))
+    } else {
+        create_engine_translator(config.translation_engine, config)
+    }
+}
+
+/// 主引擎加上备用引擎的顺序列表，已去重
+pub fn engine_chain(config: &AppConfig) -> Vec<TranslationEngine> {
+    let mut chain = vec![config.translation_engine];
+    for engine in &config.fallback_engines {
+        if !chain.contains(engine) {
+            chain.push(*engine);
+        }
+    }
+    chain
+}
+
+/// 创建指定引擎的翻译器
+pub fn create_engine_translator(
+    engine: TranslationEngine,
+    config: &AppConfig,
+) -> Box<dyn Translator> {
+    match engine {
+        TranslationEngine::DeepLX => Box::new(DeepLXService::new(config)),
+        TranslationEngine::OpenAI => Box::new(OpenAIService::new(config)),
+        TranslationEngine::DeepL => Box::new(DeepLService::new(config)),
+        TranslationEngine::LibreTranslate => Box::new(LibreTranslateService::new(config)),
+        TranslationEngine::Ollama => Box::new(OllamaService::new(config)),
+        TranslationEngine::Mock => Box::new(MockTranslator::new(config)),
+    }
+}
+
+/// 合并引擎用量，按首次出现的顺序排列
+pub fn merge_engine_usage(usage: impl IntoIterator<Item = EngineUsage>) -> Vec<EngineUsage> {
+    let mut merged: Vec<EngineUsage> = Vec::new();
+    for item in usage {
+        match merged
+            .iter_mut()
+            .find(|existing| existing.engine == item.engine)
+        {
+            Some(existing) => existing.chunks += item.chunks,
+            None => merged.push(item),
+        }
+    }
+    merged
+}
+
+/// 格式化引擎用量，例如 "DeepLX 12 块, OpenAI 3 块"
+pub fn format_engine_usage(usage: &[EngineUsage]) -> String {
+    usage
+        .iter()
+        .map(|item| format!("{} {} 块", item.engine.display_name(), item.chunks))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 将长文本按最大长度（字节）分块，尽量在空白或标点处断开
+pub fn split_text_into_chunks(text: &str, max_length: usize) -> Vec<String> {
+    if text.len() <= max_length {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < text.len() {
+        let mut end = std::cmp::min(start + max_length, text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == start {
+            // 单个字符超过最大长度，至少前进一个字符
+            end = start + text[start..].chars().next().map_or(1, char::len_utf8);
+        }
+
+        // 向前查找最近的空格、换行或标点符号，避免在单词中间分割
+        let mut actual_end = end;
+        if end < text.len() {
+            if let Some((i, ch)) = text[start..end]
+                .char_indices()
+                .rev()
+                .find(|(_, ch)| matches!(ch, ' ' | '\n' | '.' | '!' | '?' | '；' | '。'))
+            {
+                actual_end = start + i + ch.len_utf8();
+            }
+        }
+
+        let chunk = text[start..actual_end].trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_string());
+        }
+
+        start = actual_end;
+    }
+
+    if chunks.is_empty() {
+        chunks.push(text.to_string());
+    }
+
+    chunks
+}
+
+/// 并发翻译文本块：最多同时发送 `concurrency` 个请求，结果按原顺序返回。
+/// 某个文本块失败不会中断其他文本块，失败的文本块在本轮结束后单独重试，最多尝试 `max_rounds` 轮
+pub async fn translate_chunks_concurrently<'a, F, Fut>(
+    chunks: &'a [String],
+    concurrency: usize,
+    max_rounds: usize,
+    translate: F,
+) -> AppResult<Vec<String>>
+where
+    F: Fn(usize, &'a str) -> Fut,
+    Fut: Future<Output = AppResult<String>>,
+{
+    let mut translated: Vec<Option<String>> = vec![None; chunks.len()];
+    let mut pending: Vec<usize> = (0..chunks.len()).collect();
+    let mut last_error = None;
+
+    for _ in 0..max_rounds.max(1) {
+        if pending.is_empty() {
+            break;
+        }
+        let outcomes: Vec<(usize, AppResult<String>)> = stream::iter(pending.drain(..))
+            .map(|index| {
+                let future = translate(index, &chunks[index]);
+                async move { (index, future.await) }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        for (index, outcome) in outcomes {
+            match outcome {
+                Ok(text) => translated[index] = Some(text),
+                Err(e) => {
+                    pending.push(index);
+                    last_error = Some(e);
+                }
+            }
+        }
+        pending.sort_unstable();
+    }
+
+    match last_error {
+        Some(e) if !pending.is_empty() => Err(e),
+        _ => Ok(translated.into_iter().flatten().collect()),
+    }
+}
+
+/// 将语言代码转换为提示词中使用的语言名称
+pub fn language_name(code: &str) -> &str {
+    match code.to_uppercase().as_str() {
+        "AUTO" => "the detected source language",
+        "ZH" => "Simplified Chinese",
+        "EN" => "English",
+        "JA" => "Japanese",
+        "FR" => "French",
+        "DE" => "German",
+        "ES" => "Spanish",
+        "KO" => "Korean",
+        "RU" => "Russian",
+        "IT" => "Italian",
+        "PT" => "Portuguese",
+        _ => code,
+    }
+}
+
+/// 检查并修复译文中的代码块占位符
+///
+/// 模型偶尔会把 `_` 转义成 `\_`，先尝试还原，仍然缺失时返回可重试的翻译错误。
+pub fn ensure_placeholders(source: &str, translated: &str) -> AppResult<String> {
+    let mut result = translated.to_string();
+    let mut missing = Vec::new();
+
+    for placeholder in find_placeholders(source) {
+        if result.contains(&placeholder) {
+            continue;
+        }
+
+        let escaped = placeholder.replace('_', "\\_");
+        if result.contains(&escaped) {
+            result = result.replace(&escaped, &placeholder);
+        } else {
+            missing.push(placeholder);
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(result)
+    } else {
+        Err(AppError::translation(format!(
+            "译文丢失了 {} 个代码块占位符: {}",
+            missing.len(),
+            missing.join(", ")
+        )))
+    }
+}
+
+/// 提取文本中所有 __CODE_BLOCK_xxx__ 占位符
+pub fn find_placeholders(text: &str) -> Vec<String> {
+    const PREFIX: &str = "__CODE_BLOCK_";
+    let mut placeholders = Vec::new();
+    let mut search_start = 0;
+
+    while let Some(pos) = text[search_start..].find(PREFIX) {
+        let start = search_start + pos;
+        let body_start = start + PREFIX.len();
+        let body_len = text[body_start..]
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(text.len() - body_start);
+        let end = body_start + body_len;
+
+        if body_len > 0 && text[end..].starts_with("__") {
+            let placeholder = text[start..end + 2].to_string();
+            if !placeholders.contains(&placeholder) {
+                placeholders.push(placeholder);
+            }
+            search_start = end + 2;
+        } else {
+            search_start = body_start;
+        }
+    }
+
+    placeholders
+}
@@ -1,4 +1,13 @@
+use crate::services::batch_service::BatchConfig;
+use crate::services::deepl_service::DeepLConfig;
+use crate::services::extractor::ExtractorConfig;
 use crate::services::file_naming_service::FileNamingConfig;
+use crate::services::jina_service::JinaConfig;
+use crate::services::libretranslate_service::LibreTranslateConfig;
+use crate::services::mock_translator::MockConfig;
+use crate::services::ollama_service::OllamaConfig;
+use crate::services::openai_service::OpenAIConfig;
+use crate::services::translation_memory::TranslationMemoryConfig;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,29 +24,106 @@ pub struct DeepLXResponse {
     pub alternatives: Vec<String>,
 }
 
+/// 可选的翻译引擎
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum TranslationEngine {
+    #[default]
+    DeepLX,
+    OpenAI,
+    DeepL,
+    LibreTranslate,
+    Ollama,
+    /// 离线模拟引擎，不发起网络请求
+    Mock,
+}
+
+impl TranslationEngine {
+    pub const ALL: [TranslationEngine; 6] = [
+        TranslationEngine::DeepLX,
+        TranslationEngine::OpenAI,
+        TranslationEngine::DeepL,
+        TranslationEngine::LibreTranslate,
+        TranslationEngine::Ollama,
+        TranslationEngine::Mock,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TranslationEngine::DeepLX => "DeepLX",
+            TranslationEngine::OpenAI => "OpenAI",
+            TranslationEngine::DeepL => "DeepL",
+            TranslationEngine::LibreTranslate => "LibreTranslate",
+            TranslationEngine::Ollama => "Ollama",
+            TranslationEngine::Mock => "Mock",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
+    #[serde(default)]
+    pub translation_engine: TranslationEngine,
+    /// 主引擎失败时依次尝试的备用引擎
+    #[serde(default)]
+    pub fallback_engines: Vec<TranslationEngine>,
     pub deeplx_api_url: String,
     pub jina_api_url: String,
+    #[serde(default)]
+    pub jina: JinaConfig,
+    #[serde(default)]
+    pub extractor: ExtractorConfig,
     pub default_source_lang: String,
     pub default_target_lang: String,
     pub max_requests_per_second: u32,
     pub max_text_length: usize,
     pub max_paragraphs_per_request: usize,
+    /// 长文档分块翻译时同时发送的请求数
+    #[serde(default = "default_chunk_concurrency")]
+    pub chunk_concurrency: usize,
     pub file_naming: FileNamingConfig,
+    #[serde(default)]
+    pub openai: OpenAIConfig,
+    #[serde(default)]
+    pub deepl: DeepLConfig,
+    #[serde(default)]
+    pub libretranslate: LibreTranslateConfig,
+    #[serde(default)]
+    pub ollama: OllamaConfig,
+    #[serde(default)]
+    pub mock: MockConfig,
+    #[serde(default)]
+    pub batch: BatchConfig,
+    #[serde(default)]
+    pub translation_memory: TranslationMemoryConfig,
+}
+
+fn default_chunk_concurrency() -> usize {
+    3
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            translation_engine: TranslationEngine::default(),
+            fallback_engines: Vec::new(),
             deeplx_api_url: "https://deepl3.fileaiwork.online/dptrans?token=ej0ab47388ed86e843de9f499e52e6e664ae1m491cad7bf1.bIrYaAAAAAA=.b9c326068ac3c37ff36b8fea77867db51ddf235150945d7ad43472d68581e6c4pd14&newllm=1".to_string(),
             jina_api_url: "https://r.jina.ai".to_string(),
+            jina: JinaConfig::default(),
+            extractor: ExtractorConfig::default(),
             default_source_lang: "auto".to_string(),
             default_target_lang: "ZH".to_string(),
             max_requests_per_second: 10, // 提高到每秒10个请求
             max_text_length: 5000, // 提高到5000字符
             max_paragraphs_per_request: 10, // 提高到10个段落
+            chunk_concurrency: default_chunk_concurrency(),
             file_naming: FileNamingConfig::default(),
+            openai: OpenAIConfig::default(),
+            deepl: DeepLConfig::default(),
+            libretranslate: LibreTranslateConfig::default(),
+            ollama: OllamaConfig::default(),
+            mock: MockConfig::default(),
+            batch: BatchConfig::default(),
+            translation_memory: TranslationMemoryConfig::default(),
         }
     }
 }
@@ -0,0 +1,173 @@
+use crate::error::AppError;
+use crate::services::batch_service::{BatchFailure, DocumentLink, TranslatedDocument};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 批量任务中单个文档的处理状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JobDocumentStatus {
+    Pending,
+    Completed,
+    /// 重试后仍失败，附带最后一次的错误
+    Failed(AppError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobDocument {
+    pub link: DocumentLink,
+    pub status: JobDocumentStatus,
+    /// 已完成文档的译文，恢复任务时无需重新翻译
+    #[serde(default)]
+    pub result: Option<TranslatedDocument>,
+}
+
+/// 可在页面重新加载后继续的批量翻译任务
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchJob {
+    pub id: String,
+    pub index_url: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub documents: Vec<JobDocument>,
+}
+
+impl BatchJob {
+    pub fn new(
+        index_url: String,
+        source_lang: String,
+        target_lang: String,
+        links: Vec<DocumentLink>,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            index_url,
+            source_lang,
+            target_lang,
+            created_at: now.clone(),
+            updated_at: now,
+            documents: links
+                .into_iter()
+                .map(|link| JobDocument {
+                    link,
+                    status: JobDocumentStatus::Pending,
+                    result: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// 尚未处理的文档，按目录顺序排列
+    pub fn pending_links(&self) -> Vec<DocumentLink> {
+        let mut links: Vec<DocumentLink> = self
+            .documents
+            .iter()
+            .filter(|doc| doc.status == JobDocumentStatus::Pending)
+            .map(|doc| doc.link.clone())
+            .collect();
+        links.sort_by_key(|link| link.order);
+        links
+    }
+
+    /// 记录翻译完成的文档
+    pub fn record_success(&mut self, translated: &TranslatedDocument) {
+        if let Some(doc) = self.find_mut(&translated.link) {
+            doc.status = JobDocumentStatus::Completed;
+            doc.result = Some(translated.clone());
+        }
+        self.touch();
+    }
+
+    /// 记录最终失败的文档
+    pub fn record_failure(&mut self, link: &DocumentLink, error: &AppError) {
+        if let Some(doc) = self.find_mut(link) {
+            doc.status = JobDocumentStatus::Failed(error.clone());
+            doc.result = None;
+        }
+        self.touch();
+    }
+
+    /// 已完成的译文，按目录顺序排列
+    pub fn completed_documents(&self) -> Vec<TranslatedDocument> {
+        let mut docs: Vec<TranslatedDocument> = self
+            .documents
+            .iter()
+            .filter_map(|doc| doc.result.clone())
+            .collect();
+        docs.sort_by_key(|doc| doc.link.order);
+        docs
+    }
+
+    /// 失败的文档及错误，按目录顺序排列
+    pub fn failures(&self) -> Vec<BatchFailure> {
+        let mut failures: Vec<BatchFailure> = self
+            .documents
+            .iter()
+            .filter_map(|doc| match &doc.status {
+                JobDocumentStatus::Failed(error) => Some(BatchFailure {
+                    link: doc.link.clone(),
+                    error: error.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+        failures.sort_by_key(|failure| failure.link.order);
+        failures
+    }
+
+    /// 把失败的文档重新标记为待处理，已完成的译文保持不变；返回重置的文档数
+    pub fn reset_failed(&mut self) -> usize {
+        let mut reset = 0;
+        for doc in &mut self.documents {
+            if matches!(doc.status, JobDocumentStatus::Failed(_)) {
+                doc.status = JobDocumentStatus::Pending;
+                reset += 1;
+            }
+        }
+        if reset > 0 {
+            self.touch();
+        }
+        reset
+    }
+
+    pub fn total_count(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.count(|status| *status == JobDocumentStatus::Completed)
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.count(|status| matches!(status, JobDocumentStatus::Failed(_)))
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.count(|status| *status == JobDocumentStatus::Pending)
+    }
+
+    /// 所有文档都已处理（成功或失败）
+    pub fn is_finished(&self) -> bool {
+        self.pending_count() == 0
+    }
+
+    fn count(&self, predicate: impl Fn(&JobDocumentStatus) -> bool) -> usize {
+        self.documents
+            .iter()
+            .filter(|doc| predicate(&doc.status))
+            .count()
+    }
+
+    fn find_mut(&mut self, link: &DocumentLink) -> Option<&mut JobDocument> {
+        self.documents
+            .iter_mut()
+            .find(|doc| doc.link.order == link.order && doc.link.url == link.url)
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now().to_rfc3339();
+    }
+}
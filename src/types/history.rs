@@ -1,3 +1,6 @@
+use crate::services::translator::EngineUsage;
+use crate::types::api_types::TranslationEngine;
+use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -16,6 +19,12 @@ pub struct HistoryEntry {
     pub entry_type: HistoryEntryType,
     #[serde(default)]
     pub batch_data: Option<BatchTranslationData>,
+    /// 各翻译引擎完成的文本块数
+    #[serde(default)]
+    pub engine_usage: Vec<EngineUsage>,
+    /// 多引擎对比时每段选用的引擎
+    #[serde(default)]
+    pub paragraph_engines: Vec<TranslationEngine>,
 }
 
 fn default_entry_type() -> HistoryEntryType {
@@ -26,6 +35,8 @@ fn default_entry_type() -> HistoryEntryType {
 pub enum HistoryEntryType {
     SinglePage,
     BatchTranslation,
+    /// 直接粘贴的文本，没有来源地址
+    PastedText,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +46,9 @@ pub struct BatchTranslationData {
     pub failed_documents: usize,
     pub index_url: String,
     pub document_list: Vec<BatchDocumentInfo>,
+    /// 生成该记录的批量任务，旧记录为空
+    #[serde(default)]
+    pub job_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +61,11 @@ pub struct BatchDocumentInfo {
     pub translated: bool,
     pub original_content: String,
     pub translated_content: String,
+    #[serde(default)]
+    pub engine_usage: Vec<EngineUsage>,
+    /// 原文内容哈希，旧记录为空
+    #[serde(default)]
+    pub content_hash: String,
 }
 
 impl HistoryEntry {
@@ -74,6 +93,8 @@ impl HistoryEntry {
             word_count,
             entry_type: HistoryEntryType::SinglePage,
             batch_data: None,
+            engine_usage: Vec::new(),
+            paragraph_engines: Vec::new(),
         }
     }
 
@@ -107,9 +128,112 @@ impl HistoryEntry {
             word_count,
             entry_type: HistoryEntryType::BatchTranslation,
             batch_data: Some(batch_data),
+            engine_usage: Vec::new(),
+            paragraph_engines: Vec::new(),
+        }
+    }
+
+    pub fn new_pasted_text(
+        title: String,
+        source_lang: String,
+        target_lang: String,
+        original_content: String,
+        translated_content: String,
+    ) -> Self {
+        Self {
+            entry_type: HistoryEntryType::PastedText,
+            ..Self::new_single_page(
+                String::new(),
+                title,
+                source_lang,
+                target_lang,
+                original_content,
+                translated_content,
+            )
         }
     }
 
+    /// 界面和导出中显示的来源，粘贴的文本没有URL
+    pub fn source_label(&self) -> String {
+        match self.entry_type {
+            HistoryEntryType::PastedText => "粘贴文本".to_string(),
+            _ => self.url.clone(),
+        }
+    }
+
+    /// 保存时是否应覆盖已有的记录：粘贴的文本每次都新增；
+    /// 批量翻译只覆盖同一任务之前写入的记录，保留上一次发布的结果作为增量翻译的基准；
+    /// 其他记录覆盖同一URL的记录
+    pub fn replaces(&self, existing: &HistoryEntry) -> bool {
+        match self.entry_type {
+            HistoryEntryType::PastedText => false,
+            HistoryEntryType::BatchTranslation => {
+                let job_id = |entry: &HistoryEntry| {
+                    entry
+                        .batch_data
+                        .as_ref()
+                        .map(|data| data.job_id.clone())
+                        .unwrap_or_default()
+                };
+                existing.url == self.url
+                    && matches!(existing.entry_type, HistoryEntryType::BatchTranslation)
+                    && !job_id(self).is_empty()
+                    && job_id(existing) == job_id(self)
+            }
+            _ => existing.url == self.url,
+        }
+    }
+
+    /// 记录是否早于给定的 RFC 3339 时间，时间无法解析时返回 false
+    pub fn created_before(&self, time: &str) -> bool {
+        match (
+            DateTime::parse_from_rfc3339(&self.created_at),
+            DateTime::parse_from_rfc3339(time),
+        ) {
+            (Ok(created_at), Ok(time)) => created_at < time,
+            _ => false,
+        }
+    }
+
+    /// 写入这条批量翻译记录前清理同一站点、同一语言对其他任务的旧记录，避免每次运行都保存一份完整译文。
+    /// 本次运行全部成功时不保留其他任务的记录；还有失败的文档、之后可能重试时，
+    /// 保留之前最近的一次记录作为重试时增量翻译的基准
+    pub fn prune_older_runs(&self, entries: &mut Vec<HistoryEntry>) {
+        let Some(data) = self.batch_data.as_ref() else {
+            return;
+        };
+        let same_target = |entry: &HistoryEntry| {
+            !self.replaces(entry)
+                && matches!(entry.entry_type, HistoryEntryType::BatchTranslation)
+                && entry.url == self.url
+                && entry.source_lang == self.source_lang
+                && entry.target_lang == self.target_lang
+        };
+
+        let baseline_id = if data.failed_documents > 0 {
+            entries
+                .iter()
+                .filter(|entry| same_target(entry))
+                .max_by(|a, b| a.created_at.cmp(&b.created_at))
+                .map(|entry| entry.id.clone())
+        } else {
+            None
+        };
+        entries.retain(|entry| !same_target(entry) || Some(&entry.id) == baseline_id.as_ref());
+    }
+
+    /// 记录各翻译引擎完成的文本块数
+    pub fn with_engine_usage(mut self, engine_usage: Vec<EngineUsage>) -> Self {
+        self.engine_usage = engine_usage;
+        self
+    }
+
+    /// 记录多引擎对比时每段选用的引擎
+    pub fn with_paragraph_engines(mut self, paragraph_engines: Vec<TranslationEngine>) -> Self {
+        self.paragraph_engines = paragraph_engines;
+        self
+    }
+
     // 保持向后兼容性
     pub fn new(
         url: String,
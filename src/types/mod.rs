@@ -1,3 +1,4 @@
 pub mod api_types;
+pub mod batch_job;
 pub mod history;
 pub mod translation;
@@ -0,0 +1,137 @@
+mod common;
+
+use common::{link, translated};
+use url_translator::error::AppError;
+use url_translator::services::batch_service::BatchFailure;
+use url_translator::types::batch_job::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_job() -> BatchJob {
+        BatchJob::new(
+            "https://example.com/docs/".to_string(),
+            "auto".to_string(),
+            "ZH".to_string(),
+            vec![link(2), link(0), link(1), link(3)],
+        )
+    }
+
+    #[test]
+    fn test_new_job_is_all_pending() {
+        let job = new_job();
+        assert_eq!(job.total_count(), 4);
+        assert_eq!(job.pending_count(), 4);
+        assert!(!job.is_finished());
+        assert_eq!(
+            job.pending_links()
+                .iter()
+                .map(|link| link.order)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert!(job.completed_documents().is_empty());
+    }
+
+    #[test]
+    fn test_checkpoint_skips_finished_documents() {
+        let mut job = new_job();
+        job.record_success(&translated(link(1)));
+        job.record_failure(&link(3), &AppError::extraction("HTTP 404"));
+        job.record_success(&translated(link(0)));
+
+        assert_eq!(job.completed_count(), 2);
+        assert_eq!(job.failed_count(), 1);
+        assert_eq!(job.pending_links(), vec![link(2)]);
+        assert_eq!(
+            job.documents
+                .iter()
+                .find(|doc| doc.link.order == 3)
+                .unwrap()
+                .status,
+            JobDocumentStatus::Failed(AppError::extraction("HTTP 404"))
+        );
+        // 译文按目录顺序返回
+        assert_eq!(
+            job.completed_documents()
+                .iter()
+                .map(|doc| doc.link.order)
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        job.record_success(&translated(link(2)));
+        assert!(job.is_finished());
+    }
+
+    #[test]
+    fn test_retry_failed_keeps_completed_documents() {
+        let mut job = new_job();
+        job.record_success(&translated(link(0)));
+        job.record_failure(&link(2), &AppError::network("连接超时"));
+        job.record_failure(&link(1), &AppError::translation("翻译结果为空"));
+        job.record_success(&translated(link(3)));
+        assert!(job.is_finished());
+
+        assert_eq!(
+            job.failures(),
+            vec![
+                BatchFailure {
+                    link: link(1),
+                    error: AppError::translation("翻译结果为空"),
+                },
+                BatchFailure {
+                    link: link(2),
+                    error: AppError::network("连接超时"),
+                },
+            ]
+        );
+
+        assert_eq!(job.reset_failed(), 2);
+        assert_eq!(job.failed_count(), 0);
+        assert_eq!(job.pending_links(), vec![link(1), link(2)]);
+        assert_eq!(job.completed_count(), 2);
+
+        // 重试结果合并到已完成的译文中
+        job.record_success(&translated(link(2)));
+        job.record_failure(&link(1), &AppError::translation("翻译结果为空"));
+        assert_eq!(
+            job.completed_documents()
+                .iter()
+                .map(|doc| doc.link.order)
+                .collect::<Vec<_>>(),
+            vec![0, 2, 3]
+        );
+        assert_eq!(job.failures().len(), 1);
+        assert_eq!(job.reset_failed(), 1);
+        assert_eq!(job.reset_failed(), 0);
+    }
+
+    #[test]
+    fn test_job_round_trips_through_json() {
+        let mut job = new_job();
+        job.record_success(&translated(link(0)));
+        job.record_failure(&link(1), &AppError::api("DeepL", "配额已用完"));
+        let json = serde_json::to_string(&job).unwrap();
+        let restored: BatchJob = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, job);
+        assert_eq!(restored.pending_count(), 2);
+        assert_eq!(
+            restored.failures()[0].error,
+            AppError::api("DeepL", "配额已用完")
+        );
+        assert_eq!(
+            restored.completed_documents()[0].translated_content,
+            "# 页面 0"
+        );
+    }
+
+    #[test]
+    fn test_unknown_document_is_ignored() {
+        let mut job = new_job();
+        job.record_success(&translated(link(9)));
+        assert_eq!(job.completed_count(), 0);
+        assert_eq!(job.pending_count(), 4);
+    }
+}
@@ -0,0 +1,223 @@
+mod common;
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+use url_translator::error::AppError;
+use url_translator::services::batch_service::*;
+use url_translator::services::mock_translator::MockConfig;
+use url_translator::types::api_types::{AppConfig, TranslationEngine};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_bounded_limits_concurrency() {
+        let running = Cell::new(0);
+        let peak = Cell::new(0);
+
+        let mut results = run_bounded(0..10, 3, |i| {
+            let running = &running;
+            let peak = &peak;
+            async move {
+                running.set(running.get() + 1);
+                peak.set(peak.get().max(running.get()));
+                // 后面的任务更快完成，检验结果按完成顺序返回
+                tokio::time::sleep(Duration::from_millis(30 - i * 2)).await;
+                running.set(running.get() - 1);
+                i * 10
+            }
+        })
+        .await;
+
+        assert_eq!(peak.get(), 3);
+        assert_eq!(results.len(), 10);
+        assert_ne!(results, (0..10).map(|i| i * 10).collect::<Vec<_>>());
+        results.sort();
+        assert_eq!(results, (0..10).map(|i| i * 10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_treats_zero_workers_as_one() {
+        let running = Cell::new(0);
+        let peak = Cell::new(0);
+
+        let results = run_bounded(vec!["a", "b", "c"], 0, |item| {
+            let running = &running;
+            let peak = &peak;
+            async move {
+                running.set(running.get() + 1);
+                peak.set(peak.get().max(running.get()));
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                running.set(running.get() - 1);
+                item.to_uppercase()
+            }
+        })
+        .await;
+
+        assert_eq!(peak.get(), 1);
+        assert_eq!(results, vec!["A", "B", "C"]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_control_pause_and_resume() {
+        let control = BatchControl::new();
+        assert_eq!(control.state(), ControlState::Running);
+        assert!(control.proceed().await);
+
+        control.pause();
+        assert_eq!(control.state(), ControlState::Paused);
+        let resumed_at = Cell::new(None);
+        let (proceeded, _) = tokio::join!(
+            async {
+                let proceeded = control.proceed().await;
+                // 恢复之前不会继续
+                assert!(resumed_at.get().is_some());
+                proceeded
+            },
+            async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                resumed_at.set(Some(()));
+                control.resume();
+            }
+        );
+        assert!(proceeded);
+        assert_eq!(control.state(), ControlState::Running);
+    }
+
+    #[tokio::test]
+    async fn test_batch_control_cancel_releases_paused_workers() {
+        let control = BatchControl::new();
+        let started = Cell::new(0);
+        control.pause();
+
+        let cancel = control.clone();
+        let results = tokio::join!(
+            run_bounded(0..6, 2, |i| {
+                let control = &control;
+                let started = &started;
+                async move {
+                    if !control.proceed().await {
+                        return None;
+                    }
+                    started.set(started.get() + 1);
+                    Some(i)
+                }
+            }),
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                cancel.cancel();
+                // 取消后不能再恢复
+                cancel.resume();
+            }
+        )
+        .0;
+
+        assert_eq!(started.get(), 0);
+        assert!(results.iter().all(Option::is_none));
+        assert!(control.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_interrupts_retry_delay() {
+        // 每次翻译都失败，第一次失败后进入 2 秒的重试等待
+        let config = AppConfig {
+            translation_engine: TranslationEngine::Mock,
+            mock: MockConfig {
+                fail_every: 1,
+                ..MockConfig::default()
+            },
+            ..AppConfig::default()
+        };
+        let service = BatchTranslationService::new(&config);
+        let control = BatchControl::new();
+        let started = Instant::now();
+
+        let (result, _) = tokio::join!(
+            service.batch_translate(vec![common::link(0)], &control, |_| {}, |_, _| {}),
+            async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                control.cancel();
+            }
+        );
+
+        assert!(started.elapsed() < Duration::from_millis(1000));
+        // 取消时尚未完成的文档保持待处理状态，不计为失败
+        let result = result.unwrap();
+        assert!(result.documents.is_empty());
+        assert!(result.failures.is_empty());
+    }
+
+    #[test]
+    fn test_failure_report() {
+        assert_eq!(failure_report(&[]), "");
+
+        let report = failure_report(&[BatchFailure {
+            link: DocumentLink {
+                title: "Installation".to_string(),
+                url: "https://example.com/docs/install".to_string(),
+                level: 0,
+                order: 4,
+            },
+            error: AppError::network("连接超时"),
+        }]);
+        assert_eq!(
+            report,
+            "## 翻译失败的文档 (1 个)\n\n\
+             - 5. Installation\n  \
+             - 原始URL: https://example.com/docs/install\n  \
+             - 错误: 网络请求失败: 连接超时\n\n"
+        );
+    }
+
+    #[test]
+    fn test_batch_config_defaults_and_clamping() {
+        assert_eq!(AppConfig::default().batch.concurrency, 3);
+        assert_eq!(BatchConfig { concurrency: 0 }.workers(), 1);
+        assert_eq!(
+            BatchConfig { concurrency: 64 }.workers(),
+            MAX_BATCH_CONCURRENCY
+        );
+
+        // 旧版本保存的配置没有 batch 字段
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("batch");
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.batch, BatchConfig::default());
+    }
+
+    #[tokio::test]
+    async fn test_structured_index_without_links_falls_back_to_generic_parsing() {
+        // 识别为 Sphinx 源文件，但其中没有 toctree（与按主机名识别的 readthedocs 页面相同）
+        let base = common::spawn_stub_routes(|_| {
+            vec![(
+                "/docs/_sources/index.rst.txt",
+                "text/plain",
+                b"Welcome\n=======\n\nNothing to list here.\n".to_vec(),
+            )]
+        })
+        .await;
+        let index_url = format!("{}/docs/_sources/index.rst.txt", base);
+
+        // 模拟引擎使用模拟提取器，通用解析可以离线完成
+        let config = AppConfig {
+            translation_engine: TranslationEngine::Mock,
+            ..AppConfig::default()
+        };
+        let links = BatchTranslationService::new(&config)
+            .parse_document_index(&index_url)
+            .await
+            .unwrap();
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| link.url.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                format!("{}/docs/_sources/getting-started", base),
+                format!("{}/docs/_sources/configuration", base),
+                format!("{}/docs/_sources/faq", base),
+            ]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! 集成测试共用的本地桩服务器和批量翻译测试数据
+#![allow(dead_code)]
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use url_translator::services::batch_service::{DocumentLink, TranslatedDocument};
+
+/// 启动一个只响应一次请求的本地桩服务器，返回基础地址和收到的原始请求
+pub async fn spawn_stub_server(
+    status_line: &'static str,
+    response_body: String,
+) -> (String, oneshot::Receiver<String>) {
+    spawn_stub_server_with_type(status_line, "application/json", response_body).await
+}
+
+/// 同上，可指定响应的Content-Type
+pub async fn spawn_stub_server_with_type(
+    status_line: &'static str,
+    content_type: &'static str,
+    response_body: String,
+) -> (String, oneshot::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+
+        // 读取请求头和请求体
+        loop {
+            let n = socket.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..n]);
+            let request = String::from_utf8_lossy(&buffer).to_string();
+            if let Some(header_end) = request.find("\r\n\r\n") {
+                let content_length = request[..header_end]
+                    .lines()
+                    .find_map(|line| {
+                        line.to_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if buffer.len() >= header_end + 4 + content_length {
+                    let _ = tx.send(request);
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status_line,
+            content_type,
+            response_body.len(),
+            response_body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+    });
+
+    (format!("http://{}", addr), rx)
+}
+
+/// 从原始HTTP请求中取出请求体
+pub fn request_body(request: &str) -> &str {
+    request
+        .find("\r\n\r\n")
+        .map(|pos| &request[pos + 4..])
+        .unwrap_or("")
+}
+
+/// 按请求路径响应的本地桩服务器，可处理多次请求，未配置的路径返回404。
+/// 路由表由基础地址生成，便于响应内容引用服务器自身的地址
+pub async fn spawn_stub_routes(
+    routes: impl FnOnce(&str) -> Vec<(&'static str, &'static str, Vec<u8>)>,
+) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let routes = routes(&base);
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut chunk).await.unwrap_or(0);
+                buffer.extend_from_slice(&chunk[..n]);
+                if n == 0 || request_complete(&buffer) {
+                    break;
+                }
+            }
+
+            let request = String::from_utf8_lossy(&buffer).to_string();
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+            let (status_line, content_type, body) = routes
+                .iter()
+                .find(|(route, _, _)| *route == path)
+                .map(|(_, content_type, body)| ("200 OK", *content_type, body.clone()))
+                .unwrap_or(("404 Not Found", "text/plain", b"not found".to_vec()));
+
+            let header = format!(
+                "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                status_line,
+                content_type,
+                body.len()
+            );
+            let _ = socket.write_all(header.as_bytes()).await;
+            let _ = socket.write_all(&body).await;
+        }
+    });
+
+    base
+}
+
+/// 请求头和Content-Length声明的请求体都已读完
+fn request_complete(buffer: &[u8]) -> bool {
+    let request = String::from_utf8_lossy(buffer);
+    let Some(header_end) = request.find("\r\n\r\n") else {
+        return false;
+    };
+    let content_length = request[..header_end]
+        .lines()
+        .find_map(|line| {
+            line.to_lowercase()
+                .strip_prefix("content-length:")
+                .and_then(|v| v.trim().parse::<usize>().ok())
+        })
+        .unwrap_or(0);
+    buffer.len() >= header_end + 4 + content_length
+}
+
+/// 批量翻译测试用的目录链接
+pub fn link(order: usize) -> DocumentLink {
+    DocumentLink {
+        title: format!("Page {}", order),
+        url: format!("https://example.com/docs/page-{}", order),
+        level: 0,
+        order,
+    }
+}
+
+/// 目录链接对应的译文文档
+pub fn translated(link: DocumentLink) -> TranslatedDocument {
+    TranslatedDocument {
+        original_content: format!("# {}", link.title),
+        translated_content: format!("# 页面 {}", link.order),
+        file_name: format!("{:03}_page.md", link.order),
+        folder_path: "documents".to_string(),
+        selected: true,
+        engine_usage: Vec::new(),
+        content_hash: String::new(),
+        change: Default::default(),
+        link,
+    }
+}
@@ -0,0 +1,134 @@
+use std::cell::RefCell;
+use url_translator::services::preview_service::*;
+use url_translator::services::translator::{
+    EngineUsage, TranslateFuture, Translator, TranslatorCapabilities,
+};
+use url_translator::types::api_types::TranslationEngine;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 模拟会拆分段落的大模型：译文中间夹着空行
+    struct SplittingTranslator {
+        requests: RefCell<Vec<String>>,
+    }
+
+    impl Translator for SplittingTranslator {
+        fn translate<'a>(
+            &'a self,
+            text: &'a str,
+            _source_lang: &'a str,
+            _target_lang: &'a str,
+        ) -> TranslateFuture<'a, String> {
+            self.requests.borrow_mut().push(text.to_string());
+            Box::pin(async move { Ok(format!("\n[译] {}\n\n（续）\n", text)) })
+        }
+
+        fn capabilities(&self) -> TranslatorCapabilities {
+            TranslatorCapabilities {
+                engine: TranslationEngine::Mock,
+                max_text_length: 5000,
+                max_requests_per_second: 10,
+                supports_auto_detect: true,
+                supports_streaming: false,
+            }
+        }
+    }
+
+    fn column(engine: TranslationEngine, paragraphs: &[&str]) -> ComparisonColumn {
+        ComparisonColumn {
+            engine,
+            paragraphs: paragraphs.iter().map(|p| p.to_string()).collect(),
+            error: None,
+        }
+    }
+
+    fn sample_content() -> ComparisonContent {
+        ComparisonContent {
+            title: "Numbers".to_string(),
+            original_text: "One\n\nTwo\n\nThree".to_string(),
+            original_paragraphs: vec!["One".into(), "Two".into(), "Three".into()],
+            columns: vec![
+                ComparisonColumn {
+                    engine: TranslationEngine::DeepLX,
+                    paragraphs: Vec::new(),
+                    error: Some("timeout".to_string()),
+                },
+                column(TranslationEngine::OpenAI, &["一", "二", "三"]),
+                column(TranslationEngine::DeepL, &["壹", "贰", "叁"]),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translate_paragraphs_keeps_rows_aligned() {
+        let translator = SplittingTranslator {
+            requests: Default::default(),
+        };
+        let paragraphs = vec![
+            "# Title".to_string(),
+            "__CODE_BLOCK_abc123__".to_string(),
+            "Run __CODE_BLOCK_def456__ first".to_string(),
+            "Last".to_string(),
+        ];
+
+        let translated = translate_paragraphs(&translator, &paragraphs, "EN", "ZH", 2)
+            .await
+            .unwrap();
+        // 每段单独翻译，引擎拆出的空行留在本段内，不会挤占后面的行
+        assert_eq!(
+            translated,
+            vec![
+                "[译] # Title\n\n（续）",
+                "__CODE_BLOCK_abc123__",
+                "[译] Run __CODE_BLOCK_def456__ first\n\n（续）",
+                "[译] Last\n\n（续）",
+            ]
+        );
+        // 只有代码块的段落不发送请求
+        let mut requests = translator.requests.borrow().clone();
+        requests.sort();
+        assert_eq!(
+            requests,
+            vec!["# Title", "Last", "Run __CODE_BLOCK_def456__ first"]
+        );
+    }
+
+    #[test]
+    fn test_compose_uses_selected_paragraphs() {
+        let content = sample_content();
+        assert_eq!(content.default_choices(), vec![1, 1, 1]);
+
+        let choices = vec![1, 2, 1];
+        assert_eq!(content.compose(&choices), "一\n\n贰\n\n三");
+        assert_eq!(
+            content.chosen_engines(&choices),
+            vec![
+                TranslationEngine::OpenAI,
+                TranslationEngine::DeepL,
+                TranslationEngine::OpenAI
+            ]
+        );
+        assert_eq!(
+            content.engine_usage(&choices),
+            vec![
+                EngineUsage {
+                    engine: TranslationEngine::OpenAI,
+                    chunks: 2
+                },
+                EngineUsage {
+                    engine: TranslationEngine::DeepL,
+                    chunks: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_choices_fall_back_to_default_column() {
+        let content = sample_content();
+        // 失败的列、越界的列和缺失的选择都改用第一个成功的引擎
+        assert_eq!(content.compose(&[0, 7]), "一\n\n二\n\n三");
+    }
+}
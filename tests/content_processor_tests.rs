@@ -222,4 +222,17 @@ fn main() {}
         assert!(summary.contains("个代码块"));
         assert!(stats.total_blocks() > 0);
     }
+
+    #[test]
+    fn test_protect_html_comments() {
+        let mut processor = ContentProcessor::new();
+        let content = "<!-- page 1 -->\n\n第一页正文。\n\n<!-- page 2 -->\n\n第二页正文。";
+
+        let protected = processor.protect_code_blocks(content);
+        assert!(!protected.contains("<!--"));
+        assert!(protected.contains("第一页正文。"));
+
+        let restored = processor.restore_code_blocks(&protected);
+        assert_eq!(restored, content);
+    }
 }
@@ -0,0 +1,219 @@
+use url::Url;
+use url_translator::services::crawler::*;
+use url_translator::services::readability_extractor::extract_from_html;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, url: &str) -> bool {
+        glob_matches(pattern, &Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url(
+                "https://Example.com:443/docs/guide/?utm_source=x&page=2&fbclid=abc#install"
+            ),
+            Some("https://example.com/docs/guide?page=2".to_string())
+        );
+        assert_eq!(
+            normalize_url("https://example.com/docs/?ref=nav"),
+            Some("https://example.com/docs".to_string())
+        );
+        assert_eq!(
+            normalize_url("https://example.com/"),
+            Some("https://example.com/".to_string())
+        );
+        assert_eq!(normalize_url("mailto:team@example.com"), None);
+        assert_eq!(normalize_url("/relative/path"), None);
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(matches("/docs/**", "https://example.com/docs/a/b/c"));
+        assert!(matches("/docs/*", "https://example.com/docs/intro"));
+        assert!(!matches("/docs/*", "https://example.com/docs/guide/intro"));
+        assert!(matches(
+            "**/changelog*",
+            "https://example.com/docs/changelog-2024"
+        ));
+        assert!(matches("/docs/**/api", "https://example.com/docs/api"));
+        assert!(matches("/v?/*", "https://example.com/v2/intro"));
+        assert!(matches("/*?lang=*", "https://example.com/page?lang=en"));
+        assert!(matches(
+            "https://example.com/blog/**",
+            "https://example.com/blog/post"
+        ));
+        assert!(!matches(
+            "https://other.com/**",
+            "https://example.com/blog/post"
+        ));
+    }
+
+    #[test]
+    fn test_parse_patterns() {
+        assert_eq!(
+            parse_patterns(" /docs/** ,\n\n**/old/*\n"),
+            vec!["/docs/**".to_string(), "**/old/*".to_string()]
+        );
+        assert!(parse_patterns("  \n ").is_empty());
+    }
+
+    #[test]
+    fn test_crawler_respects_depth_scope_and_dedupe() {
+        let mut crawler = Crawler::new(
+            "https://example.com/docs/",
+            CrawlOptions {
+                max_depth: 1,
+                ..CrawlOptions::default()
+            },
+        )
+        .unwrap();
+
+        let (url, depth) = crawler.next_page().unwrap();
+        assert_eq!((url.as_str(), depth), ("https://example.com/docs/", 0));
+        crawler.visit(
+            &url,
+            depth,
+            "Docs Home",
+            [
+                "https://example.com/docs/intro#top",
+                "https://example.com/docs/intro?utm_medium=nav",
+                "https://example.com/docs/guide/",
+                "https://example.com/docs/logo.png",
+                "https://example.com/blog/news",
+                "https://other.com/docs/intro",
+                "http://example.com/docs/insecure",
+            ],
+        );
+
+        let (url, depth) = crawler.next_page().unwrap();
+        assert_eq!((url.as_str(), depth), ("https://example.com/docs/intro", 1));
+        // 达到最大深度，链接不再加入队列
+        crawler.visit(&url, depth, "", ["https://example.com/docs/deeper"]);
+
+        let (url, depth) = crawler.next_page().unwrap();
+        assert_eq!(
+            (url.as_str(), depth),
+            ("https://example.com/docs/guide/", 1)
+        );
+        crawler.visit(&url, depth, "User Guide", std::iter::empty());
+
+        assert!(crawler.next_page().is_none());
+        assert_eq!(crawler.visited(), 3);
+
+        let links = crawler.into_links();
+        let summary: Vec<(&str, &str, usize, usize)> = links
+            .iter()
+            .map(|link| {
+                (
+                    link.title.as_str(),
+                    link.url.as_str(),
+                    link.level,
+                    link.order,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Docs Home", "https://example.com/docs/", 0, 0),
+                ("Intro", "https://example.com/docs/intro", 1, 1),
+                ("User Guide", "https://example.com/docs/guide/", 1, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crawler_include_exclude_and_origin_scope() {
+        let mut crawler = Crawler::new(
+            "https://example.com/docs/index.html",
+            CrawlOptions {
+                max_depth: 3,
+                scope: CrawlScope::Origin,
+                include: vec!["/docs/api/**".to_string()],
+                exclude: vec!["**/internal/**".to_string()],
+                max_pages: 3,
+            },
+        )
+        .unwrap();
+
+        let (url, depth) = crawler.next_page().unwrap();
+        crawler.visit(
+            &url,
+            depth,
+            "Index",
+            [
+                "https://example.com/docs/api/client",
+                "https://example.com/docs/api/internal/secret",
+                "https://example.com/blog/release",
+                "https://example.com/docs/api/server",
+            ],
+        );
+        while let Some((url, depth)) = crawler.next_page() {
+            crawler.visit(&url, depth, "", std::iter::empty());
+        }
+
+        // 起始页面不匹配包含模式，只用于发现链接；页面上限为 3
+        assert_eq!(crawler.visited(), 3);
+        let urls: Vec<String> = crawler
+            .into_links()
+            .into_iter()
+            .map(|link| link.url)
+            .collect();
+        assert_eq!(
+            urls,
+            vec!["https://example.com/docs/api/client".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_crawler_keeps_trailing_slash_for_relative_links() {
+        let mut crawler = Crawler::new(
+            "https://docs.example.com/guide/?utm_source=x#top",
+            CrawlOptions::default(),
+        )
+        .unwrap();
+
+        // 目录式页面按原地址访问，相对链接才能解析到 /guide/ 之下
+        let (url, depth) = crawler.next_page().unwrap();
+        assert_eq!(url, "https://docs.example.com/guide/");
+        let content = extract_from_html(
+            r#"<html><head><title>Guide</title></head><body><article>
+                <h1>Guide</h1>
+                <p>Start with <a href="install/">Install</a>, then read
+                <a href="config/#options">Config</a>, <a href="install">Install again</a>
+                and <a href="../blog/">Blog</a>.</p>
+            </article></body></html>"#,
+            &url,
+        );
+        crawler.visit(
+            &url,
+            depth,
+            &content.title,
+            content.links.iter().map(|link| link.url.as_str()),
+        );
+
+        let mut queued = Vec::new();
+        while let Some((url, depth)) = crawler.next_page() {
+            crawler.visit(&url, depth, "", std::iter::empty());
+            queued.push(url);
+        }
+        assert_eq!(
+            queued,
+            vec![
+                "https://docs.example.com/guide/install/".to_string(),
+                "https://docs.example.com/guide/config/".to_string(),
+            ]
+        );
+        assert_eq!(crawler.found(), 3);
+    }
+
+    #[test]
+    fn test_crawler_rejects_invalid_start() {
+        assert!(Crawler::new("not a url", CrawlOptions::default()).is_err());
+        assert!(Crawler::new("ftp://example.com/docs", CrawlOptions::default()).is_err());
+    }
+}
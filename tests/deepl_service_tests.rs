@@ -0,0 +1,139 @@
+mod common;
+
+use url_translator::error::AppError;
+use url_translator::services::deepl_service::*;
+use url_translator::types::api_types::{AppConfig, TranslationEngine};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{request_body, spawn_stub_server};
+
+    fn test_config(base_url: String) -> AppConfig {
+        let mut config = AppConfig {
+            translation_engine: TranslationEngine::DeepL,
+            ..AppConfig::default()
+        };
+        config.deepl.api_url = format!("{}/v2/translate", base_url);
+        config.deepl.auth_key = "test-key:fx".to_string();
+        config
+    }
+
+    #[tokio::test]
+    async fn test_translate_batch_against_stub_server() {
+        let body = serde_json::json!({
+            "translations": [
+                { "detected_source_language": "EN", "text": "你好" },
+                { "detected_source_language": "EN", "text": "参见 __CODE_BLOCK_ab12__" }
+            ]
+        })
+        .to_string();
+        let (url, request_rx) = spawn_stub_server("200 OK", body).await;
+        let service = DeepLService::new(&test_config(url));
+
+        let texts = vec!["Hello".to_string(), "See __CODE_BLOCK_ab12__".to_string()];
+        let result = service.translate_batch(&texts, "auto", "zh").await.unwrap();
+        assert_eq!(result, vec!["你好", "参见 __CODE_BLOCK_ab12__"]);
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("POST /v2/translate"));
+        assert!(request
+            .to_lowercase()
+            .contains("authorization: deepl-auth-key test-key:fx"));
+
+        let json: serde_json::Value = serde_json::from_str(request_body(&request)).unwrap();
+        assert_eq!(json["text"].as_array().unwrap().len(), 2);
+        assert_eq!(json["target_lang"], "ZH");
+        assert!(json.get("source_lang").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_quota_and_rate_limit_status_map_to_rate_limit_error() {
+        for status in ["456 Quota Exceeded", "429 Too Many Requests"] {
+            let body = serde_json::json!({ "message": "limit" }).to_string();
+            let (url, _request_rx) = spawn_stub_server(status, body).await;
+            let service = DeepLService::new(&test_config(url));
+
+            let error = service
+                .translate_batch(&["Hello".to_string()], "EN", "ZH")
+                .await
+                .unwrap_err();
+            assert!(matches!(error, AppError::RateLimitError { .. }));
+            assert!(error.is_retryable());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_auth_key_is_config_error() {
+        let mut config = AppConfig::default();
+        config.deepl.auth_key = String::new();
+        let service = DeepLService::new(&config);
+
+        let error = service
+            .translate_batch(&["Hello".to_string()], "EN", "ZH")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::ConfigError { .. }));
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_build_deepl_request_optional_fields() {
+        let config = DeepLConfig {
+            formality: "more".to_string(),
+            tag_handling: "markdown".to_string(),
+            glossary_id: "glossary-1".to_string(),
+            ..DeepLConfig::default()
+        };
+        let texts = vec!["Hello".to_string()];
+
+        let request = build_deepl_request(&config, &texts, "en", "de").unwrap();
+        assert_eq!(request.source_lang.as_deref(), Some("EN"));
+        assert_eq!(request.target_lang, "DE");
+        assert_eq!(request.formality.as_deref(), Some("more"));
+        assert_eq!(request.tag_handling.as_deref(), Some("markdown"));
+        assert_eq!(request.glossary_id.as_deref(), Some("glossary-1"));
+
+        // 自动检测源语言时不能使用术语表，返回校验错误而不是静默丢弃
+        let error = build_deepl_request(&config, &texts, "auto", "de").unwrap_err();
+        assert!(matches!(error, AppError::ValidationError { .. }));
+        let request = build_deepl_request(&DeepLConfig::default(), &texts, "auto", "de").unwrap();
+        assert!(request.source_lang.is_none());
+        assert!(request.glossary_id.is_none());
+
+        let request = build_deepl_request(&DeepLConfig::default(), &texts, "EN", "ZH").unwrap();
+        assert!(request.formality.is_none());
+        assert!(request.tag_handling.is_none());
+        assert!(request.preserve_formatting);
+    }
+
+    #[test]
+    fn test_map_deepl_error() {
+        assert!(matches!(
+            map_deepl_error(403, r#"{"message": "Wrong endpoint"}"#),
+            AppError::ConfigError { .. }
+        ));
+        assert!(matches!(
+            map_deepl_error(413, ""),
+            AppError::TranslationError { .. }
+        ));
+        assert!(matches!(
+            map_deepl_error(500, "oops"),
+            AppError::ApiError { .. }
+        ));
+    }
+
+    #[test]
+    fn test_group_into_batches() {
+        let chunks: Vec<String> = (0..120).map(|i| format!("chunk {}", i)).collect();
+        let batches = group_into_batches(&chunks);
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![50, 50, 20]
+        );
+
+        let large = vec!["a".repeat(80 * 1024), "b".repeat(80 * 1024)];
+        assert_eq!(group_into_batches(&large).len(), 2);
+        assert!(group_into_batches(&[]).is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::time::{Duration, Instant};
+use url_translator::services::deeplx_service::*;
+use url_translator::services::translator::split_text_into_chunks;
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::spawn_stub_routes;
+
+    #[tokio::test]
+    async fn test_concurrent_chunks_share_rate_limit() {
+        let body = serde_json::json!({ "code": 200, "data": "译文", "alternatives": [] })
+            .to_string()
+            .into_bytes();
+        let base = spawn_stub_routes(|_| vec![("/translate", "application/json", body)]).await;
+
+        let mut config = AppConfig {
+            deeplx_api_url: format!("{}/translate", base),
+            max_requests_per_second: 2,
+            max_text_length: 14,
+            chunk_concurrency: 4,
+            ..AppConfig::default()
+        };
+        config.translation_memory.enabled = false;
+
+        let text = "First part.\n\nSecond bit.\n\nThird part.\n\nFourth bit.";
+        assert_eq!(
+            split_text_into_chunks(text, config.max_text_length).len(),
+            4
+        );
+
+        let started = Instant::now();
+        let result = DeepLXService::new(&config)
+            .translate_text(text, "EN", "ZH")
+            .await
+            .unwrap();
+        assert_eq!(result, "译文\n\n译文\n\n译文\n\n译文");
+        // 四个文本块同时发送，但每秒最多两个请求，后两个要等下一个时间窗口
+        assert!(started.elapsed() >= Duration::from_millis(950));
+    }
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use url_translator::error::AppError;
+use url_translator::services::extractor::*;
+use url_translator::services::jina_service::*;
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::spawn_stub_server_with_type;
+
+    const READER_OUTPUT: &str = "Title: Getting Started\n\nURL Source: https://docs.example.com/guide/start\n\nMarkdown Content:\nWelcome to the guide.\n\nSee [Install](install.html) and [API](/api/) or [Install again](./install.html).\n\n![logo](logo.png) [Top](#top)";
+
+    #[test]
+    fn test_parse_reader_text() {
+        let content = parse_reader_text(READER_OUTPUT, "https://docs.example.com/start");
+
+        assert_eq!(content.title, "Getting Started");
+        assert_eq!(
+            content.canonical_url,
+            "https://docs.example.com/guide/start"
+        );
+        assert!(content.markdown.starts_with("Welcome to the guide."));
+        assert!(!content.markdown.contains("URL Source:"));
+        assert_eq!(
+            content.links,
+            vec![
+                ExtractedLink {
+                    text: "Install".to_string(),
+                    url: "https://docs.example.com/guide/install.html".to_string(),
+                },
+                ExtractedLink {
+                    text: "API".to_string(),
+                    url: "https://docs.example.com/api/".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_text_without_headers() {
+        let content = parse_reader_text("# Heading\n\nBody", "https://example.com/a");
+        assert_eq!(content.title, "Heading");
+        assert_eq!(content.canonical_url, "https://example.com/a");
+        assert_eq!(content.markdown, "# Heading\n\nBody");
+        assert_eq!(content.document(), "# Heading\n\nBody");
+    }
+
+    #[test]
+    fn test_document_adds_missing_title() {
+        let content = ExtractedContent {
+            title: "Guide".to_string(),
+            markdown: "## Section\n\nText".to_string(),
+            ..ExtractedContent::default()
+        };
+        assert_eq!(content.document(), "# Guide\n\n## Section\n\nText");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reader_text_mode() {
+        let (url, request_rx) =
+            spawn_stub_server_with_type("200 OK", "text/plain", READER_OUTPUT.to_string()).await;
+        let config = AppConfig {
+            jina_api_url: format!("{}/", url),
+            jina: JinaConfig {
+                json_mode: false,
+                ..JinaConfig::default()
+            },
+            ..AppConfig::default()
+        };
+        let service = JinaService::new(&config);
+
+        let content = service
+            .fetch_reader("https://docs.example.com/guide/start")
+            .await
+            .unwrap();
+        assert_eq!(content.title, "Getting Started");
+        assert_eq!(content.links.len(), 2);
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("GET /https://docs.example.com/guide/start "));
+        assert!(!request.to_lowercase().contains("authorization"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reader_json_mode_sends_headers() {
+        let body = serde_json::json!({
+            "code": 200,
+            "status": 20000,
+            "data": {
+                "title": "Getting Started",
+                "url": "https://docs.example.com/guide/start",
+                "content": "Welcome.\n\nSee [Install](install.html).",
+                "links": { "Install": "https://docs.example.com/guide/install.html", "Home": "/" }
+            }
+        })
+        .to_string();
+        let (url, request_rx) =
+            spawn_stub_server_with_type("200 OK", "application/json", body).await;
+        let config = AppConfig {
+            jina_api_url: url,
+            jina: JinaConfig {
+                api_key: "jina_secret".to_string(),
+                target_selector: "article".to_string(),
+                remove_selector: "nav, footer".to_string(),
+                with_generated_alt: true,
+                no_cache: true,
+                ..JinaConfig::default()
+            },
+            ..AppConfig::default()
+        };
+
+        let content = JinaService::new(&config)
+            .fetch_reader("https://docs.example.com/guide/start")
+            .await
+            .unwrap();
+        assert_eq!(content.title, "Getting Started");
+        assert_eq!(content.markdown, "Welcome.\n\nSee [Install](install.html).");
+        assert_eq!(
+            content
+                .links
+                .iter()
+                .map(|link| link.url.as_str())
+                .collect::<Vec<_>>(),
+            // 保持页面中的顺序，不按文本排序
+            vec![
+                "https://docs.example.com/guide/install.html",
+                "https://docs.example.com/"
+            ]
+        );
+
+        let request = request_rx.await.unwrap().to_lowercase();
+        assert!(request.contains("accept: application/json"));
+        assert!(request.contains("authorization: bearer jina_secret"));
+        assert!(request.contains("x-target-selector: article"));
+        assert!(request.contains("x-remove-selector: nav, footer"));
+        assert!(request.contains("x-with-generated-alt: true"));
+        assert!(request.contains("x-no-cache: true"));
+        assert!(!request.contains("x-return-format"));
+    }
+
+    #[test]
+    fn test_parse_reader_json_link_pairs_and_fallback() {
+        let body = serde_json::json!({
+            "data": {
+                "title": "Docs",
+                "content": "Read [Guide](guide/).",
+                "links": [["Guide", "guide/"], ["Guide again", "guide/"]]
+            }
+        })
+        .to_string();
+        let content = parse_reader_json(&body, "https://example.com/docs/").unwrap();
+        assert_eq!(content.canonical_url, "https://example.com/docs/");
+        assert_eq!(content.links.len(), 1);
+        assert_eq!(content.links[0].url, "https://example.com/docs/guide/");
+
+        let body = serde_json::json!({ "data": { "content": "See [A](a.html)" } }).to_string();
+        let content = parse_reader_json(&body, "https://example.com/").unwrap();
+        assert_eq!(content.links[0].url, "https://example.com/a.html");
+
+        assert!(parse_reader_json("{\"data\": null}", "https://example.com/").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reader_error_status() {
+        let (url, _request_rx) =
+            spawn_stub_server_with_type("502 Bad Gateway", "text/plain", "upstream".into()).await;
+        let config = AppConfig {
+            jina_api_url: url,
+            ..AppConfig::default()
+        };
+
+        let error = JinaService::new(&config)
+            .fetch_reader("https://example.com")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::ApiError { .. }));
+        assert!(error.is_retryable());
+    }
+}
@@ -0,0 +1,186 @@
+use std::cell::RefCell;
+use url_translator::error::AppError;
+use url_translator::services::fallback_translator::FallbackTranslator;
+use url_translator::services::translator::*;
+use url_translator::types::api_types::{AppConfig, TranslationEngine};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按预设行为返回结果的测试翻译器
+    struct StubTranslator {
+        engine: TranslationEngine,
+        max_text_length: usize,
+        fail_with: Option<fn() -> AppError>,
+    }
+
+    impl StubTranslator {
+        fn ok(engine: TranslationEngine) -> Self {
+            Self {
+                engine,
+                max_text_length: 5000,
+                fail_with: None,
+            }
+        }
+
+        fn failing(engine: TranslationEngine, error: fn() -> AppError) -> Self {
+            Self {
+                fail_with: Some(error),
+                ..Self::ok(engine)
+            }
+        }
+    }
+
+    impl Translator for StubTranslator {
+        fn translate<'a>(
+            &'a self,
+            text: &'a str,
+            _source_lang: &'a str,
+            _target_lang: &'a str,
+        ) -> TranslateFuture<'a, String> {
+            let result = match self.fail_with {
+                Some(error) => Err(error()),
+                None => Ok(format!("[{}] {}", self.engine.display_name(), text)),
+            };
+            Box::pin(async move { result })
+        }
+
+        fn capabilities(&self) -> TranslatorCapabilities {
+            TranslatorCapabilities {
+                engine: self.engine,
+                max_text_length: self.max_text_length,
+                max_requests_per_second: 10,
+                supports_auto_detect: true,
+                supports_streaming: false,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retryable_error_falls_back_to_next_engine() {
+        let chain = FallbackTranslator::from_translators(vec![
+            Box::new(StubTranslator::failing(TranslationEngine::DeepLX, || {
+                AppError::translation("空的翻译结果")
+            })),
+            Box::new(StubTranslator::ok(TranslationEngine::OpenAI)),
+        ]);
+
+        let progress = RefCell::new(Vec::new());
+        let on_progress = |partial: &str| progress.borrow_mut().push(partial.to_string());
+        let outcome = chain
+            .translate_with_report("Hello world", "EN", "ZH", &on_progress)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.text, "[OpenAI] Hello world");
+        assert_eq!(
+            outcome.chunks,
+            vec![ChunkRecord {
+                index: 0,
+                engine: TranslationEngine::OpenAI
+            }]
+        );
+        assert_eq!(
+            progress.into_inner().last().unwrap(),
+            "[OpenAI] Hello world"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_each_chunk_records_its_engine() {
+        let mut primary = StubTranslator::ok(TranslationEngine::DeepLX);
+        primary.max_text_length = 12;
+        let chain = FallbackTranslator::from_translators(vec![
+            Box::new(primary),
+            Box::new(StubTranslator::ok(TranslationEngine::DeepL)),
+        ]);
+
+        let outcome = chain
+            .translate_with_report("First part. Second part.", "EN", "ZH", &|_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.chunks.len(), 2);
+        assert!(outcome
+            .chunks
+            .iter()
+            .all(|chunk| chunk.engine == TranslationEngine::DeepLX));
+        assert_eq!(
+            outcome.engine_usage(),
+            vec![EngineUsage {
+                engine: TranslationEngine::DeepLX,
+                chunks: 2
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_stops_chain() {
+        let chain = FallbackTranslator::from_translators(vec![
+            Box::new(StubTranslator::failing(TranslationEngine::DeepL, || {
+                AppError::config("未配置DeepL Auth Key")
+            })),
+            Box::new(StubTranslator::ok(TranslationEngine::OpenAI)),
+        ]);
+
+        let error = chain.translate("Hello", "EN", "ZH").await.unwrap_err();
+        assert!(matches!(error, AppError::ConfigError { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_all_engines_failing_reports_each_failure() {
+        let chain = FallbackTranslator::from_translators(vec![
+            Box::new(StubTranslator::failing(TranslationEngine::DeepLX, || {
+                AppError::network("timeout")
+            })),
+            Box::new(StubTranslator::failing(TranslationEngine::OpenAI, || {
+                AppError::rate_limit("slow down")
+            })),
+        ]);
+
+        let error = chain.translate("Hello", "EN", "ZH").await.unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("DeepLX"));
+        assert!(message.contains("OpenAI"));
+    }
+
+    #[test]
+    fn test_engine_chain_deduplicates() {
+        let config = AppConfig {
+            translation_engine: TranslationEngine::OpenAI,
+            fallback_engines: vec![
+                TranslationEngine::DeepLX,
+                TranslationEngine::OpenAI,
+                TranslationEngine::DeepLX,
+            ],
+            ..AppConfig::default()
+        };
+        assert_eq!(
+            engine_chain(&config),
+            vec![TranslationEngine::OpenAI, TranslationEngine::DeepLX]
+        );
+
+        let translator = create_translator(&config);
+        assert_eq!(translator.capabilities().engine, TranslationEngine::OpenAI);
+    }
+
+    #[test]
+    fn test_merge_and_format_engine_usage() {
+        let usage = merge_engine_usage(vec![
+            EngineUsage {
+                engine: TranslationEngine::DeepLX,
+                chunks: 3,
+            },
+            EngineUsage {
+                engine: TranslationEngine::OpenAI,
+                chunks: 1,
+            },
+            EngineUsage {
+                engine: TranslationEngine::DeepLX,
+                chunks: 2,
+            },
+        ]);
+        assert_eq!(format_engine_usage(&usage), "DeepLX 5 块, OpenAI 1 块");
+    }
+}
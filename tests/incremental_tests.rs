@@ -0,0 +1,349 @@
+mod common;
+
+use common::link;
+use url_translator::services::batch_service::TranslatedDocument;
+use url_translator::services::incremental::*;
+use url_translator::services::translator::{
+    ChunkRecord, TranslateFuture, Translator, TranslatorCapabilities,
+};
+use url_translator::types::api_types::TranslationEngine;
+use url_translator::types::batch_job::BatchJob;
+use url_translator::types::history::{
+    BatchDocumentInfo, BatchTranslationData, HistoryEntry, HistoryEntryType,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX_URL: &str = "https://example.com/docs/";
+
+    /// 给文本加上前缀并记录收到的请求
+    struct PrefixTranslator {
+        requests: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl Translator for PrefixTranslator {
+        fn translate<'a>(
+            &'a self,
+            text: &'a str,
+            _source_lang: &'a str,
+            _target_lang: &'a str,
+        ) -> TranslateFuture<'a, String> {
+            self.requests.borrow_mut().push(text.to_string());
+            Box::pin(async move { Ok(format!("[译] {}", text)) })
+        }
+
+        fn capabilities(&self) -> TranslatorCapabilities {
+            TranslatorCapabilities {
+                engine: TranslationEngine::Mock,
+                max_text_length: 5000,
+                max_requests_per_second: 10,
+                supports_auto_detect: true,
+                supports_streaming: false,
+            }
+        }
+    }
+
+    fn previous_doc(order: usize, original: &str, hash: &str) -> BatchDocumentInfo {
+        let link = link(order);
+        BatchDocumentInfo {
+            title: link.title,
+            url: link.url,
+            file_name: format!("{:03}_page.md", order),
+            folder_path: "documents".to_string(),
+            order,
+            translated: true,
+            original_content: original.to_string(),
+            translated_content: format!("# 页面 {}", order),
+            engine_usage: Vec::new(),
+            content_hash: hash.to_string(),
+        }
+    }
+
+    fn history_entry(created_at: &str, documents: Vec<BatchDocumentInfo>) -> HistoryEntry {
+        HistoryEntry {
+            id: "history-1".to_string(),
+            url: INDEX_URL.to_string(),
+            title: "批量翻译: example.com".to_string(),
+            source_lang: "auto".to_string(),
+            target_lang: "ZH".to_string(),
+            original_content: String::new(),
+            translated_content: String::new(),
+            created_at: created_at.to_string(),
+            word_count: 0,
+            entry_type: HistoryEntryType::BatchTranslation,
+            batch_data: Some(BatchTranslationData {
+                total_documents: documents.len(),
+                successful_documents: documents.len(),
+                failed_documents: 0,
+                index_url: INDEX_URL.to_string(),
+                document_list: documents,
+                job_id: String::new(),
+            }),
+            engine_usage: Vec::new(),
+            paragraph_engines: Vec::new(),
+        }
+    }
+
+    fn translated(order: usize, change: DocumentChange) -> TranslatedDocument {
+        TranslatedDocument {
+            change,
+            ..common::translated(link(order))
+        }
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_eq!(content_hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(
+            content_hash("  # Title\n\nBody\n"),
+            content_hash("# Title\n\nBody")
+        );
+        assert_ne!(
+            content_hash("# Title\n\nBody"),
+            content_hash("# Title\n\nBody!")
+        );
+    }
+
+    #[test]
+    fn test_classify_against_previous_translation() {
+        let entry = history_entry(
+            "2026-01-01T00:00:00.000Z",
+            vec![
+                previous_doc(0, "# Intro", &content_hash("# Intro")),
+                // 旧记录没有哈希，从保存的原文补算
+                previous_doc(1, "# Guide", ""),
+            ],
+        );
+        let previous = PreviousTranslations::from_history(&entry).unwrap();
+
+        let url0 = link(0).url;
+        let url1 = link(1).url;
+        let url2 = link(2).url;
+        assert_eq!(
+            previous.classify(&url0, &content_hash("# Intro")),
+            DocumentChange::Unchanged
+        );
+        assert_eq!(
+            previous.classify(&url1, &content_hash("# Guide")),
+            DocumentChange::Unchanged
+        );
+        assert_eq!(
+            previous.classify(&url1, &content_hash("# Guide v2")),
+            DocumentChange::Modified
+        );
+        assert_eq!(
+            previous.classify(&url2, &content_hash("# New")),
+            DocumentChange::Added
+        );
+
+        let reused = previous.reusable(&url0, &content_hash("# Intro")).unwrap();
+        assert_eq!(reused.translated_content, "# 页面 0");
+        assert!(previous
+            .reusable(&url1, &content_hash("# Guide v2"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_previous_translation_must_predate_job() {
+        let job = BatchJob::new(
+            INDEX_URL.to_string(),
+            "auto".to_string(),
+            "ZH".to_string(),
+            vec![link(0)],
+        );
+        let docs = vec![previous_doc(0, "# Intro", "")];
+
+        let earlier = history_entry("2000-01-01T00:00:00.000Z", docs.clone());
+        assert!(PreviousTranslations::for_job(&earlier, &job).is_some());
+
+        // 任务自己写入的记录不作为比较基准
+        let later = history_entry("2999-01-01T00:00:00.000Z", docs.clone());
+        assert!(PreviousTranslations::for_job(&later, &job).is_none());
+
+        let mut other_lang = earlier.clone();
+        other_lang.target_lang = "JA".to_string();
+        assert!(PreviousTranslations::for_job(&other_lang, &job).is_none());
+
+        let empty = history_entry("2000-01-01T00:00:00.000Z", Vec::new());
+        assert!(PreviousTranslations::for_job(&empty, &job).is_none());
+    }
+
+    #[test]
+    fn test_job_history_keeps_previous_release() {
+        let with_job = |created_at: &str, job_id: &str| {
+            let mut entry = history_entry(created_at, Vec::new());
+            entry.batch_data.as_mut().unwrap().job_id = job_id.to_string();
+            entry
+        };
+        let release = with_job("2026-01-01T00:00:00.000Z", "job-1");
+        let first_run = with_job("2026-02-01T00:10:00.000Z", "job-2");
+        let retry = with_job("2026-02-01T00:20:00.000Z", "job-2");
+
+        // 重试只覆盖本任务写入的记录，上一次发布的记录保留下来
+        assert!(retry.replaces(&first_run));
+        assert!(!first_run.replaces(&release));
+        assert!(!with_job("2026-02-01T00:10:00.000Z", "")
+            .replaces(&history_entry("2026-01-01T00:00:00.000Z", Vec::new())));
+
+        // 任务开始之后写入的记录不早于任务创建时间，查找时会跳过
+        let job_created_at = "2026-02-01T00:00:00.000Z";
+        assert!(release.created_before(job_created_at));
+        assert!(!first_run.created_before(job_created_at));
+        assert!(!release.created_before("not a date"));
+    }
+
+    #[test]
+    fn test_history_keeps_latest_run_and_retry_baseline() {
+        let run = |created_at: &str, job_id: &str, failed_documents: usize| {
+            let mut entry = history_entry(created_at, vec![previous_doc(0, "# Intro", "")]);
+            entry.id = format!("{}-{}", job_id, created_at);
+            let data = entry.batch_data.as_mut().unwrap();
+            data.job_id = job_id.to_string();
+            data.failed_documents = failed_documents;
+            entry
+        };
+        let ids = |entries: &[HistoryEntry]| -> Vec<String> {
+            entries.iter().map(|entry| entry.id.clone()).collect()
+        };
+        let older = run("2025-12-01T00:00:00.000Z", "job-0", 0);
+        let release = run("2026-01-01T00:00:00.000Z", "job-1", 0);
+        let mut other_site = run("2025-11-01T00:00:00.000Z", "job-9", 0);
+        other_site.url = "https://example.org/docs/".to_string();
+
+        // 新任务还有失败的文档：保留上一次发布的记录作为重试的基准，更早的记录删除
+        let mut entries = vec![release.clone(), older.clone(), other_site.clone()];
+        let first_run = run("2026-02-01T00:10:00.000Z", "job-2", 1);
+        first_run.prune_older_runs(&mut entries);
+        assert_eq!(ids(&entries), ids(&[release.clone(), other_site.clone()]));
+
+        // 重试全部成功后不再需要基准；本任务之前的记录随后被覆盖
+        entries.insert(0, first_run.clone());
+        let retry = run("2026-02-01T00:20:00.000Z", "job-2", 0);
+        retry.prune_older_runs(&mut entries);
+        assert_eq!(ids(&entries), ids(&[first_run, other_site]));
+    }
+
+    #[test]
+    fn test_changelog_lists_changes_and_removed_pages() {
+        let entry = history_entry(
+            "2026-01-01T00:00:00.000Z",
+            vec![
+                previous_doc(0, "# Intro", ""),
+                previous_doc(1, "# Guide", ""),
+                previous_doc(3, "# Old", ""),
+            ],
+        );
+        let previous = PreviousTranslations::from_history(&entry).unwrap();
+        let links = vec![link(0), link(1), link(2)];
+        let documents = vec![
+            translated(0, DocumentChange::Unchanged),
+            translated(1, DocumentChange::Modified),
+            translated(2, DocumentChange::Added),
+        ];
+
+        let changelog = Changelog::build(&previous, &links, &documents);
+        assert_eq!(changelog.count(DocumentChange::Unchanged), 1);
+        assert_eq!(changelog.count(DocumentChange::Removed), 1);
+        assert_eq!(changelog.summary(), "新增 1, 更新 1, 未变 1, 移除 1");
+        assert_eq!(
+            changelog.to_markdown(),
+            "# 变更记录\n\n\
+             与 2026-01-01T00:00:00.000Z 的翻译相比: 新增 1, 更新 1, 未变 1, 移除 1\n\n\
+             ## 新增 (1 个)\n\n\
+             - [Page 2](https://example.com/docs/page-2)\n\n\
+             ## 更新 (1 个)\n\n\
+             - [Page 1](https://example.com/docs/page-1)\n\n\
+             ## 移除 (1 个)\n\n\
+             - [Page 3](https://example.com/docs/page-3)\n\n"
+        );
+    }
+
+    #[test]
+    fn test_paragraph_diff_reuses_unchanged_paragraphs() {
+        let previous_source =
+            "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n\nThird paragraph.";
+        let previous_translation = "# 标题\n\n第一段。\n\n第二段。\n\n第三段。";
+        let source = "# Title\n\nFirst paragraph.\n\nSecond paragraph, revised.\n\nInserted.\n\nThird paragraph.\n\nAppended.";
+
+        let diff = ParagraphDiff::new(source, previous_source, previous_translation).unwrap();
+        assert_eq!(diff.paragraph_count(), 6);
+        assert_eq!(diff.reused_count(), 3);
+        assert_eq!(diff.changed_count(), 3);
+        // 连续变化的段落合并为一段
+        assert_eq!(
+            diff.changed_segments(),
+            vec!["Second paragraph, revised.\n\nInserted.", "Appended."]
+        );
+        assert_eq!(
+            diff.assemble(&[
+                "第二段（修订）。\n\n插入。\n".to_string(),
+                "追加。".to_string()
+            ]),
+            "# 标题\n\n第一段。\n\n第二段（修订）。\n\n插入。\n\n第三段。\n\n追加。"
+        );
+    }
+
+    #[test]
+    fn test_paragraph_diff_keeps_code_blocks_whole() {
+        let previous_source = "Intro\n\n```rust\nfn a() {}\n\nfn b() {}\n```\n\nOutro";
+        let previous_translation = "介绍\n\n```rust\nfn a() {}\n\nfn b() {}\n```\n\n结尾";
+        let source = "Intro\n\n```rust\nfn a() {}\n\nfn c() {}\n```\n\nOutro";
+
+        let diff = ParagraphDiff::new(source, previous_source, previous_translation).unwrap();
+        assert_eq!(diff.paragraph_count(), 3);
+        assert_eq!(
+            diff.changed_segments(),
+            vec!["```rust\nfn a() {}\n\nfn c() {}\n```"]
+        );
+    }
+
+    #[test]
+    fn test_paragraph_diff_requires_aligned_previous_translation() {
+        // 上次的译文合并了段落，无法逐段对应
+        assert!(ParagraphDiff::new("A\n\nB", "A\n\nB", "甲乙").is_none());
+        assert!(ParagraphDiff::new("A", "", "").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_translate_changed_paragraphs_only_sends_changes() {
+        let translator = PrefixTranslator {
+            requests: Default::default(),
+        };
+        let diff = ParagraphDiff::new(
+            "kept\n\nchanged\n\nkept too\n\n```\nlet x = 1;\n```",
+            "kept\n\nold\n\nkept too",
+            "保留\n\n旧\n\n也保留",
+        )
+        .unwrap();
+
+        let outcome = translate_changed_paragraphs(&diff, &translator, "EN", "ZH")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome.text,
+            "保留\n\n[译] changed\n\n也保留\n\n[译] ```\nlet x = 1;\n```"
+        );
+        let requests = translator.requests.borrow();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0], "changed");
+        // 代码块以占位符发送，不会被翻译
+        assert!(!requests[1].contains("let x"));
+        assert_eq!(
+            outcome.chunks,
+            vec![
+                ChunkRecord {
+                    index: 0,
+                    engine: TranslationEngine::Mock,
+                },
+                ChunkRecord {
+                    index: 1,
+                    engine: TranslationEngine::Mock,
+                },
+            ]
+        );
+    }
+}
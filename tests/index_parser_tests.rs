@@ -0,0 +1,443 @@
+use url::Url;
+use url_translator::services::batch_service::DocumentLink;
+use url_translator::services::index_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(links: &[DocumentLink]) -> Vec<(String, String, usize)> {
+        links
+            .iter()
+            .map(|link| (link.title.clone(), link.url.clone(), link.level))
+            .collect()
+    }
+
+    fn item(title: &str, url: &str, level: usize) -> (String, String, usize) {
+        (title.to_string(), url.to_string(), level)
+    }
+
+    #[test]
+    fn test_detect_index_format() {
+        assert_eq!(
+            IndexFormat::detect(
+                "https://raw.githubusercontent.com/rust-lang/book/main/src/SUMMARY.md"
+            ),
+            Some(IndexFormat::MdBook)
+        );
+        assert_eq!(
+            IndexFormat::detect("https://example.com/gitbook/docs/SUMMARY.md"),
+            Some(IndexFormat::GitBook)
+        );
+        assert_eq!(
+            IndexFormat::detect("https://example.com/website/sidebars.ts"),
+            Some(IndexFormat::Docusaurus)
+        );
+        assert_eq!(
+            IndexFormat::detect("https://example.com/repo/mkdocs.yml"),
+            Some(IndexFormat::MkDocs)
+        );
+        assert_eq!(
+            IndexFormat::detect("https://example.com/docs/_sources/index.rst.txt"),
+            Some(IndexFormat::Sphinx)
+        );
+        assert_eq!(
+            IndexFormat::detect("https://requests.readthedocs.io/en/latest/"),
+            Some(IndexFormat::Sphinx)
+        );
+        assert_eq!(IndexFormat::detect("https://example.com/docs/"), None);
+    }
+
+    #[test]
+    fn test_raw_source_url() {
+        assert_eq!(
+            raw_source_url("https://github.com/rust-lang/book/blob/main/src/SUMMARY.md"),
+            "https://raw.githubusercontent.com/rust-lang/book/main/src/SUMMARY.md"
+        );
+        assert_eq!(
+            raw_source_url("https://github.com/rust-lang/book"),
+            "https://github.com/rust-lang/book"
+        );
+        assert_eq!(
+            raw_source_url("https://example.com/SUMMARY.md"),
+            "https://example.com/SUMMARY.md"
+        );
+    }
+
+    #[test]
+    fn test_parse_mdbook_summary() {
+        let content = r#"# Summary
+
+[Introduction](README.md)
+
+# User Guide
+
+- [Installation](guide/installation.md)
+    - [Pre-built \[binaries\]](guide/binaries.md)
+        - [Linux](guide/linux.md)
+    - [From source](guide/source.md)
+- [Draft chapter]()
+- [Reading Books](guide/reading.md#top)
+
+---
+
+- [External](https://example.com/other)
+[Contributors](misc/contributors.md)
+"#;
+        let base = Url::parse("https://example.com/book/src/SUMMARY.md").unwrap();
+        let links = parse_summary_md(content, &base);
+        assert_eq!(
+            summary(&links),
+            vec![
+                item("Introduction", "https://example.com/book/src/README.md", 0),
+                item(
+                    "Installation",
+                    "https://example.com/book/src/guide/installation.md",
+                    0
+                ),
+                item(
+                    "Pre-built [binaries]",
+                    "https://example.com/book/src/guide/binaries.md",
+                    1
+                ),
+                item("Linux", "https://example.com/book/src/guide/linux.md", 2),
+                item(
+                    "From source",
+                    "https://example.com/book/src/guide/source.md",
+                    1
+                ),
+                item(
+                    "Reading Books",
+                    "https://example.com/book/src/guide/reading.md",
+                    0
+                ),
+                item(
+                    "Contributors",
+                    "https://example.com/book/src/misc/contributors.md",
+                    0
+                ),
+            ]
+        );
+        assert_eq!(
+            links.iter().map(|link| link.order).collect::<Vec<_>>(),
+            (0..7).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_gitbook_summary() {
+        let content = "# Table of contents\n\n\
+                       * [Overview](README.md)\n\n\
+                       ## Getting Started\n\n\
+                       * [Quickstart](getting-started/quickstart.md)\n  \
+                       * [Setup](getting-started/setup.md)\n";
+        let links = IndexFormat::GitBook
+            .parse(content, "https://example.com/gitbook/SUMMARY.md")
+            .unwrap();
+        assert_eq!(
+            summary(&links),
+            vec![
+                item("Overview", "https://example.com/gitbook/README.md", 0),
+                item(
+                    "Quickstart",
+                    "https://example.com/gitbook/getting-started/quickstart.md",
+                    0
+                ),
+                item(
+                    "Setup",
+                    "https://example.com/gitbook/getting-started/setup.md",
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_docusaurus_sidebars_js() {
+        let content = r#"
+// @ts-check
+/** @type {import('@docusaurus/plugin-content-docs').SidebarsConfig} */
+const sidebars = {
+  tutorialSidebar: [
+    'intro',
+    {
+      type: 'category',
+      label: 'Tutorial - Basics',
+      link: {type: 'doc', id: 'tutorial-basics/index'},
+      items: [
+        'tutorial-basics/create-a-page',
+        {type: 'doc', id: 'tutorial-basics/markdown-features', label: "Markdown \"Features\""},
+        {
+          type: 'category',
+          label: 'Advanced',
+          items: ['tutorial-basics/advanced/plugins',],
+        },
+      ],
+    },
+    {type: 'link', label: 'Blog', href: 'https://example.com/blog'},
+    {type: 'autogenerated', dirName: 'extras'},
+  ],
+  apiSidebar: {
+    'API Reference': ['api/overview', 'intro'],
+  },
+};
+
+module.exports = sidebars;
+"#;
+        let links = IndexFormat::Docusaurus
+            .parse(content, "https://example.com/repo/website/sidebars.js")
+            .unwrap();
+        let docs = "https://example.com/repo/website/docs";
+        assert_eq!(
+            summary(&links),
+            vec![
+                item("Intro", &format!("{}/intro.md", docs), 0),
+                item(
+                    "Tutorial - Basics",
+                    &format!("{}/tutorial-basics/index.md", docs),
+                    0
+                ),
+                item(
+                    "Create a page",
+                    &format!("{}/tutorial-basics/create-a-page.md", docs),
+                    1
+                ),
+                item(
+                    "Markdown \"Features\"",
+                    &format!("{}/tutorial-basics/markdown-features.md", docs),
+                    1
+                ),
+                item(
+                    "Plugins",
+                    &format!("{}/tutorial-basics/advanced/plugins.md", docs),
+                    2
+                ),
+                item("Overview", &format!("{}/api/overview.md", docs), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_docusaurus_sidebars_ts() {
+        // Docusaurus TypeScript 模板，第一个 `{` 属于 import 语句
+        let content = r#"import type {SidebarsConfig} from '@docusaurus/plugin-content-docs';
+
+const sidebars: SidebarsConfig = {
+  tutorialSidebar: [
+    'intro',
+    {type: 'category', label: 'Guides', items: ['guides/setup']},
+  ],
+};
+
+export default sidebars;
+"#;
+        let links = IndexFormat::Docusaurus
+            .parse(content, "https://example.com/website/sidebars.ts")
+            .unwrap();
+        let docs = "https://example.com/website/docs";
+        assert_eq!(
+            summary(&links),
+            vec![
+                item("Intro", &format!("{}/intro.md", docs), 0),
+                item("Setup", &format!("{}/guides/setup.md", docs), 1),
+            ]
+        );
+
+        let exported = r#"import type {SidebarsConfig} from '@docusaurus/plugin-content-docs';
+export default {docs: ['intro']} satisfies SidebarsConfig;
+"#;
+        assert_eq!(
+            IndexFormat::Docusaurus
+                .parse(exported, "https://example.com/sidebars.ts")
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_parse_docusaurus_sidebars_json() {
+        let content = r#"{"docs": [{"type": "category", "label": "Guides", "items": ["guides/one", "guides/two"]}]}"#;
+        let links = IndexFormat::Docusaurus
+            .parse(content, "https://example.com/sidebars.json")
+            .unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].url, "https://example.com/docs/guides/one.md");
+        assert_eq!(links[1].level, 1);
+
+        assert!(IndexFormat::Docusaurus
+            .parse(
+                "module.exports = {docs: ['a',",
+                "https://example.com/sidebars.js"
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_docusaurus_sidebars_malformed() {
+        // 数组中多余的右括号返回错误，而不是反复解析同一位置
+        for content in [
+            "module.exports = { docs: ['a' } };",
+            "module.exports = { docs: ['a', ) ] };",
+        ] {
+            assert!(IndexFormat::Docusaurus
+                .parse(content, "https://example.com/sidebars.js")
+                .is_err());
+        }
+
+        // 字符串中的括号和逗号不影响跳过无法求值的表达式
+        let links = IndexFormat::Docusaurus
+            .parse(
+                "module.exports = { docs: [require(')', ','), 'intro'] };",
+                "https://example.com/sidebars.js",
+            )
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com/docs/intro.md");
+    }
+
+    #[test]
+    fn test_parse_mkdocs_nav() {
+        let content = r#"site_name: Example
+docs_dir: documentation/
+theme:
+  name: material
+nav:
+  - Home: index.md
+  - 'User Guide':
+      - Writing: user-guide/writing.md
+      - "Styling: themes": user-guide/styling.md # 主题
+      - Advanced:
+          - user-guide/plugins.md
+  - About:
+    - License: about/license.md
+    - GitHub: https://github.com/example/example
+plugins:
+  - search
+"#;
+        let links = parse_mkdocs_nav(
+            content,
+            &Url::parse("https://example.com/repo/mkdocs.yml").unwrap(),
+        );
+        let docs = "https://example.com/repo/documentation";
+        assert_eq!(
+            summary(&links),
+            vec![
+                item("Home", &format!("{}/index.md", docs), 0),
+                item("Writing", &format!("{}/user-guide/writing.md", docs), 1),
+                item(
+                    "Styling: themes",
+                    &format!("{}/user-guide/styling.md", docs),
+                    1
+                ),
+                item("Plugins", &format!("{}/user-guide/plugins.md", docs), 2),
+                item("License", &format!("{}/about/license.md", docs), 1),
+            ]
+        );
+
+        assert!(IndexFormat::MkDocs
+            .parse("site_name: Empty\n", "https://example.com/mkdocs.yml")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_sphinx_toctree() {
+        let content = r#"Welcome
+=======
+
+.. toctree::
+   :maxdepth: 2
+   :caption: Contents:
+
+   installation
+   Quick start <usage/quickstart>
+   self
+   api/*
+   Python <https://python.org>
+
+Indices
+-------
+
+.. toctree::
+   :hidden:
+
+   /changelog
+"#;
+        let base = Url::parse("https://example.com/docs/index.rst").unwrap();
+        assert_eq!(
+            summary(&parse_sphinx_toctree(content, &base)),
+            vec![
+                item(
+                    "Installation",
+                    "https://example.com/docs/installation.rst",
+                    0
+                ),
+                item(
+                    "Quick start",
+                    "https://example.com/docs/usage/quickstart.rst",
+                    0
+                ),
+                item("Changelog", "https://example.com/docs/changelog.rst", 0),
+            ]
+        );
+
+        // _sources 下的源文件带有 .rst.txt 后缀
+        let base = Url::parse("https://example.com/_sources/index.rst.txt").unwrap();
+        assert_eq!(
+            parse_sphinx_toctree(content, &base)[0].url,
+            "https://example.com/_sources/installation.rst.txt"
+        );
+    }
+
+    #[test]
+    fn test_parse_sphinx_html() {
+        let html = r#"<html><body>
+<div class="wy-menu wy-menu-vertical" role="navigation">
+  <ul class="current">
+    <li class="toctree-l1 current"><a class="reference internal" href="user/install.html">Installation</a>
+      <ul>
+        <li class="toctree-l2"><a class="reference internal" href="user/install.html#pip">Using pip</a></li>
+        <li class="toctree-l2"><a class="reference internal" href="user/advanced/">Advanced <code>Usage</code></a></li>
+      </ul>
+    </li>
+    <li class="toctree-l1"><a class="reference external" href="https://github.com/psf/requests">Source</a></li>
+    <li class="toctree-l1"><a class="reference internal" href="api.html">API</a></li>
+  </ul>
+</div>
+<div class="toctree-wrapper"><ul><li class="toctree-l1"><a href="api.html">API</a></li></ul></div>
+</body></html>"#;
+        let links = IndexFormat::Sphinx
+            .parse(html, "https://requests.readthedocs.io/en/latest/")
+            .unwrap();
+        assert_eq!(
+            summary(&links),
+            vec![
+                item(
+                    "Installation",
+                    "https://requests.readthedocs.io/en/latest/user/install.html",
+                    0
+                ),
+                item(
+                    "Advanced Usage",
+                    "https://requests.readthedocs.io/en/latest/user/advanced/",
+                    1
+                ),
+                item(
+                    "API",
+                    "https://requests.readthedocs.io/en/latest/api.html",
+                    0
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_title_from_slug() {
+        assert_eq!(
+            title_from_slug("guides/getting-started.md"),
+            "Getting started"
+        );
+        assert_eq!(title_from_slug("api_reference/"), "Api reference");
+        assert_eq!(title_from_slug(".hidden"), ".hidden");
+    }
+}
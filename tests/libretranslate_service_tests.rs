@@ -0,0 +1,122 @@
+mod common;
+
+use url_translator::error::AppError;
+use url_translator::services::libretranslate_service::*;
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{request_body, spawn_stub_server};
+
+    fn test_config(base_url: String) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.libretranslate.api_url = format!("{}/", base_url);
+        config.libretranslate.api_key = "secret".to_string();
+        config
+    }
+
+    #[tokio::test]
+    async fn test_translate_chunk_against_stub_server() {
+        let body = serde_json::json!({ "translatedText": "你好 __CODE_BLOCK_ab12__" }).to_string();
+        let (url, request_rx) = spawn_stub_server("200 OK", body).await;
+        let service = LibreTranslateService::new(&test_config(url));
+
+        let result = service
+            .translate_chunk("Hello __CODE_BLOCK_ab12__", "EN", "ZH")
+            .await
+            .unwrap();
+        assert_eq!(result, "你好 __CODE_BLOCK_ab12__");
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("POST /translate "));
+
+        let json: serde_json::Value = serde_json::from_str(request_body(&request)).unwrap();
+        assert_eq!(json["q"], "Hello __CODE_BLOCK_ab12__");
+        assert_eq!(json["source"], "en");
+        assert_eq!(json["target"], "zh");
+        assert_eq!(json["format"], "text");
+        assert_eq!(json["api_key"], "secret");
+    }
+
+    #[tokio::test]
+    async fn test_languages_against_stub_server() {
+        let body = serde_json::json!([
+            { "code": "en", "name": "English", "targets": ["zh", "ja"] },
+            { "code": "zh", "name": "Chinese", "targets": ["en"] }
+        ])
+        .to_string();
+        let (url, request_rx) = spawn_stub_server("200 OK", body).await;
+        let service = LibreTranslateService::new(&test_config(url));
+
+        let languages = service.languages().await.unwrap();
+        assert_eq!(languages.len(), 2);
+        assert_eq!(languages[0].code, "en");
+        assert_eq!(languages[0].targets, vec!["zh", "ja"]);
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("GET /languages "));
+    }
+
+    #[tokio::test]
+    async fn test_detect_language_sorted_by_confidence() {
+        let body = serde_json::json!([
+            { "confidence": 12.0, "language": "de" },
+            { "confidence": 90.0, "language": "en" }
+        ])
+        .to_string();
+        let (url, request_rx) = spawn_stub_server("200 OK", body).await;
+        let service = LibreTranslateService::new(&test_config(url));
+
+        let detected = service.detect_language("Hello world").await.unwrap();
+        assert_eq!(detected[0].language, "en");
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("POST /detect "));
+    }
+
+    #[tokio::test]
+    async fn test_error_status_mapping() {
+        let body = serde_json::json!({ "error": "Invalid API key" }).to_string();
+        let (url, _request_rx) = spawn_stub_server("403 Forbidden", body).await;
+        let service = LibreTranslateService::new(&test_config(url));
+
+        let error = service
+            .translate_chunk("Hello", "EN", "ZH")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::ConfigError { .. }));
+        assert!(error.to_string().contains("Invalid API key"));
+
+        let body = serde_json::json!({ "error": "Slowdown" }).to_string();
+        let (url, _request_rx) = spawn_stub_server("429 Too Many Requests", body).await;
+        let service = LibreTranslateService::new(&test_config(url));
+
+        let error = service
+            .translate_chunk("Hello", "EN", "ZH")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::RateLimitError { .. }));
+    }
+
+    #[test]
+    fn test_language_code_conversion() {
+        assert_eq!(to_libre_lang("ZH"), "zh");
+        assert_eq!(to_libre_lang("auto"), "auto");
+        assert_eq!(to_libre_lang("zh-Hans"), "zh-Hans");
+        assert_eq!(from_libre_lang("en"), "EN");
+        assert_eq!(from_libre_lang("zh-Hant"), "zh-Hant");
+    }
+
+    #[test]
+    fn test_endpoint_joins_paths() {
+        assert_eq!(
+            endpoint("http://localhost:5000/", "translate"),
+            "http://localhost:5000/translate"
+        );
+        assert_eq!(
+            endpoint("http://localhost:5000", "languages"),
+            "http://localhost:5000/languages"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::io::{Cursor, Write};
+use url_translator::services::content_processor::ContentProcessor;
+use url_translator::services::local_file_service::*;
+use zip::write::FileOptions;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"
+            xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <w:body>
+    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Release Notes</w:t></w:r></w:p>
+    <w:p>
+      <w:r><w:t xml:space="preserve">This release is </w:t></w:r>
+      <w:r><w:rPr><w:b/></w:rPr><w:t>much</w:t></w:r>
+      <w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve"> faster</w:t></w:r>
+      <w:r><w:t xml:space="preserve">. Read the </w:t></w:r>
+      <w:hyperlink r:id="rId5"><w:r><w:t>changelog</w:t></w:r></w:hyperlink>
+      <w:r><w:t>.</w:t></w:r>
+    </w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t>Changes</w:t></w:r></w:p>
+    <w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>Faster startup</w:t></w:r></w:p>
+    <w:p><w:pPr><w:numPr><w:ilvl w:val="1"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:rPr><w:i/></w:rPr><w:t>Lazy loading</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Code"/></w:pPr><w:r><w:t>cargo run --release</w:t></w:r></w:p>
+    <w:tbl>
+      <w:tr><w:tc><w:p><w:r><w:t>Name</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Value</w:t></w:r></w:p></w:tc></w:tr>
+      <w:tr><w:tc><w:p><w:r><w:t>Threads</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>4</w:t></w:r></w:p></w:tc></w:tr>
+    </w:tbl>
+    <w:p/>
+  </w:body>
+</w:document>"#;
+
+    const RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com/changelog" TargetMode="External"/>
+</Relationships>"#;
+
+    fn build_docx(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in entries {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_format_from_file_name() {
+        assert_eq!(
+            LocalFileFormat::from_file_name("README.MD"),
+            Some(LocalFileFormat::Markdown)
+        );
+        assert_eq!(
+            LocalFileFormat::from_file_name("page.htm"),
+            Some(LocalFileFormat::Html)
+        );
+        assert_eq!(
+            LocalFileFormat::from_file_name("报告.docx"),
+            Some(LocalFileFormat::Docx)
+        );
+        assert_eq!(LocalFileFormat::from_file_name("slides.pptx"), None);
+        assert_eq!(LocalFileFormat::from_file_name("Makefile"), None);
+    }
+
+    #[test]
+    fn test_markdown_file_keeps_content() {
+        let file = LocalFile {
+            name: "guide.md".to_string(),
+            bytes: "\u{feff}# Guide\r\n\r\nSee [setup](setup.md).\r\n"
+                .as_bytes()
+                .to_vec(),
+        };
+
+        let content = convert_local_file(&file).unwrap();
+        assert_eq!(content.title, "Guide");
+        assert_eq!(content.markdown, "# Guide\n\nSee [setup](setup.md).");
+        assert_eq!(content.canonical_url, "file:///guide.md");
+        assert_eq!(content.links[0].url, "file:///setup.md");
+    }
+
+    #[test]
+    fn test_text_file_uses_file_name_as_title() {
+        let file = LocalFile {
+            name: "notes.txt".to_string(),
+            bytes: b"Plain text notes.\n".to_vec(),
+        };
+
+        let content = convert_local_file(&file).unwrap();
+        assert_eq!(content.title, "notes");
+        assert_eq!(content.document(), "# notes\n\nPlain text notes.");
+    }
+
+    #[test]
+    fn test_html_file_is_converted_to_markdown() {
+        let html = r#"<html><head><title>Local Page</title></head><body>
+            <article><h1>Local Page</h1>
+            <p>This paragraph is long enough to be considered the main content of the page.</p>
+            <pre><code>let x = 1;</code></pre></article></body></html>"#;
+        let file = LocalFile {
+            name: "page.html".to_string(),
+            bytes: html.as_bytes().to_vec(),
+        };
+
+        let content = convert_local_file(&file).unwrap();
+        assert_eq!(content.title, "Local Page");
+        assert!(content.markdown.contains("# Local Page"));
+        assert!(content.markdown.contains("```\nlet x = 1;\n```"));
+        assert_eq!(content.canonical_url, "file:///page.html");
+    }
+
+    #[test]
+    fn test_docx_to_markdown() {
+        let bytes = build_docx(&[
+            ("word/document.xml", DOCUMENT_XML),
+            ("word/_rels/document.xml.rels", RELS_XML),
+        ]);
+
+        let (title, markdown) = docx_to_markdown(&bytes).unwrap();
+        assert_eq!(title, "Release Notes");
+        assert_eq!(
+            markdown,
+            "# Release Notes\n\n\
+             This release is **much faster**. Read the [changelog](https://example.com/changelog).\n\n\
+             ## Changes\n\n\
+             - Faster startup\n  - *Lazy loading*\n\n\
+             ```\ncargo run --release\n```\n\n\
+             | Name | Value |\n| --- | --- |\n| Threads | 4 |"
+        );
+    }
+
+    #[test]
+    fn test_docx_title_from_core_properties() {
+        let core = r#"<?xml version="1.0" encoding="UTF-8"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
+                   xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>Quarterly &amp; Annual</dc:title></cp:coreProperties>"#;
+        let bytes = build_docx(&[
+            ("word/document.xml", DOCUMENT_XML),
+            ("docProps/core.xml", core),
+        ]);
+
+        let content = convert_local_file(&LocalFile {
+            name: "report.docx".to_string(),
+            bytes,
+        })
+        .unwrap();
+        assert_eq!(content.title, "Quarterly & Annual");
+        // 没有关系文件时超链接保留为普通文字
+        assert!(content.markdown.contains("Read the changelog."));
+        assert!(content.links.is_empty());
+    }
+
+    #[test]
+    fn test_docx_code_is_protected_before_translation() {
+        let bytes = build_docx(&[("word/document.xml", DOCUMENT_XML)]);
+        let content = convert_local_file(&LocalFile {
+            name: "notes.docx".to_string(),
+            bytes,
+        })
+        .unwrap();
+
+        let mut processor = ContentProcessor::new();
+        let protected = processor.protect_code_blocks(&content.document());
+        assert!(!protected.contains("cargo run --release"));
+        assert_eq!(
+            processor.restore_code_blocks(&protected),
+            content.document()
+        );
+    }
+
+    #[test]
+    fn test_invalid_files_are_rejected() {
+        let unsupported = convert_local_file(&LocalFile {
+            name: "image.png".to_string(),
+            bytes: vec![0x89, 0x50],
+        });
+        assert!(unsupported.is_err());
+
+        let not_zip = convert_local_file(&LocalFile {
+            name: "broken.docx".to_string(),
+            bytes: b"not a zip archive".to_vec(),
+        });
+        assert!(not_zip.is_err());
+
+        let empty = convert_local_file(&LocalFile {
+            name: "empty.md".to_string(),
+            bytes: b"  \n".to_vec(),
+        });
+        assert!(empty.is_err());
+
+        let binary = convert_local_file(&LocalFile {
+            name: "latin1.txt".to_string(),
+            bytes: vec![0x63, 0x61, 0x66, 0xe9],
+        });
+        assert!(binary.is_err());
+    }
+
+    #[test]
+    fn test_pasted_text_content() {
+        let content = pasted_text_content(
+            "\r\n  # Ticket 42\r\n\r\nCrash on [startup](https://example.com/s).\r\n",
+        )
+        .unwrap();
+        assert_eq!(content.title, "Ticket 42");
+        assert_eq!(
+            content.markdown,
+            "# Ticket 42\n\nCrash on [startup](https://example.com/s)."
+        );
+        assert!(content.canonical_url.is_empty());
+        assert_eq!(content.links[0].url, "https://example.com/s");
+
+        let plain = pasted_text_content("just a sentence").unwrap();
+        assert!(plain.title.is_empty());
+        assert_eq!(plain.document(), "just a sentence");
+
+        assert!(pasted_text_content(" \n\t").is_err());
+    }
+}
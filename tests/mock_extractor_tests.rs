@@ -0,0 +1,89 @@
+use url_translator::services::crawler::{CrawlOptions, Crawler};
+use url_translator::services::extractor::{create_extractor, Extractor};
+use url_translator::services::mock_extractor::*;
+use url_translator::types::api_types::{AppConfig, TranslationEngine};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_extractor_is_deterministic() {
+        let extractor = MockExtractor::new();
+        let first = extractor
+            .extract("https://example.com/docs/")
+            .await
+            .unwrap();
+        let second = extractor
+            .extract("https://example.com/docs/")
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+
+        assert_eq!(first.title, "Docs");
+        assert!(first.markdown.starts_with("# Docs\n\n"));
+        assert!(first.markdown.contains("```rust\n"));
+        assert_eq!(
+            first
+                .links
+                .iter()
+                .map(|link| link.url.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "https://example.com/docs/getting-started",
+                "https://example.com/docs/configuration",
+                "https://example.com/docs/faq",
+            ]
+        );
+        // 正文中也列出链接，首页目录解析同样能离线发现文档
+        assert!(first
+            .markdown
+            .contains("- [FAQ](https://example.com/docs/faq)"));
+
+        assert!(extractor.extract("not a url").await.is_err());
+    }
+
+    #[test]
+    fn test_mock_engine_selects_mock_extractor() {
+        let config = AppConfig {
+            translation_engine: TranslationEngine::Mock,
+            ..AppConfig::default()
+        };
+        assert_eq!(create_extractor(&config).name(), "模拟提取器");
+        assert_ne!(create_extractor(&AppConfig::default()).name(), "模拟提取器");
+    }
+
+    #[test]
+    fn test_crawling_mock_pages_terminates() {
+        let extractor = MockExtractor::new();
+        let mut crawler = Crawler::new(
+            "https://example.com/docs/",
+            CrawlOptions {
+                max_depth: 5,
+                ..CrawlOptions::default()
+            },
+        )
+        .unwrap();
+        while let Some((url, depth)) = crawler.next_page() {
+            let content = extractor.sample_content(&url).unwrap();
+            crawler.visit(
+                &url,
+                depth,
+                &content.title,
+                content.links.iter().map(|link| link.url.as_str()),
+            );
+        }
+
+        // 子页面的链接指向同级页面，不会无限展开
+        assert_eq!(crawler.visited(), 4);
+        let titles: Vec<String> = crawler
+            .into_links()
+            .into_iter()
+            .map(|link| link.title)
+            .collect();
+        assert_eq!(
+            titles,
+            vec!["Docs", "Getting started", "Configuration", "Faq"]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use url_translator::error::AppError;
+use url_translator::services::batch_service::{BatchControl, BatchTranslationService};
+use url_translator::services::content_processor::ContentProcessor;
+use url_translator::services::fallback_translator::FallbackTranslator;
+use url_translator::services::mock_translator::*;
+use url_translator::services::preview_service::PreviewService;
+use url_translator::services::translator::*;
+use url_translator::types::api_types::{AppConfig, TranslationEngine};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_config(mock: MockConfig) -> AppConfig {
+        AppConfig {
+            translation_engine: TranslationEngine::Mock,
+            mock,
+            ..AppConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_pseudo_localize_keeps_markup() {
+        let text = "# Hello\n\n- Read __CODE_BLOCK_ab12__ at https://example.com/docs now";
+        let result = pseudo_localize(text);
+        let lines: Vec<&str> = result.lines().collect();
+
+        assert_eq!(lines[0], "# [Héllö ~~]");
+        assert_eq!(lines[1], "");
+        assert!(lines[2].starts_with("- [Réád __CODE_BLOCK_ab12__ át https://example.com/docs ñöw"));
+        assert!(lines[2].ends_with("~]"));
+    }
+
+    #[test]
+    fn test_reverse_keeps_placeholders() {
+        assert_eq!(
+            reverse_text("abc __CODE_BLOCK_ab12__ def\nxyz"),
+            " cba__CODE_BLOCK_ab12__fed \nzyx"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_runs_without_network() {
+        let config = mock_config(MockConfig {
+            mode: MockMode::Echo,
+            ..MockConfig::default()
+        });
+        let content = "Intro text.\n\n```rust\nfn main() {}\n```\n\nMore text.";
+
+        let mut processor = ContentProcessor::new();
+        let protected = processor.protect_code_blocks(content);
+        let translator = create_translator(&config);
+        let outcome = translator
+            .translate_with_report(&protected, "EN", "ZH", &|_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(processor.restore_code_blocks(&outcome.text), content);
+        assert_eq!(
+            outcome.engine_usage(),
+            vec![EngineUsage {
+                engine: TranslationEngine::Mock,
+                chunks: 1
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_translate_runs_without_network() {
+        let config = mock_config(MockConfig {
+            mode: MockMode::Echo,
+            ..MockConfig::default()
+        });
+        let service = BatchTranslationService::new(&config);
+
+        // 模拟提取器的首页列出三个示例页面
+        let links = service
+            .parse_document_index("https://example.com/docs/")
+            .await
+            .unwrap();
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| link.url.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "https://example.com/docs/getting-started",
+                "https://example.com/docs/configuration",
+                "https://example.com/docs/faq",
+            ]
+        );
+
+        let result = service
+            .batch_translate(links, &BatchControl::new(), |_| {}, |_, _| {})
+            .await
+            .unwrap();
+        assert!(result.failures.is_empty());
+        assert_eq!(result.documents.len(), 3);
+        for doc in &result.documents {
+            // 原样返回模式下译文与原文相同，代码块完整保留
+            assert_eq!(doc.translated_content, doc.original_content);
+            assert!(doc
+                .translated_content
+                .contains("println!(\"Hello, world!\");"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_comparison_runs_without_network() {
+        let config = mock_config(MockConfig {
+            mode: MockMode::Reverse,
+            ..MockConfig::default()
+        });
+        let comparison = PreviewService::new(&config)
+            .generate_comparison(
+                "https://example.com/docs/faq",
+                &config,
+                &[TranslationEngine::Mock],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(comparison.title, "Faq");
+        let column = &comparison.columns[0];
+        assert!(column.error.is_none());
+        assert_eq!(
+            column.paragraphs.len(),
+            comparison.original_paragraphs.len()
+        );
+        assert_eq!(comparison.original_paragraphs[2], "## Overview");
+        assert_eq!(column.paragraphs[2], "weivrevO ##");
+        // 代码块不经过翻译
+        let code = comparison
+            .original_paragraphs
+            .iter()
+            .position(|paragraph| paragraph.starts_with("```"))
+            .unwrap();
+        assert_eq!(
+            column.paragraphs[code],
+            comparison.original_paragraphs[code]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_injected_errors_are_deterministic() {
+        let translator = MockTranslator::new(&mock_config(MockConfig {
+            mode: MockMode::Reverse,
+            fail_every: 2,
+            error_kind: MockErrorKind::Config,
+            ..MockConfig::default()
+        }));
+
+        assert_eq!(translator.translate_chunk("abc").await.unwrap(), "cba");
+        let error = translator.translate_chunk("abc").await.unwrap_err();
+        assert!(matches!(error, AppError::ConfigError { .. }));
+        assert!(translator.translate_chunk("abc").await.is_ok());
+        assert!(translator.translate_chunk("abc").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_injected_errors_trigger_fallback() {
+        let failing = MockTranslator::new(&mock_config(MockConfig {
+            fail_every: 1,
+            latency_ms: 5,
+            ..MockConfig::default()
+        }));
+        let echo = MockTranslator::new(&mock_config(MockConfig {
+            mode: MockMode::Echo,
+            ..MockConfig::default()
+        }));
+        let chain = FallbackTranslator::from_translators(vec![Box::new(failing), Box::new(echo)]);
+
+        let result = chain.translate("Hello", "EN", "ZH").await.unwrap();
+        assert_eq!(result, "Hello");
+    }
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use std::cell::RefCell;
+use url_translator::error::AppError;
+use url_translator::services::ollama_service::*;
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{request_body, spawn_stub_server, spawn_stub_server_with_type};
+
+    fn stream_body(pieces: &[&str], chat: bool) -> String {
+        let mut lines: Vec<String> = pieces
+            .iter()
+            .map(|piece| {
+                if chat {
+                    serde_json::json!({
+                        "model": "test",
+                        "message": { "role": "assistant", "content": piece },
+                        "done": false
+                    })
+                } else {
+                    serde_json::json!({ "model": "test", "response": piece, "done": false })
+                }
+                .to_string()
+            })
+            .collect();
+        lines.push(serde_json::json!({ "model": "test", "done": true }).to_string());
+        lines.join("\n") + "\n"
+    }
+
+    fn test_config(base_url: String, endpoint: OllamaEndpoint) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.ollama.api_url = base_url;
+        config.ollama.model = "test".to_string();
+        config.ollama.endpoint = endpoint;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_stream_chunk_chat_reports_progress() {
+        let body = stream_body(&["你好", " __CODE_BLOCK_ab12__", " 世界"], true);
+        let (url, request_rx) =
+            spawn_stub_server_with_type("200 OK", "application/x-ndjson", body).await;
+        let service = OllamaService::new(&test_config(url, OllamaEndpoint::Chat));
+
+        let progress = RefCell::new(Vec::new());
+        let on_progress = |partial: &str| progress.borrow_mut().push(partial.to_string());
+        let result = service
+            .stream_chunk(
+                "Hello __CODE_BLOCK_ab12__ world",
+                "EN",
+                "ZH",
+                "前文\n\n",
+                &on_progress,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, "你好 __CODE_BLOCK_ab12__ 世界");
+
+        let progress = progress.into_inner();
+        assert!(!progress.is_empty());
+        assert!(progress.iter().all(|p| p.starts_with("前文\n\n")));
+        assert_eq!(
+            progress.last().unwrap(),
+            "前文\n\n你好 __CODE_BLOCK_ab12__ 世界"
+        );
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("POST /api/chat "));
+        let json: serde_json::Value = serde_json::from_str(request_body(&request)).unwrap();
+        assert_eq!(json["model"], "test");
+        assert_eq!(json["stream"], true);
+        assert_eq!(
+            json["messages"][1]["content"],
+            "Hello __CODE_BLOCK_ab12__ world"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_chunk_generate_endpoint() {
+        let body = stream_body(&["Bonjour", " le monde"], false);
+        let (url, request_rx) =
+            spawn_stub_server_with_type("200 OK", "application/x-ndjson", body).await;
+        let service = OllamaService::new(&test_config(url, OllamaEndpoint::Generate));
+
+        let result = service
+            .stream_chunk("Hello world", "EN", "FR", "", &|_| {})
+            .await
+            .unwrap();
+        assert_eq!(result, "Bonjour le monde");
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("POST /api/generate "));
+        let json: serde_json::Value = serde_json::from_str(request_body(&request)).unwrap();
+        assert_eq!(json["prompt"], "Hello world");
+        assert!(json["system"].as_str().unwrap().contains("French"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_is_config_error() {
+        let body = serde_json::json!({ "error": "model \"test\" not found" }).to_string();
+        let (url, _request_rx) = spawn_stub_server("404 Not Found", body).await;
+        let service = OllamaService::new(&test_config(url, OllamaEndpoint::Chat));
+
+        let error = service
+            .stream_chunk("Hello", "EN", "ZH", "", &|_| {})
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::ConfigError { .. }));
+        assert!(error.to_string().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_error_line_in_stream() {
+        let body = format!(
+            "{}\n{}\n",
+            serde_json::json!({ "response": "部分", "done": false }),
+            serde_json::json!({ "error": "out of memory" })
+        );
+        let (url, _request_rx) =
+            spawn_stub_server_with_type("200 OK", "application/x-ndjson", body).await;
+        let service = OllamaService::new(&test_config(url, OllamaEndpoint::Generate));
+
+        let error = service
+            .stream_chunk("Hello", "EN", "ZH", "", &|_| {})
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::ApiError { .. }));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_line_buffer_handles_split_lines_and_utf8() {
+        let line = serde_json::json!({ "response": "中文", "done": false }).to_string() + "\n";
+        let bytes = line.as_bytes();
+        // 在多字节字符中间切开
+        let split = line.find("中").unwrap() + 1;
+
+        let mut buffer = NdjsonLineBuffer::new();
+        assert!(buffer.push(&bytes[..split]).is_empty());
+        let lines = buffer.push(&bytes[split..]);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(parse_stream_line(&lines[0]).unwrap().content(), "中文");
+
+        assert!(buffer.push(b"{\"done\":true}").is_empty());
+        assert_eq!(buffer.finish().as_deref(), Some("{\"done\":true}"));
+        assert!(buffer.finish().is_none());
+    }
+
+    #[test]
+    fn test_build_requests() {
+        let config = OllamaConfig::default();
+
+        let chat = build_chat_request(&config, "Hello", "EN", "JA");
+        assert_eq!(chat.messages.len(), 2);
+        assert!(chat.messages[0].content.contains("Japanese"));
+        assert!(chat.stream);
+
+        let generate = build_generate_request(&config, "Hello", "EN", "JA");
+        assert_eq!(generate.prompt, "Hello");
+        assert!(generate.system.contains("Japanese"));
+        assert!(generate.stream);
+    }
+}
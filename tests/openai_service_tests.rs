@@ -0,0 +1,154 @@
+mod common;
+
+use url_translator::error::AppError;
+use url_translator::services::openai_service::*;
+use url_translator::services::translator::{ensure_placeholders, find_placeholders};
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{request_body, spawn_stub_server};
+
+    fn completion_body(content: &str) -> String {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop"
+            }]
+        })
+        .to_string()
+    }
+
+    fn test_config(base_url: String) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.openai.api_url = format!("{}/v1/chat/completions", base_url);
+        config.openai.api_key = "test-key".to_string();
+        config.openai.model = "local-model".to_string();
+        config
+    }
+
+    #[tokio::test]
+    async fn test_complete_against_stub_server() {
+        let (url, request_rx) =
+            spawn_stub_server("200 OK", completion_body("你好 __CODE_BLOCK_ab12__ 世界")).await;
+        let service = OpenAIService::new(&test_config(url));
+
+        let result = service
+            .complete("Hello __CODE_BLOCK_ab12__ world", "EN", "ZH")
+            .await
+            .unwrap();
+        assert_eq!(result, "你好 __CODE_BLOCK_ab12__ 世界");
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("POST /v1/chat/completions"));
+        assert!(request
+            .to_lowercase()
+            .contains("authorization: bearer test-key"));
+
+        let json: serde_json::Value = serde_json::from_str(request_body(&request)).unwrap();
+        assert_eq!(json["model"], "local-model");
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert!(json["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Simplified Chinese"));
+        assert_eq!(
+            json["messages"][1]["content"],
+            "Hello __CODE_BLOCK_ab12__ world"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_status_maps_to_rate_limit_error() {
+        let body = serde_json::json!({ "error": { "message": "slow down" } }).to_string();
+        let (url, _request_rx) = spawn_stub_server("429 Too Many Requests", body).await;
+        let service = OpenAIService::new(&test_config(url));
+
+        let error = service.complete("Hello", "EN", "ZH").await.unwrap_err();
+        assert!(matches!(error, AppError::RateLimitError { .. }));
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_missing_placeholder_is_retryable_error() {
+        let (url, _request_rx) = spawn_stub_server("200 OK", completion_body("你好 世界")).await;
+        let service = OpenAIService::new(&test_config(url));
+
+        let error = service
+            .complete("Hello __CODE_BLOCK_ab12__ world", "EN", "ZH")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::TranslationError { .. }));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_build_chat_request() {
+        let config = OpenAIConfig {
+            temperature: 0.5,
+            ..OpenAIConfig::default()
+        };
+        let request = build_chat_request(&config, "Hello", "auto", "JA");
+
+        assert_eq!(request.messages.len(), 2);
+        assert!(request.messages[0].content.contains("Japanese"));
+        assert!(!request.messages[0].content.contains("{target_lang}"));
+        assert_eq!(request.messages[1].content, "Hello");
+        assert_eq!(request.temperature, 0.5);
+        assert!(!request.stream);
+    }
+
+    #[test]
+    fn test_parse_chat_response_strips_outer_fence() {
+        let body = completion_body("```markdown\n# 标题\n\n正文\n```");
+        assert_eq!(parse_chat_response(&body).unwrap(), "# 标题\n\n正文");
+
+        // 正文中的代码块不应被去掉
+        let body = completion_body("```rust\nfn a() {}\n```\n\n说明\n\n```rust\nfn b() {}\n```");
+        assert!(parse_chat_response(&body).unwrap().starts_with("```rust"));
+    }
+
+    #[test]
+    fn test_parse_chat_response_errors() {
+        assert!(matches!(
+            parse_chat_response("not json"),
+            Err(AppError::ParseError { .. })
+        ));
+        assert!(matches!(
+            parse_chat_response(r#"{"choices": []}"#),
+            Err(AppError::TranslationError { .. })
+        ));
+        assert!(matches!(
+            parse_chat_response(&completion_body("   ")),
+            Err(AppError::TranslationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ensure_placeholders_repairs_escaped_underscores() {
+        let source = "See __CODE_BLOCK_ab12__ and __CODE_BLOCK_cd34__.";
+        let translated = r"参见 \_\_CODE\_BLOCK\_ab12\_\_ 和 __CODE_BLOCK_cd34__。";
+
+        let repaired = ensure_placeholders(source, translated).unwrap();
+        assert_eq!(
+            repaired,
+            "参见 __CODE_BLOCK_ab12__ 和 __CODE_BLOCK_cd34__。"
+        );
+    }
+
+    #[test]
+    fn test_find_placeholders() {
+        let text = "a __CODE_BLOCK_1f__ b __CODE_BLOCK_1f__ c __CODE_BLOCK_x d __CODE_BLOCK_99__";
+        assert_eq!(
+            find_placeholders(text),
+            vec![
+                "__CODE_BLOCK_1f__".to_string(),
+                "__CODE_BLOCK_99__".to_string()
+            ]
+        );
+    }
+}
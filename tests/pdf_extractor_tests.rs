@@ -0,0 +1,231 @@
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, Stream};
+use url_translator::services::local_file_service::{convert_local_file, LocalFile};
+use url_translator::services::pdf_extractor::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 页面上的一行：(字号, x, y, 文字)
+    type Line<'a> = (f32, f32, f32, &'a str);
+
+    fn build_pdf(pages: &[Vec<Line>], title: Option<&str>) -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let mut kids: Vec<Object> = Vec::new();
+        for lines in pages {
+            let mut operations = Vec::new();
+            for (size, x, y, text) in lines {
+                operations.push(Operation::new("BT", vec![]));
+                operations.push(Operation::new("Tf", vec!["F1".into(), (*size).into()]));
+                operations.push(Operation::new("Td", vec![(*x).into(), (*y).into()]));
+                operations.push(Operation::new("Tj", vec![Object::string_literal(*text)]));
+                operations.push(Operation::new("ET", vec![]));
+            }
+            let content = Content { operations };
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            let page_id = doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(page_id.into());
+        }
+
+        let count = kids.len() as i64;
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => count,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+        if let Some(title) = title {
+            let info_id = doc.add_object(dictionary! {
+                "Title" => Object::string_literal(title),
+            });
+            doc.trailer.set("Info", info_id);
+        }
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn line(text: &str, font_size: f32, y: f32) -> PdfLine {
+        PdfLine {
+            text: text.to_string(),
+            font_size,
+            y,
+        }
+    }
+
+    #[test]
+    fn test_is_pdf_url() {
+        assert!(is_pdf_url("https://example.com/docs/manual.PDF"));
+        assert!(is_pdf_url("https://example.com/manual.pdf?download=1"));
+        assert!(!is_pdf_url("https://example.com/pdf/guide.html"));
+        assert!(!is_pdf_url("not a url.pdf"));
+    }
+
+    #[test]
+    fn test_headings_paragraphs_and_page_markers() {
+        let pages = vec![PdfPage {
+            number: 1,
+            height: 842.0,
+            lines: vec![
+                line("Installation Guide", 20.0, 760.0),
+                line("Requirements", 14.0, 720.0),
+                line("The installer needs a recent operating sys-", 10.0, 700.0),
+                line("tem and about two gigabytes of free space.", 10.0, 688.0),
+                line("Run the installer as an administrator.", 10.0, 660.0),
+                line("• Windows 10 or later", 10.0, 640.0),
+                line("• macOS 12 or later", 10.0, 628.0),
+            ],
+        }];
+
+        assert_eq!(
+            pages_to_markdown(&pages),
+            "<!-- page 1 -->\n\n\
+             # Installation Guide\n\n\
+             ## Requirements\n\n\
+             The installer needs a recent operating system and about two gigabytes of free space.\n\n\
+             Run the installer as an administrator.\n\n\
+             - Windows 10 or later\n\n\
+             - macOS 12 or later"
+        );
+    }
+
+    #[test]
+    fn test_running_headers_and_footers_are_removed() {
+        let pages: Vec<PdfPage> = (1..=3)
+            .map(|number| PdfPage {
+                number,
+                height: 842.0,
+                lines: vec![
+                    line("ACME Corp — Product Manual", 8.0, 820.0),
+                    line(
+                        &format!("Body text on page {} goes here.", number),
+                        10.0,
+                        700.0,
+                    ),
+                    line(
+                        &format!("Another body line for page {}.", number),
+                        10.0,
+                        600.0,
+                    ),
+                    line(&format!("Page {} of 3", number), 8.0, 30.0),
+                ],
+            })
+            .collect();
+
+        let markdown = pages_to_markdown(&pages);
+        assert!(!markdown.contains("ACME Corp"));
+        assert!(!markdown.contains("of 3"));
+        assert!(markdown.contains("<!-- page 2 -->\n\nBody text on page 2 goes here."));
+        assert_eq!(markdown.matches("<!-- page").count(), 3);
+    }
+
+    #[test]
+    fn test_single_page_number_is_removed_but_body_kept() {
+        let pages = vec![PdfPage {
+            number: 1,
+            height: 842.0,
+            lines: vec![
+                line("第一章 概述", 16.0, 780.0),
+                line("本手册介绍系统的安装", 10.0, 700.0),
+                line("和配置方法。", 10.0, 688.0),
+                line("- 7 -", 9.0, 20.0),
+            ],
+        }];
+
+        assert_eq!(
+            pages_to_markdown(&pages),
+            "<!-- page 1 -->\n\n# 第一章 概述\n\n本手册介绍系统的安装和配置方法。"
+        );
+    }
+
+    #[test]
+    fn test_extract_from_generated_pdf() {
+        let bytes = build_pdf(
+            &[
+                vec![
+                    (8.0, 72.0, 810.0, "Vendor SDK Reference"),
+                    (20.0, 72.0, 760.0, "Getting Started"),
+                    (10.0, 72.0, 720.0, "Call the init function before"),
+                    (10.0, 72.0, 708.0, "any other API."),
+                    (8.0, 290.0, 30.0, "1"),
+                ],
+                vec![
+                    (8.0, 72.0, 810.0, "Vendor SDK Reference"),
+                    (14.0, 72.0, 760.0, "Configuration"),
+                    (10.0, 72.0, 720.0, "Settings are read from the environment."),
+                    (8.0, 290.0, 30.0, "2"),
+                ],
+            ],
+            Some("SDK Reference Manual"),
+        );
+
+        let content = extract_from_pdf(&bytes, "https://example.com/sdk.pdf").unwrap();
+        assert_eq!(content.title, "SDK Reference Manual");
+        assert_eq!(content.canonical_url, "https://example.com/sdk.pdf");
+        assert_eq!(
+            content.markdown,
+            "<!-- page 1 -->\n\n\
+             # Getting Started\n\n\
+             Call the init function before any other API.\n\n\
+             <!-- page 2 -->\n\n\
+             ## Configuration\n\n\
+             Settings are read from the environment."
+        );
+    }
+
+    #[test]
+    fn test_pdf_upload_uses_first_heading_as_title() {
+        let bytes = build_pdf(
+            &[vec![
+                (18.0, 72.0, 760.0, "Release Notes"),
+                (10.0, 72.0, 720.0, "Version two adds offline mode."),
+            ]],
+            None,
+        );
+
+        let content = convert_local_file(&LocalFile {
+            name: "notes.pdf".to_string(),
+            bytes,
+        })
+        .unwrap();
+        assert_eq!(content.title, "Release Notes");
+        assert_eq!(content.canonical_url, "file:///notes.pdf");
+        assert!(content
+            .markdown
+            .starts_with("<!-- page 1 -->\n\n# Release Notes"));
+    }
+
+    #[test]
+    fn test_invalid_pdf_is_rejected() {
+        assert!(extract_from_pdf(b"%PDF-1.4 truncated", "file:///broken.pdf").is_err());
+
+        let empty = build_pdf(&[vec![]], None);
+        assert!(extract_from_pdf(&empty, "file:///empty.pdf").is_err());
+    }
+}
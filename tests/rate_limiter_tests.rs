@@ -0,0 +1,37 @@
+use futures::future::join_all;
+use std::time::{Duration, Instant};
+use url_translator::services::rate_limiter::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acquire_waits_until_window_has_room() {
+        let limiter = RateLimiter::new(2, 200);
+        let started = Instant::now();
+
+        // 并发获取5次许可：每个200ms窗口最多放行2次，最后一次至少要等两个窗口
+        let mut finished: Vec<Duration> = join_all((0..5).map(|_| async {
+            limiter.acquire().await.unwrap();
+            started.elapsed()
+        }))
+        .await;
+
+        finished.sort();
+        assert!(finished[1] < Duration::from_millis(100));
+        assert!(finished[2] >= Duration::from_millis(195));
+        assert!(finished[4] >= Duration::from_millis(395));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_allows_requests_after_window_passes() {
+        let limiter = RateLimiter::new(1, 50);
+        limiter.acquire().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        let started = Instant::now();
+        limiter.acquire().await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use url_translator::services::content_processor::ContentProcessor;
+use url_translator::services::readability_extractor::*;
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::spawn_stub_server_with_type;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <title>Async Guide | Example Docs</title>
+  <link rel="canonical" href="/docs/async-guide">
+</head>
+<body>
+  <nav class="top-nav"><a href="/">Home</a> <a href="/docs">Docs</a> <a href="/blog">Blog</a></nav>
+  <div class="sidebar"><ul><li><a href="/docs/a">A link in the sidebar menu</a></li></ul></div>
+  <div id="main-content" class="article-body">
+    <h1>Async Guide</h1>
+    <p>Async functions let you write <strong>non-blocking</strong> code, which reads like ordinary sequential code, and runs efficiently.</p>
+    <p>See the <a href="../reference/futures.html">futures reference</a> for details, examples, and edge cases.</p>
+    <h2>Example</h2>
+    <pre><code class="language-rust">async fn fetch() -> u32 {
+    42
+}</code></pre>
+    <ul>
+      <li>First point</li>
+      <li>Second point
+        <ol><li>Nested step</li></ol>
+      </li>
+    </ul>
+    <table>
+      <tr><th>Name</th><th>Type</th></tr>
+      <tr><td>fetch</td><td>async fn</td></tr>
+    </table>
+    <p><img src="images/flow.png" alt="Flow chart"></p>
+  </div>
+  <footer class="site-footer"><p>Copyright notice, privacy policy, terms of service and more.</p></footer>
+  <script>console.log("tracking")</script>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_extracts_main_content_as_markdown() {
+        let content = extract_from_html(PAGE, "https://example.com/docs/async-guide?ref=home");
+
+        assert_eq!(content.title, "Async Guide | Example Docs");
+        assert_eq!(
+            content.canonical_url,
+            "https://example.com/docs/async-guide"
+        );
+        assert_eq!(content.language.as_deref(), Some("en"));
+
+        let markdown = &content.markdown;
+        assert!(markdown
+            .starts_with("# Async Guide\n\nAsync functions let you write **non-blocking** code"));
+        assert!(markdown.contains("## Example"));
+        assert!(markdown.contains("```rust\nasync fn fetch() -> u32 {\n    42\n}\n```"));
+        assert!(markdown.contains("- First point\n- Second point\n\n  1. Nested step"));
+        assert!(markdown.contains("| Name | Type |\n| --- | --- |\n| fetch | async fn |"));
+        assert!(markdown.contains("![Flow chart](https://example.com/docs/images/flow.png)"));
+        assert!(
+            markdown.contains("[futures reference](https://example.com/reference/futures.html)")
+        );
+
+        assert!(!markdown.contains("sidebar menu"));
+        assert!(!markdown.contains("Copyright"));
+        assert!(!markdown.contains("tracking"));
+
+        // 与Jina的Markdown一样交给代码块保护处理
+        let mut processor = ContentProcessor::new();
+        let protected = processor.protect_code_blocks(markdown);
+        assert!(!protected.contains("async fn fetch"));
+        assert_eq!(processor.restore_code_blocks(&protected), *markdown);
+
+        assert_eq!(content.links.len(), 1);
+        assert_eq!(
+            content.links[0].url,
+            "https://example.com/reference/futures.html"
+        );
+    }
+
+    #[test]
+    fn test_blockquote_and_inline_markup() {
+        let html = format!(
+            "<html><body><article>{}</article></body></html>",
+            "<blockquote><p>Quoted <em>text</em></p><p>Second</p></blockquote><p>Use <code>cargo build</code><br>now</p>"
+        );
+        let markdown = extract_from_html(&html, "https://example.com/").markdown;
+        assert_eq!(
+            markdown,
+            "> Quoted *text*\n>\n> Second\n\nUse `cargo build`\nnow"
+        );
+    }
+
+    #[test]
+    fn test_proxied_url() {
+        assert_eq!(
+            proxied_url("https://proxy.example/raw?url={url}", "https://a.com/x?y=1"),
+            "https://proxy.example/raw?url=https%3A%2F%2Fa.com%2Fx%3Fy%3D1"
+        );
+        assert_eq!(
+            proxied_url("https://proxy.example/?", "https://a.com"),
+            "https://proxy.example/?https%3A%2F%2Fa.com"
+        );
+        assert_eq!(proxied_url("", "https://a.com"), "https://a.com");
+    }
+
+    #[test]
+    fn test_markdown_pages_are_kept_as_is() {
+        let page = FetchedPage {
+            content_type: "text/markdown; charset=utf-8".to_string(),
+            body: "# Readme\n\nSee [docs](docs/index.md).".to_string(),
+        };
+        let content = parse_page(&page, "https://example.com/repo/README.md");
+        assert_eq!(content.title, "Readme");
+        assert_eq!(content.markdown, "# Readme\n\nSee [docs](docs/index.md).");
+        assert_eq!(
+            content.links[0].url,
+            "https://example.com/repo/docs/index.md"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_against_stub_server() {
+        let (url, request_rx) =
+            spawn_stub_server_with_type("200 OK", "text/html; charset=utf-8", PAGE.to_string())
+                .await;
+        let extractor = ReadabilityExtractor::new(&AppConfig::default());
+
+        let page = extractor
+            .fetch_page(&format!("{}/docs/async-guide", url))
+            .await
+            .unwrap();
+        assert!(page.content_type.starts_with("text/html"));
+        assert_eq!(page.body, PAGE);
+
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with("GET /docs/async-guide "));
+    }
+}
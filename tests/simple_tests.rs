@@ -1,4 +1,5 @@
 use url_translator::hooks::use_translation::TranslationStatus;
+use url_translator::services::translator::{create_translator, split_text_into_chunks};
 use url_translator::theme::*;
 use url_translator::types::api_types::*;
 
@@ -210,4 +211,39 @@ mod tests {
             assert!(!theme.text.is_empty());
         }
     }
+
+    #[test]
+    fn test_translator_from_config() {
+        let config = AppConfig::default();
+        assert_eq!(config.translation_engine, TranslationEngine::DeepLX);
+
+        let translator = create_translator(&config);
+        let capabilities = translator.capabilities();
+        assert_eq!(capabilities.engine, TranslationEngine::DeepLX);
+        assert_eq!(capabilities.max_text_length, config.max_text_length);
+        assert_eq!(translator.name(), "DeepLX");
+    }
+
+    #[test]
+    fn test_config_without_engine_field() {
+        // 旧版本保存的配置没有translation_engine字段
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("translation_engine");
+
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.translation_engine, TranslationEngine::DeepLX);
+    }
+
+    #[test]
+    fn test_split_text_into_chunks_multibyte() {
+        let text = "这是一个很长的中文句子。".repeat(100);
+        let chunks = split_text_into_chunks(&text, 100);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 100);
+            assert!(chunk.ends_with('。'));
+        }
+        assert_eq!(chunks.concat(), text);
+    }
 }
@@ -0,0 +1,316 @@
+mod common;
+
+use chrono::NaiveDate;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::Write;
+use url_translator::services::sitemap_service::*;
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::spawn_stub_routes;
+
+    fn entry(loc: &str, lastmod: Option<&str>) -> SitemapEntry {
+        SitemapEntry {
+            loc: loc.to_string(),
+            lastmod: lastmod.map(|s| s.to_string()),
+        }
+    }
+
+    fn urlset(base: &str, paths: &[(&str, &str)]) -> String {
+        let urls: String = paths
+            .iter()
+            .map(|(path, lastmod)| {
+                format!(
+                    "<url><loc>{}{}</loc><lastmod>{}</lastmod></url>",
+                    base, path, lastmod
+                )
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">{}</urlset>"#,
+            urls
+        )
+    }
+
+    #[test]
+    fn test_parse_urlset_and_index() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/docs/?a=1&amp;b=2</loc><lastmod>2024-03-01</lastmod></url>
+  <url><loc><![CDATA[https://example.com/docs/intro]]></loc></url>
+  <url><lastmod>2024-03-01</lastmod></url>
+</urlset>"#;
+        assert_eq!(
+            parse_sitemap(xml).unwrap(),
+            Sitemap::UrlSet(vec![
+                entry("https://example.com/docs/?a=1&b=2", Some("2024-03-01")),
+                entry("https://example.com/docs/intro", None),
+            ])
+        );
+
+        let index = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/sitemap-docs.xml</loc><lastmod>2024-01-01T08:00:00+00:00</lastmod></sitemap>
+</sitemapindex>"#;
+        assert_eq!(
+            parse_sitemap(index).unwrap(),
+            Sitemap::Index(vec![entry(
+                "https://example.com/sitemap-docs.xml",
+                Some("2024-01-01T08:00:00+00:00")
+            )])
+        );
+
+        assert!(parse_sitemap("<html><body>Not found</body></html>").is_err());
+    }
+
+    #[test]
+    fn test_parse_lastmod() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 20).unwrap();
+        assert_eq!(parse_lastmod("2024-05-20"), Some(date));
+        assert_eq!(parse_lastmod("2024-05-20T23:10:00+08:00"), Some(date));
+        assert_eq!(parse_lastmod("2024-05-20T10:00Z"), Some(date));
+        assert_eq!(parse_lastmod("yesterday"), None);
+    }
+
+    #[test]
+    fn test_robots_rules() {
+        let robots = RobotsRules::parse(
+            "User-agent: Googlebot\n\
+             Disallow: /\n\
+             \n\
+             User-agent: *\n\
+             Disallow: /docs/internal/\n\
+             Allow: /docs/internal/public.html\n\
+             Disallow: /*.pdf$\n\
+             Disallow: /search?\n\
+             Disallow:\n\
+             \n\
+             Sitemap: https://example.com/sitemap_index.xml # 主站点地图\n",
+        );
+
+        assert!(robots.is_allowed("/docs/guide/"));
+        assert!(!robots.is_allowed("/docs/internal/notes.html"));
+        assert!(robots.is_allowed("/docs/internal/public.html"));
+        assert!(!robots.is_allowed("/docs/manual.pdf"));
+        assert!(robots.is_allowed("/docs/manual.pdf.html"));
+        assert!(!robots.is_allowed("/search?q=install"));
+        assert_eq!(
+            robots.sitemaps,
+            vec!["https://example.com/sitemap_index.xml".to_string()]
+        );
+
+        assert!(RobotsRules::parse("").is_allowed("/anything"));
+    }
+
+    #[test]
+    fn test_sitemap_links_filter_and_levels() {
+        let entries = vec![
+            entry(
+                "https://example.com/docs/guide/install.html",
+                Some("2024-06-01"),
+            ),
+            entry("https://example.com/docs/", Some("2024-06-01")),
+            entry("https://example.com/docs/guide/", None),
+            entry(
+                "https://example.com/docs/guide/advanced/tuning-tips",
+                Some("2024-07-15"),
+            ),
+            entry("https://example.com/docs/old-page", Some("2023-01-01")),
+            entry("https://example.com/docs/private/keys", Some("2024-06-01")),
+            entry("https://example.com/blog/news", Some("2024-06-01")),
+            entry("https://other.com/docs/external", Some("2024-06-01")),
+            entry("https://example.com/docs/logo.png", Some("2024-06-01")),
+            entry(
+                "https://example.com/docs/guide/install.html#top",
+                Some("2024-06-01"),
+            ),
+        ];
+        let robots = RobotsRules::parse("User-agent: *\nDisallow: /docs/private/");
+        let filter = SitemapFilter {
+            modified_since: NaiveDate::from_ymd_opt(2024, 1, 1),
+            ..SitemapFilter::default()
+        };
+
+        let links = sitemap_links(
+            &entries,
+            "https://example.com/docs/",
+            &filter,
+            Some(&robots),
+        );
+        let summary: Vec<(&str, &str, usize, usize)> = links
+            .iter()
+            .map(|link| {
+                (
+                    link.title.as_str(),
+                    link.url.as_str(),
+                    link.level,
+                    link.order,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("example.com", "https://example.com/docs/", 0, 0),
+                ("Guide", "https://example.com/docs/guide/", 0, 1),
+                (
+                    "Tuning tips",
+                    "https://example.com/docs/guide/advanced/tuning-tips",
+                    2,
+                    2
+                ),
+                (
+                    "Install",
+                    "https://example.com/docs/guide/install.html",
+                    1,
+                    3
+                ),
+            ]
+        );
+
+        // 指定前缀且不遵守 robots.txt
+        let filter = SitemapFilter {
+            path_prefix: "docs/private".to_string(),
+            modified_since: None,
+            respect_robots: false,
+        };
+        let links = sitemap_links(&entries, "https://example.com/docs/", &filter, None);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com/docs/private/keys");
+    }
+
+    #[tokio::test]
+    async fn test_discover_from_robots_and_sitemap_index() {
+        let base = spawn_stub_routes(|base| {
+            let robots = format!(
+                "User-agent: *\nDisallow: /docs/drafts/\nSitemap: {}/sitemap_index.xml\n",
+                base
+            );
+            let index = format!(
+                r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>{0}/sitemap-docs.xml.gz</loc></sitemap>
+  <sitemap><loc>{0}/missing.xml</loc></sitemap>
+</sitemapindex>"#,
+                base
+            );
+            let docs = urlset(
+                base,
+                &[
+                    ("/docs/setup/linux", "2024-02-01"),
+                    ("/docs/intro", "2024-02-01"),
+                    ("/docs/drafts/todo", "2024-02-01"),
+                    ("/about", "2024-02-01"),
+                ],
+            );
+            let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+            gz.write_all(docs.as_bytes()).unwrap();
+
+            vec![
+                ("/robots.txt", "text/plain", robots.into_bytes()),
+                ("/sitemap_index.xml", "application/xml", index.into_bytes()),
+                (
+                    "/sitemap-docs.xml.gz",
+                    "application/gzip",
+                    gz.finish().unwrap(),
+                ),
+            ]
+        })
+        .await;
+
+        let service = SitemapService::new(&AppConfig::default());
+        let links = service
+            .discover_links(&format!("{}/docs/", base), &SitemapFilter::default())
+            .await
+            .unwrap();
+        let urls: Vec<String> = links.iter().map(|link| link.url.clone()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                format!("{}/docs/intro", base),
+                format!("{}/docs/setup/linux", base),
+            ]
+        );
+        assert_eq!(links[1].level, 1);
+    }
+
+    #[tokio::test]
+    async fn test_discover_falls_back_to_default_sitemap() {
+        let base = spawn_stub_routes(|base| {
+            vec![(
+                "/sitemap.xml",
+                "application/xml",
+                urlset(base, &[("/guide/start", "2024-02-01")]).into_bytes(),
+            )]
+        })
+        .await;
+
+        let service = SitemapService::new(&AppConfig::default());
+        let links = service
+            .discover_links(&format!("{}/", base), &SitemapFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].title, "Start");
+
+        // 直接给出的站点地图不存在时报错
+        let missing = service
+            .discover_links(
+                &format!("{}/docs/sitemap.xml", base),
+                &SitemapFilter::default(),
+            )
+            .await;
+        assert!(missing.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discover_skips_unreadable_sitemaps() {
+        // 无法解压的内容会导致解析错误
+        let corrupt = vec![0x1f, 0x8b, 0x00, 0x01, 0x02];
+        let base = spawn_stub_routes(|base| {
+            let robots = format!(
+                "Sitemap: {0}/broken.xml.gz\nSitemap: {0}/sitemap_index.xml\n",
+                base
+            );
+            let index = format!(
+                r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>{0}/corrupt-child.xml.gz</loc></sitemap>
+  <sitemap><loc>{0}/sitemap-docs.xml</loc></sitemap>
+</sitemapindex>"#,
+                base
+            );
+            vec![
+                ("/robots.txt", "text/plain", robots.into_bytes()),
+                ("/broken.xml.gz", "application/gzip", corrupt.clone()),
+                ("/sitemap_index.xml", "application/xml", index.into_bytes()),
+                ("/corrupt-child.xml.gz", "application/gzip", corrupt),
+                (
+                    "/sitemap-docs.xml",
+                    "application/xml",
+                    urlset(base, &[("/docs/intro", "2024-02-01")]).into_bytes(),
+                ),
+            ]
+        })
+        .await;
+
+        let service = SitemapService::new(&AppConfig::default());
+        let links = service
+            .discover_links(&format!("{}/docs/", base), &SitemapFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, format!("{}/docs/intro", base));
+
+        // 一个站点地图都读不到时报告最后的错误
+        let error = service
+            .discover_links(
+                &format!("{}/broken.xml.gz", base),
+                &SitemapFilter::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("解压"));
+    }
+}
@@ -0,0 +1,155 @@
+use chrono::{TimeZone, Utc};
+use url_translator::services::translation_memory::*;
+use url_translator::types::api_types::{AppConfig, TranslationEngine};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, engine: TranslationEngine, last_used_at: &str, hits: u32) -> MemoryEntry {
+        MemoryEntry {
+            key: key.to_string(),
+            engine,
+            source_lang: "EN".to_string(),
+            target_lang: "ZH".to_string(),
+            source: "Hello".to_string(),
+            translation: "你好".to_string(),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            last_used_at: last_used_at.to_string(),
+            hits,
+        }
+    }
+
+    #[test]
+    fn test_segment_normalizes_whitespace() {
+        let segment = MemorySegment::new("  Hello   world \n\tsecond  line  ");
+        assert_eq!(segment.normalized(), "Hello world\nsecond line");
+        assert_eq!(
+            segment.key(TranslationEngine::DeepLX, "EN", "ZH"),
+            MemorySegment::new("Hello world\nsecond line").key(
+                TranslationEngine::DeepLX,
+                "EN",
+                "ZH"
+            )
+        );
+    }
+
+    #[test]
+    fn test_key_separates_language_pair_and_engine() {
+        let segment = MemorySegment::new("Hello world");
+        let key = segment.key(TranslationEngine::DeepLX, "EN", "ZH");
+        assert!(key.starts_with("DeepLX:EN:ZH:"));
+        assert_ne!(key, segment.key(TranslationEngine::DeepLX, "EN", "JA"));
+        assert_ne!(key, segment.key(TranslationEngine::DeepL, "EN", "ZH"));
+    }
+
+    #[test]
+    fn test_placeholders_are_canonicalized_and_restored() {
+        let first = MemorySegment::new("Run __CODE_BLOCK_aaa111__ then __CODE_BLOCK_bbb222__");
+        let second = MemorySegment::new("Run __CODE_BLOCK_ccc333__ then __CODE_BLOCK_ddd444__");
+        assert_eq!(
+            first.normalized(),
+            "Run __CODE_BLOCK_0__ then __CODE_BLOCK_1__"
+        );
+        // 不同次提取生成的随机占位符命中同一条记忆
+        assert_eq!(
+            first.key(TranslationEngine::DeepLX, "EN", "ZH"),
+            second.key(TranslationEngine::DeepLX, "EN", "ZH")
+        );
+
+        let stored = first
+            .canonical_translation("先运行 __CODE_BLOCK_aaa111__ 再运行 __CODE_BLOCK_bbb222__ ")
+            .unwrap();
+        assert_eq!(stored, "先运行 __CODE_BLOCK_0__ 再运行 __CODE_BLOCK_1__");
+        assert_eq!(
+            second.restore_translation(&stored),
+            "先运行 __CODE_BLOCK_ccc333__ 再运行 __CODE_BLOCK_ddd444__"
+        );
+    }
+
+    #[test]
+    fn test_translation_with_unknown_placeholder_is_not_stored() {
+        let segment = MemorySegment::new("Run __CODE_BLOCK_aaa111__");
+        assert_eq!(
+            segment.canonical_translation("运行 __CODE_BLOCK_zzz999__"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stale_entries() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 31, 0, 0, 0).unwrap();
+        let entries = vec![
+            entry(
+                "recent",
+                TranslationEngine::DeepLX,
+                "2026-03-20T00:00:00+00:00",
+                1,
+            ),
+            entry(
+                "old",
+                TranslationEngine::DeepLX,
+                "2026-02-01T00:00:00+00:00",
+                5,
+            ),
+            entry("broken", TranslationEngine::DeepLX, "not a date", 0),
+        ];
+        assert_eq!(stale_entries(&entries, now, 30), vec!["old", "broken"]);
+        assert_eq!(
+            stale_entries(&entries, now, 0),
+            vec!["recent", "old", "broken"]
+        );
+    }
+
+    #[test]
+    fn test_summary_counts_entries_by_pair() {
+        let entries = vec![
+            entry(
+                "a",
+                TranslationEngine::DeepLX,
+                "2026-03-20T00:00:00+00:00",
+                2,
+            ),
+            entry(
+                "b",
+                TranslationEngine::DeepLX,
+                "2026-03-20T00:00:00+00:00",
+                3,
+            ),
+            entry(
+                "c",
+                TranslationEngine::DeepL,
+                "2026-03-20T00:00:00+00:00",
+                0,
+            ),
+        ];
+        let summary = MemorySummary::from_entries(&entries);
+        assert_eq!(summary.entries, 3);
+        assert_eq!(summary.total_hits, 5);
+        assert_eq!(summary.by_pair.len(), 2);
+        assert_eq!(summary.by_pair[0].1, 2);
+        assert_eq!(MemorySummary::from_entries(&[]), MemorySummary::default());
+    }
+
+    #[test]
+    fn test_stats_hit_rate() {
+        assert_eq!(MemoryStats::default().hit_rate(), 0.0);
+        let stats = MemoryStats { hits: 3, misses: 1 };
+        assert_eq!(stats.lookups(), 4);
+        assert_eq!(stats.hit_rate(), 75.0);
+    }
+
+    #[test]
+    fn test_memory_enabled_by_default() {
+        assert!(AppConfig::default().translation_memory.enabled);
+
+        // 旧版本保存的配置没有 translation_memory 字段
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("translation_memory");
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            config.translation_memory,
+            TranslationMemoryConfig::default()
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::Duration;
+use url_translator::error::AppError;
+use url_translator::services::translator::translate_chunks_concurrently;
+use url_translator::types::api_types::AppConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("chunk {}", i)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_chunks_translated_concurrently_in_order() {
+        let chunks = chunks(8);
+        let running = Cell::new(0);
+        let peak = Cell::new(0);
+
+        let translated = translate_chunks_concurrently(&chunks, 3, 1, |i, chunk| {
+            let running = &running;
+            let peak = &peak;
+            async move {
+                running.set(running.get() + 1);
+                peak.set(peak.get().max(running.get()));
+                // 靠前的文本块耗时更长，完成顺序与原顺序相反
+                tokio::time::sleep(Duration::from_millis(40 - i as u64 * 4)).await;
+                running.set(running.get() - 1);
+                Ok(chunk.to_uppercase())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(peak.get(), 3);
+        assert_eq!(
+            translated,
+            (0..8).map(|i| format!("CHUNK {}", i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_chunk_is_retried_alone() {
+        let chunks = chunks(5);
+        let attempts = RefCell::new(HashMap::new());
+
+        let translated = translate_chunks_concurrently(&chunks, 2, 3, |i, chunk| {
+            let attempt = {
+                let mut attempts = attempts.borrow_mut();
+                let count = attempts.entry(i).or_insert(0);
+                *count += 1;
+                *count
+            };
+            async move {
+                if i == 2 && attempt < 3 {
+                    Err(AppError::network("连接超时"))
+                } else {
+                    Ok(format!("译文 {}", chunk))
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(translated[2], "译文 chunk 2");
+        assert_eq!(translated.len(), 5);
+        let attempts = attempts.into_inner();
+        assert_eq!(attempts[&2], 3);
+        assert!((0..5).filter(|i| *i != 2).all(|i| attempts[&i] == 1));
+    }
+
+    #[tokio::test]
+    async fn test_chunk_failing_every_round_returns_error() {
+        let chunks = chunks(3);
+        let calls = Cell::new(0);
+
+        let result = translate_chunks_concurrently(&chunks, 4, 2, |i, _chunk| {
+            calls.set(calls.get() + 1);
+            async move {
+                if i == 1 {
+                    Err(AppError::config("未配置API地址"))
+                } else {
+                    Ok(String::new())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Err(AppError::config("未配置API地址")));
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn test_chunk_concurrency_default() {
+        assert_eq!(AppConfig::default().chunk_concurrency, 3);
+
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("chunk_concurrency");
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.chunk_concurrency, 3);
+    }
+}